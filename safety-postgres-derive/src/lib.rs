@@ -0,0 +1,297 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr};
+
+/// Derives `safety_postgres::converter::row_converter::FromRow` for a struct with named fields.
+///
+/// Each field is read from the column of the same name. Use `#[from_row(rename = "column")]`
+/// to read a field from a column with another name.
+#[proc_macro_derive(FromRow, attributes(from_row))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_from_row(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_from_row(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(input, "FromRow can only be derived for a struct with named fields")),
+        },
+        _ => return Err(Error::new_spanned(input, "FromRow can only be derived for a struct")),
+    };
+
+    let mut field_readers = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field should have an ident");
+        let column_name = column_name(field)?.unwrap_or_else(|| ident.to_string());
+        field_readers.push(quote! {
+            #ident: row.try_get(#column_name).map_err(|e| {
+                ::safety_postgres::utils::errors::ExecutorError::ConvertRowError(
+                    format!("'{}' column can not be converted due to {}", #column_name, e))
+            })?
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::safety_postgres::converter::row_converter::FromRow for #name #type_generics #where_clause {
+            fn from_row(row: ::safety_postgres::converter::row_converter::Row)
+                -> ::std::result::Result<Self, ::safety_postgres::utils::errors::ExecutorError> {
+                ::std::result::Result::Ok(Self {
+                    #(#field_readers),*
+                })
+            }
+        }
+    })
+}
+
+fn column_name(field: &syn::Field) -> Result<Option<String>, Error> {
+    let mut column_name = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("from_row")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let value: LitStr = meta.value()?.parse()?;
+                column_name = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported from_row attribute, expected `rename`"))
+            }
+        })?;
+    }
+    Ok(column_name)
+}
+
+/// Derives `safety_postgres::executor::repository::TableMeta` and `FromRow` for a struct with named fields,
+/// so it can be used with `Repository`.
+///
+/// - `#[table(schema = "app", name = "users")]` on the struct sets the table. Both are optional,
+///   and the table name defaults to the struct name in snake case.
+/// - `#[column(primary_key)]` marks the field of the primary key. Exactly one field should have it.
+/// - `#[column(rename = "column")]` maps a field to a column with another name.
+/// - `#[column(generated)]` leaves the field out of the inserted and updated columns, e.g. for a `serial` key.
+/// - `#[column(belongs_to = Parent)]` marks the field of the foreign key referencing the entity `Parent`
+///   and implements `Related<Parent>` for `Repository::load_related`.
+///
+/// The struct also gets `table()` returning its `Table` and `<field>_column()` returning the `Column` of each field.
+/// Each field type should implement `Clone` and `Into<Variable>`. Don't derive `FromRow` together with this.
+#[proc_macro_derive(Entity, attributes(table, column))]
+pub fn derive_entity(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_entity(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+struct EntityColumn {
+    ident: syn::Ident,
+    column_name: String,
+    primary_key: bool,
+    generated: bool,
+    belongs_to: Option<syn::Path>,
+}
+
+fn expand_entity(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(input, "Entity can only be derived for a struct with named fields")),
+        },
+        _ => return Err(Error::new_spanned(input, "Entity can only be derived for a struct")),
+    };
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics, "Entity can not be derived for a generic struct"))
+    }
+
+    let name = &input.ident;
+    let (schema_name, table_name) = table_attribute(input)?;
+    let table_name = table_name.unwrap_or_else(|| to_snake_case(name.to_string().as_str()));
+    let schema_name = match schema_name {
+        Some(schema_name) => quote! { ::std::option::Option::Some(#schema_name) },
+        None => quote! { ::std::option::Option::None },
+    };
+
+    let columns = fields.iter().map(entity_column).collect::<Result<Vec<EntityColumn>, Error>>()?;
+    let mut primary_keys = columns.iter().filter(|column| column.primary_key);
+    let primary_key = match (primary_keys.next(), primary_keys.next()) {
+        (Some(primary_key), None) => primary_key,
+        _ => return Err(Error::new_spanned(input, "Entity needs exactly one field with `#[column(primary_key)]`")),
+    };
+    let primary_key_ident = &primary_key.ident;
+    let primary_key_name = &primary_key.column_name;
+
+    let field_readers = columns.iter().map(|column| {
+        let ident = &column.ident;
+        let column_name = &column.column_name;
+        quote! {
+            #ident: row.try_get(#column_name).map_err(|e| {
+                ::safety_postgres::utils::errors::ExecutorError::ConvertRowError(
+                    format!("'{}' column can not be converted due to {}", #column_name, e))
+            })?
+        }
+    });
+    let written_columns = columns.iter().filter(|column| !column.generated).collect::<Vec<&EntityColumn>>();
+    let written_names = written_columns.iter().map(|column| &column.column_name);
+    let written_idents = written_columns.iter().map(|column| &column.ident);
+    let column_getters = columns.iter().map(|column| {
+        let getter = syn::Ident::new(format!("{}_column", column.ident).as_str(), column.ident.span());
+        let column_name = &column.column_name;
+        let doc = format!("Returns the `{}` column of the table.", column_name);
+        quote! {
+            #[doc = #doc]
+            pub fn #getter() -> ::safety_postgres::Column<'static> {
+                ::safety_postgres::Column::create_column(#schema_name, #table_name, #column_name)
+            }
+        }
+    });
+
+    let related_impls = columns.iter().filter_map(|column| {
+        let parent = column.belongs_to.as_ref()?;
+        let ident = &column.ident;
+        let column_name = &column.column_name;
+        Some(quote! {
+            impl ::safety_postgres::executor::repository::Related<#parent> for #name {
+                fn foreign_key() -> &'static str {
+                    #column_name
+                }
+
+                fn foreign_key_value(&self) -> ::safety_postgres::Variable {
+                    ::safety_postgres::Variable::from(::std::clone::Clone::clone(&self.#ident))
+                }
+            }
+        })
+    });
+
+    Ok(quote! {
+        impl ::safety_postgres::converter::row_converter::FromRow for #name {
+            fn from_row(row: ::safety_postgres::converter::row_converter::Row)
+                -> ::std::result::Result<Self, ::safety_postgres::utils::errors::ExecutorError> {
+                ::std::result::Result::Ok(Self {
+                    #(#field_readers),*
+                })
+            }
+        }
+
+        impl ::safety_postgres::executor::repository::TableMeta for #name {
+            fn schema_name() -> ::std::option::Option<&'static str> {
+                #schema_name
+            }
+
+            fn table_name() -> &'static str {
+                #table_name
+            }
+
+            fn primary_key() -> &'static str {
+                #primary_key_name
+            }
+
+            fn columns() -> &'static [&'static str] {
+                &[#(#written_names),*]
+            }
+
+            fn values(&self) -> ::std::vec::Vec<::safety_postgres::Variable> {
+                ::std::vec![#(::safety_postgres::Variable::from(::std::clone::Clone::clone(&self.#written_idents))),*]
+            }
+
+            fn primary_key_value(&self) -> ::safety_postgres::Variable {
+                ::safety_postgres::Variable::from(::std::clone::Clone::clone(&self.#primary_key_ident))
+            }
+        }
+
+        impl #name {
+            /// Returns the table of the entity.
+            pub fn table() -> ::safety_postgres::Table<'static> {
+                ::safety_postgres::Table::create_table(#schema_name, #table_name)
+            }
+
+            #(#column_getters)*
+        }
+
+        #(#related_impls)*
+    })
+}
+
+fn table_attribute(input: &DeriveInput) -> Result<(Option<String>, Option<String>), Error> {
+    let (mut schema_name, mut table_name) = (None, None);
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("table")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("schema") {
+                let value: LitStr = meta.value()?.parse()?;
+                schema_name = Some(value.value());
+                Ok(())
+            } else if meta.path.is_ident("name") {
+                let value: LitStr = meta.value()?.parse()?;
+                table_name = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported table attribute, expected `schema` or `name`"))
+            }
+        })?;
+    }
+    Ok((schema_name, table_name))
+}
+
+fn entity_column(field: &syn::Field) -> Result<EntityColumn, Error> {
+    let ident = field.ident.clone().expect("named field should have an ident");
+    let mut column = EntityColumn {
+        column_name: ident.to_string(),
+        ident,
+        primary_key: false,
+        generated: false,
+        belongs_to: None,
+    };
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("column")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("primary_key") {
+                column.primary_key = true;
+                Ok(())
+            } else if meta.path.is_ident("generated") {
+                column.generated = true;
+                Ok(())
+            } else if meta.path.is_ident("rename") {
+                let value: LitStr = meta.value()?.parse()?;
+                column.column_name = value.value();
+                Ok(())
+            } else if meta.path.is_ident("belongs_to") {
+                column.belongs_to = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported column attribute, expected `primary_key`, `generated`, `rename` or `belongs_to`"))
+            }
+        })?;
+    }
+    Ok(column)
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake_case = String::new();
+    for (index, char) in name.chars().enumerate() {
+        if char.is_uppercase() {
+            if index != 0 {
+                snake_case.push('_');
+            }
+            snake_case.extend(char.to_lowercase());
+        } else {
+            snake_case.push(char);
+        }
+    }
+    snake_case
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_snake_case;
+
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("User"), "user");
+        assert_eq!(to_snake_case("UserAccount"), "user_account");
+        assert_eq!(to_snake_case("order_item"), "order_item");
+    }
+}
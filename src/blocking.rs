@@ -0,0 +1,166 @@
+//! Synchronous wrappers of the connector and the executors for the applications without an async runtime.
+//!
+//! Each wrapper drives the async API on a current-thread tokio runtime owned by the `Connector`,
+//! so the caller doesn't need to set up tokio. Don't use them inside an async context:
+//! blocking on the runtime there panics.
+
+use serde::de::DeserializeOwned;
+use tokio::runtime::{Builder, Runtime};
+use tokio_postgres::types::FromSql;
+use crate::connector::connection_config::ConnectionConfig;
+use crate::converter::row_converter::FromRow;
+use crate::executor::base::Executor;
+use crate::executor::definitions::Definition as AsyncDefinition;
+use crate::executor::dry_run::DryRun;
+use crate::executor::explain::{CostLimit, ExplainOptions, ExplainPlan};
+use crate::executor::migrations::Migrator;
+use crate::executor::policy::TablePolicy;
+use crate::executor::query::Query as AsyncQuery;
+use crate::executor::validation::ValidationReport;
+use crate::generator::base::MainGenerator;
+use crate::generator::definitions::DefinitionGenerator;
+use crate::utils::errors::ExecutorError;
+
+/// The blocking counterpart of `connector::Connector`, owning the runtime the connection runs on.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::blocking::{Connector, Query};
+/// use safety_postgres::connector::connection_config::ConnectionConfig;
+/// use safety_postgres::converter::row_converter::Row;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::generator::query::query_column::QueryColumns;
+/// use safety_postgres::Table;
+///
+/// fn fetch_users() {
+///     let config = ConnectionConfig::config_from_env().expect("config not found");
+///     let query = Query::new(Connector::connect(config).expect("connect failed"));
+///
+///     let table = Table::create_table(None, "users");
+///     let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+///     let rows = query.fetch_all::<Row, _>(&generator).expect("query failed");
+/// }
+/// ```
+pub struct Connector {
+    runtime: Runtime,
+    connector: crate::connector::Connector,
+}
+
+impl Connector {
+    /// Builds the runtime and connects to the database with `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::ConnectionNotFoundError` if the runtime can't be built,
+    /// or `ExecutorError` of the connection failure.
+    pub fn connect(config: ConnectionConfig) -> Result<Self, ExecutorError> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| ExecutorError::ConnectionNotFoundError(format!("Building the runtime failed: {}", e)))?;
+        let connector = runtime.block_on(crate::connector::Connector::connect(config))?;
+        Ok(Self {
+            runtime,
+            connector,
+        })
+    }
+
+    /// Applies the pending migrations of `migrator` and returns their versions (see `Migrator::run`).
+    pub fn run_migrations(&self, migrator: &Migrator) -> Result<Vec<i64>, ExecutorError> {
+        self.runtime.block_on(migrator.run(&self.connector))
+    }
+}
+
+/// The blocking counterpart of `executor::query::Query`.
+pub struct Query {
+    runtime: Runtime,
+    query: AsyncQuery,
+}
+
+impl Query {
+    pub fn new(connector: Connector) -> Self {
+        Self {
+            runtime: connector.runtime,
+            query: AsyncQuery::new(connector.connector),
+        }
+    }
+
+    /// Enables the cost guard (see `Query::with_cost_limit` of the async executor).
+    pub fn with_cost_limit(mut self, cost_limit: CostLimit) -> Self {
+        self.query = self.query.with_cost_limit(cost_limit);
+        self
+    }
+
+    /// Sets the table policy (see `Query::with_table_policy` of the async executor).
+    pub fn with_table_policy(mut self, table_policy: TablePolicy) -> Self {
+        self.query = self.query.with_table_policy(table_policy);
+        self
+    }
+
+    /// Executes the query and converts each row with `FromRow`.
+    pub fn fetch_all<T: FromRow, G: MainGenerator>(&self, generator: &G) -> Result<Vec<T>, ExecutorError> {
+        self.runtime.block_on(self.query.fetch_all(generator))
+    }
+
+    /// Executes the query and deserializes each row into `T` via serde.
+    pub fn query_as<T: DeserializeOwned, G: MainGenerator>(&self, generator: &G) -> Result<Vec<T>, ExecutorError> {
+        self.runtime.block_on(self.query.query_as(generator))
+    }
+
+    /// Executes the query and returns the single value of the result.
+    pub fn fetch_scalar<T: for<'a> FromSql<'a>, G: MainGenerator>(&self, generator: &G) -> Result<T, ExecutorError> {
+        self.runtime.block_on(self.query.fetch_scalar(generator))
+    }
+
+    /// Executes the query and returns the values of its single column.
+    pub fn fetch_column<T: for<'a> FromSql<'a>, G: MainGenerator>(&self, generator: &G) -> Result<Vec<T>, ExecutorError> {
+        self.runtime.block_on(self.query.fetch_column(generator))
+    }
+
+    /// Runs `EXPLAIN` for the statement of the generator and returns the parsed plan.
+    pub fn explain<G: MainGenerator + Sync>(&self, generator: &G, options: ExplainOptions) -> Result<ExplainPlan, ExecutorError> {
+        self.runtime.block_on(self.query.explain(generator, options))
+    }
+
+    /// Checks the generator against the live schema without executing it.
+    pub fn validate<G: MainGenerator + Sync>(&self, generator: &G) -> Result<ValidationReport, ExecutorError> {
+        self.runtime.block_on(self.query.validate(generator))
+    }
+
+    /// Returns the statement and the parameters of the generator which would be executed.
+    pub fn dry_run<G: MainGenerator>(&self, generator: &G) -> DryRun {
+        self.query.dry_run(generator)
+    }
+}
+
+/// The blocking counterpart of `executor::definitions::Definition`.
+pub struct Definition {
+    runtime: Runtime,
+    definition: AsyncDefinition,
+}
+
+impl Definition {
+    pub fn new(connector: Connector) -> Self {
+        Self {
+            runtime: connector.runtime,
+            definition: AsyncDefinition::new(connector.connector),
+        }
+    }
+
+    /// Executes the DDL statement of `definition`.
+    pub fn execute<D: DefinitionGenerator>(&self, definition: &D) -> Result<(), ExecutorError> {
+        self.runtime.block_on(self.definition.execute(definition))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connector::connection_config::ConnectionConfig;
+    use super::Connector;
+
+    #[test]
+    fn test_connect_without_runtime() {
+        let config = ConnectionConfig::set_config("postgres", "password", "127.0.0.1", 1, "postgres");
+        assert!(Connector::connect(config).is_err());
+    }
+}
@@ -1,38 +1,220 @@
 pub mod connection_config;
+pub mod health;
+pub mod hooks;
+#[cfg(feature = "rds-iam")]
+pub mod rds_iam;
+#[cfg(feature = "tls")]
+pub mod tls;
 
 use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio_postgres::{Client, NoTls, Error as PGError};
-use crate::connector::connection_config::ConnectionConfig;
+#[cfg(feature = "tls")]
+use tokio_postgres::config::SslMode;
+use crate::connector::connection_config::{ConnectionConfig, SecretProvider};
+use crate::connector::health::HealthReport;
+use crate::executor::capture::StatementLog;
+use crate::utils::errors::ExecutorError;
+use crate::utils::logging::warn_event;
 
 pub struct Connector {
-    config: ConnectionConfig,
+    config: Option<ConnectionConfig>,
     client: Option<Client>,
+    statement_log: Option<StatementLog>,
+    secret_provider: Option<Arc<dyn SecretProvider>>,
 }
 
 impl Connector {
     pub async fn connect(config: ConnectionConfig) -> Result<Self, PGError> {
-        let (client, connection) = tokio_postgres::Config::new()
-            .user(config.get_user())
-            .password(config.get_password())
-            .host(config.get_hostname())
-            .port(config.get_port())
-            .dbname(config.get_db_name())
-            .connect(NoTls).await?;
-
-        tokio::spawn(async move {
-            if let Err(e) = connection.await {
-                eprintln!("Connection failed due to {}", e);
-            }
-        });
+        let client = open_client(&config, config.get_password()).await?;
+        Ok(Self {
+            config: Some(config),
+            client: Some(client),
+            statement_log: None,
+            secret_provider: None,
+        })
+    }
+
+    /// Connects with the password fetched from `secret_provider` instead of the password of `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::ConnectionNotFoundError` if the password can't be fetched,
+    /// or `ExecutorError` of the connection failure.
+    pub async fn connect_with_secret_provider(config: ConnectionConfig, secret_provider: Arc<dyn SecretProvider>) -> Result<Self, ExecutorError> {
+        let password = fetch_password(secret_provider.as_ref()).await?;
+        let client = open_client(&config, password.as_str()).await?;
         Ok(Self {
-            config,
-            client: Some(client)
+            config: Some(config),
+            client: Some(client),
+            statement_log: None,
+            secret_provider: Some(secret_provider),
         })
     }
+
+    /// Replaces the connection with a new one, e.g. after it was closed.
+    /// The password is fetched again if the connector was made by `connect_with_secret_provider`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::ConnectionNotFoundError` if the connector was made by `capture` or the password can't be fetched,
+    /// or `ExecutorError` of the connection failure.
+    pub async fn reconnect(&mut self) -> Result<(), ExecutorError> {
+        let config = match &self.config {
+            Some(config) => config,
+            None => return Err(ExecutorError::ConnectionNotFoundError(
+                "The connector capturing the statements can't connect.".to_string())),
+        };
+        let client = match &self.secret_provider {
+            Some(secret_provider) => open_client(config, fetch_password(secret_provider.as_ref()).await?.as_str()).await?,
+            None => open_client(config, config.get_password()).await?,
+        };
+        if let Some(old_client) = self.client.replace(client) {
+            if let Err(e) = run_disconnect_hooks(config, &old_client).await {
+                warn_event!("Disconnect hook failed on the replaced connection due to {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Closes the connection after invoking the `on_disconnect` of the connection hooks.
+    /// The connector can be connected again by `reconnect`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` of the hook failure. The connection is closed even in the case.
+    pub async fn disconnect(&mut self) -> Result<(), ExecutorError> {
+        let (config, client) = match (&self.config, self.client.take()) {
+            (Some(config), Some(client)) => (config, client),
+            _ => return Ok(()),
+        };
+        run_disconnect_hooks(config, &client).await?;
+        Ok(())
+    }
+
+    /// Executes `SELECT 1` and returns its round trip time, e.g. for the liveness probe of a service.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::ConnectionClosedError` if the server doesn't respond within `timeout`,
+    /// or `ExecutorError` of the execution failure.
+    pub async fn ping(&self, timeout: Duration) -> Result<Duration, ExecutorError> {
+        let client = self.get_client()?;
+        let started = Instant::now();
+        with_timeout(timeout, client.simple_query("SELECT 1")).await?;
+        Ok(started.elapsed())
+    }
+
+    /// Returns the server version, the replication role and the latency of the server, e.g. for the readiness probe of a service.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::ConnectionClosedError` if the server doesn't respond within `timeout`,
+    /// or `ExecutorError` of the execution failure.
+    pub async fn health_report(&self, timeout: Duration) -> Result<HealthReport, ExecutorError> {
+        let latency = self.ping(timeout).await?;
+        let client = self.get_client()?;
+        let row = with_timeout(timeout, client.query_one("SELECT current_setting('server_version'), pg_is_in_recovery()", &[])).await?;
+        Ok(HealthReport::new(row.try_get(0)?, row.try_get(1)?, latency))
+    }
+
+    /// Creates a connector which doesn't connect to the database but appends every statement
+    /// executed through it to `statement_log`.
+    ///
+    /// The queries return no row and the definitions succeed without being checked by the database
+    /// or the table policy and the cost limit of the executor, so only the generated statements can be tested with it.
+    /// The executions reading the database directly (e.g. `explain` or `introspect`) fail with `ExecutorError::ConnectionNotFoundError`.
+    pub fn capture(statement_log: StatementLog) -> Self {
+        Self {
+            config: None,
+            client: None,
+            statement_log: Some(statement_log),
+            secret_provider: None,
+        }
+    }
+
+    pub(crate) fn get_statement_log(&self) -> Option<&StatementLog> {
+        self.statement_log.as_ref()
+    }
+
+    pub(crate) fn get_client(&self) -> Result<&Client, ExecutorError> {
+        match self.client.as_ref() {
+            Some(client) => Ok(client),
+            None => Err(ExecutorError::ConnectionNotFoundError(
+                "Client does not exist. Please connect the PostgreSQL first via connect method.".to_string())),
+        }
+    }
+}
+
+async fn open_client(config: &ConnectionConfig, password: &str) -> Result<Client, PGError> {
+    let mut pg_config = tokio_postgres::Config::new();
+    pg_config
+        .user(config.get_user())
+        .password(password)
+        .host(config.get_hostname())
+        .port(config.get_port())
+        .dbname(config.get_db_name());
+    if let Some(application_name) = config.get_application_name() {
+        pg_config.application_name(application_name);
+    }
+    if let Some(options) = config.get_options() {
+        pg_config.options(options);
+    }
+    #[cfg(feature = "tls")]
+    if let Some(tls) = config.get_tls() {
+        pg_config.ssl_mode(SslMode::Require);
+        let (client, connection) = pg_config.connect(tls.make_connector()).await?;
+        spawn_connection(connection);
+        return on_connect(config, client).await
+    }
+    let (client, connection) = pg_config.connect(NoTls).await?;
+    spawn_connection(connection);
+    on_connect(config, client).await
+}
+
+fn spawn_connection<F: Future<Output = Result<(), PGError>> + Send + 'static>(connection: F) {
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            warn_event!("Connection failed due to {}", e);
+        }
+    });
+}
+
+async fn on_connect(config: &ConnectionConfig, client: Client) -> Result<Client, PGError> {
+    for hook in config.get_connection_hooks() {
+        hook.on_connect(&client).await?;
+    }
+    Ok(client)
+}
+
+async fn run_disconnect_hooks(config: &ConnectionConfig, client: &Client) -> Result<(), PGError> {
+    for hook in config.get_connection_hooks() {
+        hook.on_disconnect(client).await?;
+    }
+    Ok(())
+}
+
+async fn with_timeout<T>(timeout: Duration, future: impl Future<Output = Result<T, PGError>>) -> Result<T, ExecutorError> {
+    match tokio::time::timeout(timeout, future).await {
+        Ok(result) => Ok(result?),
+        Err(_) => Err(ExecutorError::ConnectionClosedError(format!("no response from the server within {:?}", timeout))),
+    }
+}
+
+async fn fetch_password(secret_provider: &dyn SecretProvider) -> Result<String, ExecutorError> {
+    secret_provider
+        .get_password()
+        .await
+        .map_err(|e| ExecutorError::ConnectionNotFoundError(format!("Fetching the password failed: {}", e)))
 }
 
 impl Debug for Connector {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Connection Established to {}!!", self.config)
+        match &self.config {
+            Some(config) => write!(f, "Connection Established to {}!!", config),
+            None => write!(f, "Capturing the statements without connection"),
+        }
     }
 }
@@ -0,0 +1,74 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// The role of the server in the replication.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicationRole {
+    Primary,
+    /// The server is a standby in recovery, which accepts only read-only transactions.
+    Replica,
+}
+
+impl Display for ReplicationRole {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Primary => write!(f, "primary"),
+            Self::Replica => write!(f, "replica"),
+        }
+    }
+}
+
+/// The status of the server returned by `Connector::health_report`.
+#[derive(Debug, Clone)]
+pub struct HealthReport {
+    server_version: String,
+    replication_role: ReplicationRole,
+    latency: Duration,
+}
+
+impl HealthReport {
+    pub(crate) fn new(server_version: String, is_in_recovery: bool, latency: Duration) -> Self {
+        let replication_role = if is_in_recovery { ReplicationRole::Replica } else { ReplicationRole::Primary };
+        Self {
+            server_version,
+            replication_role,
+            latency,
+        }
+    }
+
+    /// Returns the `server_version` setting of the server (e.g. `16.2`).
+    pub fn get_server_version(&self) -> &str {
+        self.server_version.as_str()
+    }
+
+    pub fn get_replication_role(&self) -> ReplicationRole {
+        self.replication_role
+    }
+
+    /// Returns the round trip time of `SELECT 1`.
+    pub fn get_latency(&self) -> Duration {
+        self.latency
+    }
+}
+
+impl Display for HealthReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PostgreSQL {} ({}), latency: {}ms", self.server_version, self.replication_role, self.latency.as_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::{HealthReport, ReplicationRole};
+
+    #[test]
+    fn test_health_report() {
+        let report = HealthReport::new("16.2".to_string(), true, Duration::from_millis(3));
+        assert_eq!(report.get_replication_role(), ReplicationRole::Replica);
+        assert_eq!(report.to_string(), "PostgreSQL 16.2 (replica), latency: 3ms");
+
+        let report = HealthReport::new("16.2".to_string(), false, Duration::from_millis(3));
+        assert_eq!(report.get_replication_role(), ReplicationRole::Primary);
+    }
+}
@@ -0,0 +1,34 @@
+use std::future::Future;
+use std::pin::Pin;
+use tokio_postgres::{Client, Error as PGError};
+
+/// The future returned by the callbacks of `ConnectionHook`.
+pub type HookFuture<'a> = Pin<Box<dyn Future<Output = Result<(), PGError>> + Send + 'a>>;
+
+/// Runs callbacks on the lifecycle of the physical connections (e.g. to run `SET`s, warm the statement caches or emit metrics).
+///
+/// `on_connect` is invoked on every new connection made by `Connector::connect`, `Connector::connect_with_secret_provider`
+/// and `Connector::reconnect` before the connector is handed out, and an error of it fails the connection.
+/// `on_disconnect` is invoked on the old connection by `Connector::disconnect` and `Connector::reconnect`,
+/// but not when the connector is just dropped because the drop can't wait for it.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::connector::hooks::{ConnectionHook, HookFuture};
+/// use tokio_postgres::Client;
+///
+/// struct StatementTimeout;
+///
+/// impl ConnectionHook for StatementTimeout {
+///     fn on_connect<'a>(&'a self, client: &'a Client) -> HookFuture<'a> {
+///         Box::pin(async move { client.batch_execute("SET statement_timeout = 5000").await })
+///     }
+/// }
+/// ```
+pub trait ConnectionHook: Send + Sync {
+    fn on_connect<'a>(&'a self, client: &'a Client) -> HookFuture<'a>;
+
+    fn on_disconnect<'a>(&'a self, _client: &'a Client) -> HookFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+}
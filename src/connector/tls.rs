@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::pem::PemObject;
+use rustls::{ClientConfig, RootCertStore};
+use tokio_postgres_rustls::MakeRustlsConnect;
+use crate::utils::errors::ConnectionConfigError;
+
+/// The TLS settings of the connections, set by `ConnectionConfig::with_tls`.
+///
+/// The connections with TLS require it (`sslmode=require`) and verify the certificate of the server
+/// against the Mozilla root certificates and the root certificates added with `with_root_certificates_pem`.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::connector::connection_config::ConnectionConfig;
+/// use safety_postgres::connector::tls::TlsOptions;
+/// use safety_postgres::utils::errors::ConnectionConfigError;
+///
+/// // The certificate bundle of RDS is at https://truststore.pki.rds.amazonaws.com/global/global-bundle.pem
+/// fn rds_config(bundle_pem: &[u8]) -> Result<ConnectionConfig, ConnectionConfigError> {
+///     let tls = TlsOptions::new().with_root_certificates_pem(bundle_pem)?;
+///     Ok(ConnectionConfig::set_config("iam_user", "", "db.example.us-east-1.rds.amazonaws.com", 5432, "app").with_tls(tls))
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TlsOptions {
+    root_store: RootCertStore,
+}
+
+impl TlsOptions {
+    pub fn new() -> Self {
+        Self {
+            root_store: RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+    }
+
+    /// Adds the root certificates in `pem` (e.g. the certificate bundle of RDS) to the trusted ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionConfigError::InvalidFileError` if `pem` has no certificate or an invalid one.
+    pub fn with_root_certificates_pem(mut self, pem: &[u8]) -> Result<Self, ConnectionConfigError> {
+        let certificates = CertificateDer::pem_slice_iter(pem)
+            .collect::<Result<Vec<CertificateDer<'static>>, _>>()
+            .map_err(|e| ConnectionConfigError::InvalidFileError(format!("the certificates can't be read: {}", e)))?;
+        if certificates.is_empty() {
+            return Err(ConnectionConfigError::InvalidFileError("no certificate is found in the PEM.".to_string()))
+        }
+        for certificate in certificates {
+            self.root_store
+                .add(certificate)
+                .map_err(|e| ConnectionConfigError::InvalidFileError(format!("the root certificate is invalid: {}", e)))?;
+        }
+        Ok(self)
+    }
+
+    /// Builds the TLS connector of tokio-postgres trusting the root certificates.
+    pub(crate) fn make_connector(&self) -> MakeRustlsConnect {
+        let config = ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .expect("the ring provider supports the default protocol versions")
+            .with_root_certificates(self.root_store.clone())
+            .with_no_client_auth();
+        MakeRustlsConnect::new(config)
+    }
+}
+
+impl Default for TlsOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TlsOptions;
+
+    #[test]
+    fn test_root_certificates_pem() {
+        assert!(!TlsOptions::new().root_store.is_empty());
+        assert!(TlsOptions::new().with_root_certificates_pem(b"not a certificate").is_err());
+        assert!(TlsOptions::new()
+            .with_root_certificates_pem(b"-----BEGIN CERTIFICATE-----\n!!!!\n-----END CERTIFICATE-----\n")
+            .is_err());
+    }
+}
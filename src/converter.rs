@@ -1 +1,8 @@
-mod type_converter;
\ No newline at end of file
+mod type_converter;
+#[cfg(feature = "time")]
+mod time_converter;
+pub mod row_converter;
+#[cfg(feature = "arrow")]
+pub mod arrow_converter;
+#[cfg(feature = "polars")]
+pub mod polars_converter;
@@ -0,0 +1,139 @@
+use std::sync::Arc;
+use arrow_array::{ArrayRef, BooleanArray, Date32Array, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, RecordBatch, StringArray, Time64MicrosecondArray, TimestampMicrosecondArray};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use chrono::{NaiveDate, Timelike};
+use tokio_postgres::{Column, Row};
+use tokio_postgres::types::Type;
+use crate::converter::row_converter::{collect_column_values, get_variable};
+use crate::utils::errors::ExecutorError;
+use crate::Variable;
+
+/// Returns the Arrow data type used for a PostgreSQL type.
+///
+/// Types without a lossless Arrow counterpart (e.g. `numeric`, `interval`) are exported as `Utf8`
+/// with the same text as `Variable`'s `Display`.
+pub fn arrow_data_type(ty: &Type) -> DataType {
+    match *ty {
+        Type::INT2 => DataType::Int16,
+        Type::INT4 => DataType::Int32,
+        Type::INT8 => DataType::Int64,
+        Type::FLOAT4 => DataType::Float32,
+        Type::FLOAT8 => DataType::Float64,
+        Type::BOOL => DataType::Boolean,
+        Type::DATE => DataType::Date32,
+        Type::TIME => DataType::Time64(TimeUnit::Microsecond),
+        Type::TIMESTAMP => DataType::Timestamp(TimeUnit::Microsecond, None),
+        Type::TIMESTAMPTZ => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        _ => DataType::Utf8,
+    }
+}
+
+/// Converts rows into an Arrow `RecordBatch` whose schema is built from `columns`.
+///
+/// `columns` is taken separately from the rows (e.g. from the prepared statement) so that an empty result
+/// still has the schema of the query. All fields are nullable.
+///
+/// # Errors
+///
+/// Returns `ExecutorError::ConvertRowError` if a value can't be read or doesn't match the column type.
+pub fn rows_to_record_batch(columns: &[Column], rows: &[Row]) -> Result<RecordBatch, ExecutorError> {
+    let fields: Vec<Field> = columns
+        .iter()
+        .map(|column| Field::new(column.name(), arrow_data_type(column.type_()), true))
+        .collect();
+
+    let mut arrays: Vec<ArrayRef> = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let values = rows
+            .iter()
+            .map(|row| get_variable(row, index))
+            .collect::<Result<Vec<Variable>, ExecutorError>>()?;
+        arrays.push(column_to_array(field, &values)?);
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+        .map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+}
+
+fn column_to_array(field: &Field, values: &[Variable]) -> Result<ArrayRef, ExecutorError> {
+    let array: ArrayRef = match field.data_type() {
+        DataType::Int16 => Arc::new(Int16Array::from(collect_column_values(field.name(), values, |value| match value {
+            Variable::SmallInt(value) => Some(*value),
+            _ => None,
+        })?)),
+        DataType::Int32 => Arc::new(Int32Array::from(collect_column_values(field.name(), values, |value| match value {
+            Variable::Int(value) => Some(*value),
+            _ => None,
+        })?)),
+        DataType::Int64 => Arc::new(Int64Array::from(collect_column_values(field.name(), values, |value| match value {
+            Variable::BigInt(value) => Some(*value),
+            _ => None,
+        })?)),
+        DataType::Float32 => Arc::new(Float32Array::from(collect_column_values(field.name(), values, |value| match value {
+            Variable::Float(value) => Some(*value),
+            _ => None,
+        })?)),
+        DataType::Float64 => Arc::new(Float64Array::from(collect_column_values(field.name(), values, |value| match value {
+            Variable::Double(value) => Some(*value),
+            _ => None,
+        })?)),
+        DataType::Boolean => Arc::new(BooleanArray::from(collect_column_values(field.name(), values, |value| match value {
+            Variable::Bool(value) => Some(*value),
+            _ => None,
+        })?)),
+        DataType::Date32 => {
+            let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("1970-01-01 should be a valid date");
+            Arc::new(Date32Array::from(collect_column_values(field.name(), values, |value| match value {
+                Variable::Date(date) => Some((*date - epoch).num_days() as i32),
+                _ => None,
+            })?))
+        },
+        DataType::Time64(_) => Arc::new(Time64MicrosecondArray::from(collect_column_values(field.name(), values, |value| match value {
+            Variable::Time(time) => Some(time.num_seconds_from_midnight() as i64 * 1_000_000 + (time.nanosecond() / 1_000) as i64),
+            _ => None,
+        })?)),
+        DataType::Timestamp(_, None) => Arc::new(TimestampMicrosecondArray::from(collect_column_values(field.name(), values, |value| match value {
+            Variable::DateTime(datetime) => Some(datetime.and_utc().timestamp_micros()),
+            _ => None,
+        })?)),
+        DataType::Timestamp(_, Some(timezone)) => Arc::new(TimestampMicrosecondArray::from(collect_column_values(field.name(), values, |value| match value {
+            Variable::DateTimeTz(datetime) => Some(datetime.timestamp_micros()),
+            _ => None,
+        })?).with_timezone(timezone.clone())),
+        _ => Arc::new(StringArray::from(collect_column_values(field.name(), values, |value| Some(value.to_string()))?)),
+    };
+    Ok(array)
+}
+
+/// Writes the record batch into `writer` in the Parquet format.
+///
+/// # Errors
+///
+/// Returns `ExecutorError::ExportError` if writing fails.
+#[cfg(feature = "parquet")]
+pub fn write_parquet<W: std::io::Write + Send>(record_batch: &RecordBatch, writer: W) -> Result<(), ExecutorError> {
+    let export_error = |e: parquet::errors::ParquetError| ExecutorError::ExportError(e.to_string());
+    let mut arrow_writer = parquet::arrow::ArrowWriter::try_new(writer, record_batch.schema(), None).map_err(export_error)?;
+    arrow_writer.write(record_batch).map_err(export_error)?;
+    arrow_writer.close().map_err(export_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use arrow_array::{Array, Int32Array};
+    use arrow_schema::{DataType, Field};
+    use crate::Variable;
+    use super::column_to_array;
+
+    #[test]
+    fn test_column_to_array() {
+        let field = Field::new("id", DataType::Int32, true);
+        let array = column_to_array(&field, &[Variable::Int(1), Variable::Null]).unwrap();
+        let expected: Arc<dyn Array> = Arc::new(Int32Array::from(vec![Some(1), None]));
+        assert_eq!(&array, &expected);
+
+        assert!(column_to_array(&field, &[Variable::Text("a".to_string())]).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+use chrono::{NaiveDate, Timelike};
+use polars::prelude::{Column as PolarsColumn, DataFrame, DataType, IntoColumn, NamedFrom, Series, TimeUnit};
+use tokio_postgres::{Column, Row};
+use tokio_postgres::types::Type;
+use crate::converter::row_converter::{collect_column_values, get_variable};
+use crate::utils::errors::ExecutorError;
+use crate::Variable;
+
+/// Converts rows into a polars `DataFrame` whose dtypes are inferred from the column types.
+///
+/// Integers, floats and booleans keep their width, `date`/`time`/`timestamp` become `Date`/`Time`/`Datetime`,
+/// `timestamptz` becomes a `Datetime` in UTC, and the other types (e.g. `numeric`, `interval`) become
+/// `String` with the same text as `Variable`'s `Display`.
+///
+/// # Errors
+///
+/// Returns `ExecutorError::ConvertRowError` if a value can't be read or doesn't match the column type.
+pub fn rows_to_dataframe(columns: &[Column], rows: &[Row]) -> Result<DataFrame, ExecutorError> {
+    let mut polars_columns: Vec<PolarsColumn> = Vec::new();
+    for (index, column) in columns.iter().enumerate() {
+        let values = rows
+            .iter()
+            .map(|row| get_variable(row, index))
+            .collect::<Result<Vec<Variable>, ExecutorError>>()?;
+        polars_columns.push(column_to_series(column.name(), column.type_(), &values)?.into_column());
+    }
+
+    DataFrame::new(polars_columns).map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+}
+
+fn column_to_series(name: &str, ty: &Type, values: &[Variable]) -> Result<Series, ExecutorError> {
+    let series = match *ty {
+        Type::INT2 => Series::new(name.into(), collect_column_values(name, values, |value| match value {
+            Variable::SmallInt(value) => Some(*value),
+            _ => None,
+        })?),
+        Type::INT4 => Series::new(name.into(), collect_column_values(name, values, |value| match value {
+            Variable::Int(value) => Some(*value),
+            _ => None,
+        })?),
+        Type::INT8 => Series::new(name.into(), collect_column_values(name, values, |value| match value {
+            Variable::BigInt(value) => Some(*value),
+            _ => None,
+        })?),
+        Type::FLOAT4 => Series::new(name.into(), collect_column_values(name, values, |value| match value {
+            Variable::Float(value) => Some(*value),
+            _ => None,
+        })?),
+        Type::FLOAT8 => Series::new(name.into(), collect_column_values(name, values, |value| match value {
+            Variable::Double(value) => Some(*value),
+            _ => None,
+        })?),
+        Type::BOOL => Series::new(name.into(), collect_column_values(name, values, |value| match value {
+            Variable::Bool(value) => Some(*value),
+            _ => None,
+        })?),
+        Type::DATE => {
+            let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("1970-01-01 should be a valid date");
+            let days = Series::new(name.into(), collect_column_values(name, values, |value| match value {
+                Variable::Date(date) => Some((*date - epoch).num_days() as i32),
+                _ => None,
+            })?);
+            cast_series(days, &DataType::Date)?
+        },
+        Type::TIME => {
+            let nanoseconds = Series::new(name.into(), collect_column_values(name, values, |value| match value {
+                Variable::Time(time) => Some(time.num_seconds_from_midnight() as i64 * 1_000_000_000 + time.nanosecond() as i64),
+                _ => None,
+            })?);
+            cast_series(nanoseconds, &DataType::Time)?
+        },
+        Type::TIMESTAMP => {
+            let microseconds = Series::new(name.into(), collect_column_values(name, values, |value| match value {
+                Variable::DateTime(datetime) => Some(datetime.and_utc().timestamp_micros()),
+                _ => None,
+            })?);
+            cast_series(microseconds, &DataType::Datetime(TimeUnit::Microseconds, None))?
+        },
+        Type::TIMESTAMPTZ => {
+            let microseconds = Series::new(name.into(), collect_column_values(name, values, |value| match value {
+                Variable::DateTimeTz(datetime) => Some(datetime.timestamp_micros()),
+                _ => None,
+            })?);
+            cast_series(microseconds, &DataType::Datetime(TimeUnit::Microseconds, None))?
+        },
+        _ => Series::new(name.into(), collect_column_values(name, values, |value| Some(value.to_string()))?),
+    };
+    Ok(series)
+}
+
+fn cast_series(series: Series, data_type: &DataType) -> Result<Series, ExecutorError> {
+    series.cast(data_type).map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use polars::prelude::DataType;
+    use tokio_postgres::types::Type;
+    use crate::Variable;
+    use super::column_to_series;
+
+    #[test]
+    fn test_column_to_series() {
+        let dates = vec![Variable::Date(NaiveDate::from_ymd_opt(2023, 11, 29).unwrap()), Variable::Null];
+        let series = column_to_series("created", &Type::DATE, &dates).unwrap();
+        assert_eq!(series.dtype(), &DataType::Date);
+        assert_eq!(series.null_count(), 1);
+
+        assert!(column_to_series("id", &Type::INT4, &[Variable::BigInt(1)]).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+pub use tokio_postgres::Row;
+#[cfg(feature = "derive")]
+pub use safety_postgres_derive::FromRow;
+use std::collections::HashMap;
+use serde_json::{json, Map, Value};
+use rust_decimal::prelude::ToPrimitive;
+use crate::utils::errors::ExecutorError;
+use crate::Variable;
+
+/// Converts a row of the query result into a Rust value.
+///
+/// With the `derive` feature, `#[derive(FromRow)]` implements this trait for a struct with named fields
+/// by reading each field from the column of the same name. `#[from_row(rename = "column")]` reads a field
+/// from a column with another name.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::converter::row_converter::{FromRow, Row};
+/// use safety_postgres::utils::errors::ExecutorError;
+///
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// impl FromRow for User {
+///     fn from_row(row: Row) -> Result<Self, ExecutorError> {
+///         let convert_error = |e: tokio_postgres::Error| ExecutorError::ConvertRowError(e.to_string());
+///         Ok(Self {
+///             id: row.try_get("id").map_err(convert_error)?,
+///             name: row.try_get("name").map_err(convert_error)?,
+///         })
+///     }
+/// }
+/// ```
+pub trait FromRow: Sized {
+    fn from_row(row: Row) -> Result<Self, ExecutorError>;
+}
+
+impl FromRow for Row {
+    fn from_row(row: Row) -> Result<Self, ExecutorError> {
+        Ok(row)
+    }
+}
+
+impl FromRow for HashMap<String, Variable> {
+    fn from_row(row: Row) -> Result<Self, ExecutorError> {
+        row_to_map(&row)
+    }
+}
+
+/// Converts a row into a map from the column names to the values.
+///
+/// NULL is converted to `Variable::Null`, so every column of the row is present in the map.
+///
+/// # Errors
+///
+/// Returns `ExecutorError::ConvertRowError` if a column has a type which `Variable` doesn't support
+/// (register it with `types::custom::register_custom_type` to read it as `Variable::Custom`).
+pub fn row_to_map(row: &Row) -> Result<HashMap<String, Variable>, ExecutorError> {
+    let mut map = HashMap::new();
+    for (index, column) in row.columns().iter().enumerate() {
+        map.insert(column.name().to_string(), get_variable(row, index)?);
+    }
+    Ok(map)
+}
+
+pub(crate) fn get_variable(row: &Row, index: usize) -> Result<Variable, ExecutorError> {
+    match row.try_get::<usize, Option<Variable>>(index) {
+        Ok(variable) => Ok(variable.unwrap_or(Variable::Null)),
+        Err(e) => Err(ExecutorError::ConvertRowError(
+            format!("'{}' column can not be converted due to {}", row.columns()[index].name(), e))),
+    }
+}
+
+/// Extracts the values of a column with `extract`, keeping `Variable::Null` as `None`.
+///
+/// `extract` returns `None` for a value which doesn't match the type of the output column.
+#[cfg(any(feature = "arrow", feature = "polars"))]
+pub(crate) fn collect_column_values<T, F: Fn(&Variable) -> Option<T>>(column_name: &str, values: &[Variable], extract: F) -> Result<Vec<Option<T>>, ExecutorError> {
+    values
+        .iter()
+        .map(|value| match value {
+            Variable::Null => Ok(None),
+            _ => match extract(value) {
+                Some(extracted) => Ok(Some(extracted)),
+                None => Err(ExecutorError::ConvertRowError(
+                    format!("'{}' can not be stored in the '{}' column.", value, column_name))),
+            },
+        })
+        .collect()
+}
+
+/// Converts a row into a JSON object keyed by the column names. NULL is converted to `Value::Null`.
+pub(crate) fn row_to_json_map(row: &Row) -> Result<Map<String, Value>, ExecutorError> {
+    let mut map = Map::new();
+    for (index, column) in row.columns().iter().enumerate() {
+        map.insert(column.name().to_string(), variable_to_json(&get_variable(row, index)?));
+    }
+    Ok(map)
+}
+
+/// Converts a `Variable` extracted from a row into a JSON value.
+///
+/// The dates and times are written in the ISO 8601 forms which chrono's serde deserializers read
+/// (e.g. `2024-01-02T03:04:05` for `timestamp`), and the decimals as JSON numbers through `f64`,
+/// so the digits beyond the precision of `f64` are rounded.
+pub(crate) fn variable_to_json(variable: &Variable) -> Value {
+    match variable {
+        Variable::Text(text) => json!(text),
+        Variable::Secret(_) => json!("****"),
+        Variable::SmallInt(smallint) => json!(smallint),
+        Variable::Int(int) => json!(int),
+        Variable::BigInt(bigint) => json!(bigint),
+        Variable::Float(float) => json!(float),
+        Variable::Double(double) => json!(double),
+        Variable::Decimal(decimal) => match decimal.to_i64().filter(|_| decimal.fract().is_zero()) {
+            Some(integer) => json!(integer),
+            None => json!(decimal.to_f64()),
+        },
+        Variable::Date(date) => json!(date.to_string()),
+        Variable::DateTime(datetime) => json!(datetime.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+        Variable::DateTimeTz(datetime) => json!(datetime.to_rfc3339()),
+        Variable::Time(time) => json!(time.to_string()),
+        Variable::Bool(bool) => json!(bool),
+        Variable::Interval(interval) => json!(interval.to_string()),
+        Variable::Inet(inet) => json!(inet.to_string()),
+        Variable::Cidr(cidr) => json!(cidr.to_string()),
+        Variable::MacAddr(mac) => json!(mac.to_hex_string()),
+        Variable::Enum(label) => json!(label.get_label()),
+        Variable::Range(range) => json!(range.to_string()),
+        Variable::Array(values) => Value::Array(values.iter().map(variable_to_json).collect()),
+        Variable::Null => Value::Null,
+        Variable::Custom(value) => json!(value.to_string()),
+    }
+}
+
+/// Formats rows as an aligned ASCII table like `psql`, e.g. for logs or test failure output.
+///
+/// NULL is shown as an empty cell and a column which `Variable` doesn't support is shown as `<unsupported>`.
+///
+/// ```text
+///  id | username
+/// ----+----------
+///  1  | alice
+///  2  | bob
+/// (2 rows)
+/// ```
+pub fn format_rows_table(rows: &[Row]) -> String {
+    let headers: Vec<String> = match rows.first() {
+        Some(row) => row.columns().iter().map(|column| column.name().to_string()).collect(),
+        None => Vec::new(),
+    };
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            (0..row.len())
+                .map(|index| match get_variable(row, index) {
+                    Ok(Variable::Null) => String::new(),
+                    Ok(variable) => variable.to_string(),
+                    Err(_) => "<unsupported>".to_string(),
+                })
+                .collect()
+        })
+        .collect();
+    format_table(&headers, &cells)
+}
+
+fn format_table(headers: &[String], cells: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+    for row in cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_line = |values: &[String]| -> String {
+        values
+            .iter()
+            .zip(&widths)
+            .map(|(value, width)| format!(" {}{} ", value, " ".repeat(width - value.chars().count())))
+            .collect::<Vec<String>>()
+            .join("|")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = Vec::new();
+    if !headers.is_empty() {
+        lines.push(format_line(headers));
+        lines.push(widths.iter().map(|width| "-".repeat(width + 2)).collect::<Vec<String>>().join("+"));
+    }
+    for row in cells {
+        lines.push(format_line(row));
+    }
+    lines.push(match cells.len() {
+        1 => "(1 row)".to_string(),
+        row_count => format!("({} rows)", row_count),
+    });
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+    use rust_decimal::Decimal;
+    use crate::Variable;
+    use super::{format_table, variable_to_json};
+
+    #[test]
+    fn test_format_table() {
+        let headers = vec!["id".to_string(), "username".to_string()];
+        let cells = vec![
+            vec!["1".to_string(), "alice".to_string()],
+            vec!["20".to_string(), "".to_string()],
+        ];
+        let expected = [
+            " id | username",
+            "----+----------",
+            " 1  | alice",
+            " 20 |",
+            "(2 rows)",
+        ].join("\n");
+
+        assert_eq!(format_table(&headers, &cells), expected);
+        assert_eq!(format_table(&[], &[]), "(0 rows)");
+    }
+
+    #[test]
+    fn test_variable_to_json_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let time = NaiveTime::from_hms_milli_opt(3, 4, 5, 678).unwrap();
+        let datetime = date.and_time(time);
+
+        assert_eq!(serde_json::from_value::<NaiveDate>(variable_to_json(&Variable::Date(date))).unwrap(), date);
+        assert_eq!(serde_json::from_value::<NaiveTime>(variable_to_json(&Variable::Time(time))).unwrap(), time);
+        assert_eq!(variable_to_json(&Variable::DateTime(datetime)), "2024-01-02T03:04:05.678");
+        assert_eq!(serde_json::from_value::<NaiveDateTime>(variable_to_json(&Variable::DateTime(datetime))).unwrap(), datetime);
+
+        let decimal = Decimal::new(1250, 2);
+        assert_eq!(serde_json::from_value::<f64>(variable_to_json(&Variable::Decimal(decimal))).unwrap(), 12.5);
+        assert_eq!(serde_json::from_value::<Decimal>(variable_to_json(&Variable::Decimal(decimal))).unwrap(), decimal);
+        assert_eq!(variable_to_json(&Variable::Decimal(Decimal::new(42, 0))), 42);
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+use crate::Variable;
+
+// `time` values are bound through the chrono based variants, so they behave the same way
+// as the chrono values on binding, Display and extraction.
+
+fn date_to_chrono(value: Date) -> NaiveDate {
+    NaiveDate::from_ymd_opt(value.year(), u8::from(value.month()) as u32, value.day() as u32)
+        .expect("time::Date should be in the range of chrono::NaiveDate")
+}
+
+fn time_to_chrono(value: Time) -> NaiveTime {
+    NaiveTime::from_hms_nano_opt(value.hour() as u32, value.minute() as u32, value.second() as u32, value.nanosecond())
+        .expect("time::Time should be a valid chrono::NaiveTime")
+}
+
+fn primitive_datetime_to_chrono(value: PrimitiveDateTime) -> NaiveDateTime {
+    NaiveDateTime::new(date_to_chrono(value.date()), time_to_chrono(value.time()))
+}
+
+impl From<Date> for Variable {
+    fn from(value: Date) -> Self {
+        Self::Date(date_to_chrono(value))
+    }
+}
+
+impl From<Time> for Variable {
+    fn from(value: Time) -> Self {
+        Self::Time(time_to_chrono(value))
+    }
+}
+
+impl From<PrimitiveDateTime> for Variable {
+    fn from(value: PrimitiveDateTime) -> Self {
+        Self::DateTime(primitive_datetime_to_chrono(value))
+    }
+}
+
+impl From<OffsetDateTime> for Variable {
+    fn from(value: OffsetDateTime) -> Self {
+        let offset = FixedOffset::east_opt(value.offset().whole_seconds())
+            .expect("time::UtcOffset should be in the range of chrono::FixedOffset");
+        let utc = value.to_offset(UtcOffset::UTC);
+        let naive_utc = primitive_datetime_to_chrono(PrimitiveDateTime::new(utc.date(), utc.time()));
+        Self::DateTimeTz(DateTime::from_naive_utc_and_offset(naive_utc, offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+    use crate::Variable;
+
+    #[test]
+    fn test_time_values_to_variable() {
+        let date = Date::from_calendar_date(2023, Month::November, 29).unwrap();
+        let time = Time::from_hms(21, 0, 9).unwrap();
+        let datetime = PrimitiveDateTime::new(date, time);
+        let offset_datetime = datetime.assume_offset(UtcOffset::from_hms(9, 0, 0).unwrap());
+
+        assert_eq!(Variable::from(date).to_string(), "2023-11-29");
+        assert_eq!(Variable::from(time).to_string(), "21:00:09");
+        assert_eq!(Variable::from(datetime).to_string(), "2023-11-29 21:00:09");
+        assert_eq!(Variable::from(offset_datetime).to_string(), "2023-11-29T21:00:09+09:00");
+        assert!(matches!(Variable::from(OffsetDateTime::UNIX_EPOCH), Variable::DateTimeTz(_)));
+    }
+}
@@ -0,0 +1,411 @@
+use std::error::Error;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use cidr::{IpCidr, IpInet};
+use eui48::MacAddress;
+use rust_decimal::Decimal;
+use tokio_postgres::types::{to_sql_checked, FromSql, IsNull, Kind, ToSql, Type};
+use bytes::BytesMut;
+use postgres_protocol::types as protocol;
+use crate::types::custom::get_custom_type_reader;
+use crate::types::interval::Interval;
+use crate::types::pg_enum::EnumLabel;
+use crate::types::range::{Range, RangeBound};
+use crate::Variable;
+
+impl ToSql for Variable {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            Variable::Text(value) => value.to_sql_checked(ty, out),
+            Variable::Secret(value) => value.to_sql_checked(ty, out),
+            Variable::SmallInt(value) => value.to_sql_checked(ty, out),
+            Variable::Int(value) => value.to_sql_checked(ty, out),
+            Variable::BigInt(value) => value.to_sql_checked(ty, out),
+            Variable::Float(value) => value.to_sql_checked(ty, out),
+            Variable::Double(value) => value.to_sql_checked(ty, out),
+            Variable::Decimal(value) => value.to_sql_checked(ty, out),
+            Variable::Date(value) => value.to_sql_checked(ty, out),
+            Variable::DateTime(value) => value.to_sql_checked(ty, out),
+            Variable::DateTimeTz(value) => value.to_sql_checked(ty, out),
+            Variable::Time(value) => value.to_sql_checked(ty, out),
+            Variable::Bool(value) => value.to_sql_checked(ty, out),
+            Variable::Interval(value) => value.to_sql_checked(ty, out),
+            Variable::Inet(value) => value.to_sql_checked(ty, out),
+            Variable::Cidr(value) => value.to_sql_checked(ty, out),
+            Variable::MacAddr(value) => value.to_sql_checked(ty, out),
+            Variable::Enum(value) => value.to_sql_checked(ty, out),
+            Variable::Range(value) => value.to_sql_checked(ty, out),
+            Variable::Array(values) => values.to_sql_checked(ty, out),
+            Variable::Null => Ok(IsNull::Yes),
+            Variable::Custom(value) => {
+                if ty.name() != value.type_name() {
+                    return Err(format!("'{}' type is expected but the column type is '{}'", value.type_name(), ty.name()).into())
+                }
+                value.to_sql(ty, out)
+            },
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        if get_custom_type_reader(ty.name()).is_some() {
+            return true
+        }
+        match ty.kind() {
+            Kind::Array(member) => return <Self as ToSql>::accepts(member),
+            Kind::Range(_) => return <Range as ToSql>::accepts(ty),
+            Kind::Enum(_) => return true,
+            _ => {},
+        }
+
+        <String as ToSql>::accepts(ty)
+            || <i16 as ToSql>::accepts(ty)
+            || <i32 as ToSql>::accepts(ty)
+            || <i64 as ToSql>::accepts(ty)
+            || <f32 as ToSql>::accepts(ty)
+            || <f64 as ToSql>::accepts(ty)
+            || <Decimal as ToSql>::accepts(ty)
+            || <NaiveDate as ToSql>::accepts(ty)
+            || <NaiveDateTime as ToSql>::accepts(ty)
+            || <DateTime<FixedOffset> as ToSql>::accepts(ty)
+            || <NaiveTime as ToSql>::accepts(ty)
+            || <bool as ToSql>::accepts(ty)
+            || <Interval as ToSql>::accepts(ty)
+            || <IpInet as ToSql>::accepts(ty)
+            || <IpCidr as ToSql>::accepts(ty)
+            || <MacAddress as ToSql>::accepts(ty)
+    }
+
+    /// Every variant is bound through its own `to_sql_checked`, so e.g. `Variable::Text` bound to
+    /// an integer column (or inside an integer array) fails with a type error instead of sending
+    /// a malformed value.
+    fn to_sql_checked(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.to_sql(ty, out)
+    }
+}
+
+impl<'a> FromSql<'a> for Variable {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        if let Some(reader) = get_custom_type_reader(ty.name()) {
+            return Ok(Variable::Custom(reader(ty, raw)?))
+        }
+        match ty.kind() {
+            Kind::Array(_) => return Ok(Variable::Array(Vec::<Variable>::from_sql(ty, raw)?)),
+            Kind::Range(_) => return Ok(Variable::from(Range::from_sql(ty, raw)?)),
+            Kind::Enum(_) => return Ok(Variable::Enum(EnumLabel::from_sql(ty, raw)?)),
+            _ => {},
+        }
+
+        let variable = match *ty {
+            Type::INT2 => Variable::SmallInt(i16::from_sql(ty, raw)?),
+            Type::INT4 => Variable::Int(i32::from_sql(ty, raw)?),
+            Type::INT8 => Variable::BigInt(i64::from_sql(ty, raw)?),
+            Type::FLOAT4 => Variable::Float(f32::from_sql(ty, raw)?),
+            Type::FLOAT8 => Variable::Double(f64::from_sql(ty, raw)?),
+            Type::NUMERIC => Variable::Decimal(Decimal::from_sql(ty, raw)?),
+            Type::DATE => Variable::Date(NaiveDate::from_sql(ty, raw)?),
+            Type::TIMESTAMP => Variable::DateTime(NaiveDateTime::from_sql(ty, raw)?),
+            Type::TIMESTAMPTZ => Variable::DateTimeTz(DateTime::<FixedOffset>::from_sql(ty, raw)?),
+            Type::TIME => Variable::Time(NaiveTime::from_sql(ty, raw)?),
+            Type::BOOL => Variable::Bool(bool::from_sql(ty, raw)?),
+            Type::INTERVAL => Variable::Interval(Interval::from_sql(ty, raw)?),
+            Type::INET => Variable::Inet(IpInet::from_sql(ty, raw)?),
+            Type::CIDR => Variable::Cidr(IpCidr::from_sql(ty, raw)?),
+            Type::MACADDR => Variable::MacAddr(MacAddress::from_sql(ty, raw)?),
+            _ => Variable::Text(String::from_sql(ty, raw)?),
+        };
+        Ok(variable)
+    }
+
+    /// NULL is extracted as `Variable::Null`, e.g. for NULL elements of an array.
+    fn from_sql_null(_ty: &Type) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Variable::Null)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Variable as ToSql>::accepts(ty)
+    }
+}
+
+impl ToSql for Interval {
+    fn to_sql(&self, _ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        out.extend_from_slice(&self.get_microseconds().to_be_bytes());
+        out.extend_from_slice(&self.get_days().to_be_bytes());
+        out.extend_from_slice(&self.get_months().to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::INTERVAL
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Interval {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        if raw.len() != 16 {
+            return Err("invalid message length: interval should have 16 bytes".into())
+        }
+        let microseconds = i64::from_be_bytes(raw[0..8].try_into()?);
+        let days = i32::from_be_bytes(raw[8..12].try_into()?);
+        let months = i32::from_be_bytes(raw[12..16].try_into()?);
+        Ok(Interval::new(months, days, microseconds))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::INTERVAL
+    }
+}
+
+impl ToSql for Range {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let Kind::Range(subtype) = ty.kind() else {
+            return Err(format!("'{}' is not a range type", ty).into())
+        };
+
+        match self {
+            Range::Empty => protocol::empty_range_to_sql(out),
+            Range::NonEmpty { lower, upper } => {
+                protocol::range_to_sql(
+                    |buf| bound_to_sql(lower, subtype, buf),
+                    |buf| bound_to_sql(upper, subtype, buf),
+                    out)?;
+            }
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match ty.kind() {
+            Kind::Range(subtype) => <Variable as ToSql>::accepts(subtype),
+            _ => false,
+        }
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Range {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let Kind::Range(subtype) = ty.kind() else {
+            return Err(format!("'{}' is not a range type", ty).into())
+        };
+
+        match protocol::range_from_sql(raw)? {
+            protocol::Range::Empty => Ok(Range::Empty),
+            protocol::Range::Nonempty(lower, upper) => {
+                Ok(Range::NonEmpty {
+                    lower: bound_from_sql(lower, subtype)?,
+                    upper: bound_from_sql(upper, subtype)?,
+                })
+            }
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Range as ToSql>::accepts(ty)
+    }
+}
+
+impl ToSql for EnumLabel {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        if let (Kind::Enum(_), Some(type_name)) = (ty.kind(), self.get_type_name()) {
+            if ty.name() != type_name {
+                return Err(format!("'{}' enum is expected but the column type is '{}'", type_name, ty.name()).into())
+            }
+        }
+        protocol::text_to_sql(self.get_label(), out);
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(ty.kind(), Kind::Enum(_)) || <String as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for EnumLabel {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(EnumLabel::new(Some(ty.name()), protocol::text_from_sql(raw)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(ty.kind(), Kind::Enum(_))
+    }
+}
+
+fn bound_to_sql(bound: &RangeBound, subtype: &Type, buf: &mut BytesMut)
+    -> Result<protocol::RangeBound<postgres_protocol::IsNull>, Box<dyn Error + Sync + Send>> {
+    let bound = match bound {
+        RangeBound::Inclusive(value) => {
+            value.to_sql_checked(subtype, buf)?;
+            protocol::RangeBound::Inclusive(postgres_protocol::IsNull::No)
+        },
+        RangeBound::Exclusive(value) => {
+            value.to_sql_checked(subtype, buf)?;
+            protocol::RangeBound::Exclusive(postgres_protocol::IsNull::No)
+        },
+        RangeBound::Unbounded => protocol::RangeBound::Unbounded,
+    };
+    Ok(bound)
+}
+
+fn bound_from_sql(bound: protocol::RangeBound<Option<&[u8]>>, subtype: &Type)
+    -> Result<RangeBound, Box<dyn Error + Sync + Send>> {
+    let bound = match bound {
+        protocol::RangeBound::Inclusive(Some(raw)) => RangeBound::Inclusive(Variable::from_sql(subtype, raw)?),
+        protocol::RangeBound::Exclusive(Some(raw)) => RangeBound::Exclusive(Variable::from_sql(subtype, raw)?),
+        protocol::RangeBound::Inclusive(None)
+        | protocol::RangeBound::Exclusive(None)
+        | protocol::RangeBound::Unbounded => RangeBound::Unbounded,
+    };
+    Ok(bound)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use eui48::MacAddress;
+    use std::error::Error;
+    use std::fmt::{Display, Formatter};
+    use std::sync::Arc;
+    use tokio_postgres::types::{FromSql, IsNull, Kind, ToSql, Type};
+    use bytes::BytesMut;
+    use crate::types::custom::{register_custom_type, CustomType};
+    use crate::types::interval::Interval;
+    use crate::types::pg_enum::EnumLabel;
+    use crate::types::range::{Range, RangeBound};
+    use crate::Variable;
+
+    #[test]
+    fn test_array_round_trip() {
+        let array = Variable::create_array(vec![Variable::Int(1), Variable::Int(2)]).unwrap();
+        let mut buf = BytesMut::new();
+        array.to_sql_checked(&Type::INT4_ARRAY, &mut buf).unwrap();
+
+        let Variable::Array(values) = Variable::from_sql(&Type::INT4_ARRAY, &buf).unwrap() else { panic!() };
+        assert_eq!(values.iter().map(|value| value.to_string()).collect::<Vec<String>>(), vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_interval_round_trip() {
+        let interval = Interval::new(1, 2, 3_500_000);
+        let mut buf = BytesMut::new();
+        Variable::from(interval).to_sql_checked(&Type::INTERVAL, &mut buf).unwrap();
+
+        let Variable::Interval(parsed) = Variable::from_sql(&Type::INTERVAL, &buf).unwrap() else { panic!() };
+        assert_eq!(parsed, interval);
+        assert_eq!(parsed.to_string(), "P1M2DT3.500000S");
+    }
+
+    #[test]
+    fn test_network_address_round_trip() {
+        let address: IpAddr = "192.168.0.1".parse().unwrap();
+        let mut buf = BytesMut::new();
+        Variable::from(address).to_sql_checked(&Type::INET, &mut buf).unwrap();
+
+        let parsed = Variable::from_sql(&Type::INET, &buf).unwrap();
+        assert_eq!(parsed.to_string(), "192.168.0.1");
+
+        let mac = MacAddress::parse_str("08:00:2b:01:02:03").unwrap();
+        let mut buf = BytesMut::new();
+        Variable::from(mac).to_sql_checked(&Type::MACADDR, &mut buf).unwrap();
+        assert_eq!(Variable::from_sql(&Type::MACADDR, &buf).unwrap().to_string(), "08:00:2b:01:02:03");
+    }
+
+    #[test]
+    fn test_range_round_trip() {
+        let range = Range::new(RangeBound::Inclusive(Variable::Int(1)), RangeBound::Exclusive(Variable::Int(10))).unwrap();
+        let mut buf = BytesMut::new();
+        Variable::from(range).to_sql_checked(&Type::INT4_RANGE, &mut buf).unwrap();
+
+        let parsed = Variable::from_sql(&Type::INT4_RANGE, &buf).unwrap();
+        assert_eq!(parsed.to_string(), "[1,10)");
+
+        let unbounded = Range::new(RangeBound::Unbounded, RangeBound::Inclusive(Variable::Int(5))).unwrap();
+        let mut buf = BytesMut::new();
+        Variable::from(unbounded).to_sql_checked(&Type::INT4_RANGE, &mut buf).unwrap();
+        assert_eq!(Variable::from_sql(&Type::INT4_RANGE, &buf).unwrap().to_string(), "(,5]");
+        assert!(Range::new(RangeBound::Inclusive(Variable::Int(1)), RangeBound::Inclusive(Variable::BigInt(2))).is_err());
+    }
+
+    #[test]
+    fn test_enum_type_name_checked() {
+        let status = Type::new("status".to_string(), 0, Kind::Enum(vec!["active".to_string()]), "public".to_string());
+        let other = Type::new("mood".to_string(), 0, Kind::Enum(vec!["happy".to_string()]), "public".to_string());
+        let label = EnumLabel::new(Some("status"), "active");
+
+        let mut buf = BytesMut::new();
+        Variable::Enum(label.clone()).to_sql_checked(&status, &mut buf).unwrap();
+        assert_eq!(EnumLabel::from_sql(&status, &buf).unwrap(), label);
+        assert!(Variable::Enum(label).to_sql_checked(&other, &mut BytesMut::new()).is_err());
+    }
+
+    #[test]
+    fn test_secret_redacted() {
+        let secret = Variable::Secret("password".to_string());
+        let mut buf = BytesMut::new();
+        secret.to_sql_checked(&Type::TEXT, &mut buf).unwrap();
+
+        assert_eq!(String::from_sql(&Type::TEXT, &buf).unwrap(), "password");
+        assert_eq!(secret.to_string(), "****");
+        assert!(!format!("{:?}", Variable::create_array(vec![secret]).unwrap()).contains("password"));
+    }
+
+    #[test]
+    fn test_null_round_trip() {
+        let array = Variable::create_array(vec![Variable::Int(1), Variable::Null]).unwrap();
+        let mut buf = BytesMut::new();
+        array.to_sql_checked(&Type::INT4_ARRAY, &mut buf).unwrap();
+
+        let Variable::Array(values) = Variable::from_sql(&Type::INT4_ARRAY, &buf).unwrap() else { panic!() };
+        assert!(matches!(values[..], [Variable::Int(1), Variable::Null]));
+        assert!(matches!(Variable::Null.to_sql_checked(&Type::DATE, &mut BytesMut::new()), Ok(IsNull::Yes)));
+    }
+
+    #[test]
+    fn test_mismatched_type_rejected() {
+        let mut buf = BytesMut::new();
+        assert!(Variable::Text("a".to_string()).to_sql_checked(&Type::INT4, &mut buf).is_err());
+        assert!(Variable::try_from(vec!["a".to_string()]).unwrap().to_sql_checked(&Type::INT4_ARRAY, &mut buf).is_err());
+        assert!(Variable::try_from(vec![Variable::Int(1), Variable::Text("a".to_string())]).is_err());
+    }
+
+    #[derive(Debug)]
+    struct Point(i32, i32);
+
+    impl Display for Point {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "({},{})", self.0, self.1)
+        }
+    }
+
+    impl CustomType for Point {
+        fn type_name(&self) -> &str {
+            "int_point"
+        }
+
+        fn to_sql(&self, _ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+            out.extend_from_slice(&self.0.to_be_bytes());
+            out.extend_from_slice(&self.1.to_be_bytes());
+            Ok(IsNull::No)
+        }
+    }
+
+    #[test]
+    fn test_custom_type_round_trip() {
+        let point_type = Type::new("int_point".to_string(), 0, Kind::Composite(vec![]), "public".to_string());
+        register_custom_type("int_point", |_ty, raw| {
+            let x = i32::from_be_bytes(raw[0..4].try_into()?);
+            let y = i32::from_be_bytes(raw[4..8].try_into()?);
+            Ok(Arc::new(Point(x, y)))
+        });
+
+        let mut buf = BytesMut::new();
+        Variable::Custom(Arc::new(Point(1, 2))).to_sql_checked(&point_type, &mut buf).unwrap();
+        let parsed = Variable::from_sql(&point_type, &buf).unwrap();
+        assert!(matches!(parsed, Variable::Custom(_)));
+        assert_eq!(parsed.to_string(), "(1,2)");
+        assert!(Variable::Custom(Arc::new(Point(1, 2))).to_sql_checked(&Type::TEXT, &mut BytesMut::new()).is_err());
+    }
+}
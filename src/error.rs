@@ -0,0 +1,151 @@
+use std::fmt::{Display, Formatter};
+use tokio_postgres::Error as PGError;
+use crate::legacy::errors::{ConditionError, DataParseError, InsertValueError, JoinTableError, PostgresBaseError, QueryColumnError, UpdateSetError};
+use crate::utils::errors::{ConnectionConfigError, DatabaseError, ExecutorError, GeneratorError};
+
+/// The error type covering all the errors of this crate, so an application can use `?` uniformly.
+///
+/// Each variant wraps the error of a module as it is. The `Display` and `source` are the ones of the
+/// wrapped error, so the chain goes down to the `tokio_postgres::Error` for the server errors.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::legacy::postgres::PostgresBase;
+/// use safety_postgres::legacy::sql_base::InsertRecords;
+///
+/// async fn insert_user(db: &PostgresBase) -> Result<u64, safety_postgres::Error> {
+///     let mut records = InsertRecords::new(&["username"]);
+///     records.add_record(&["John"])?;
+///     let inserted = db.insert(&records).await?;
+///     Ok(inserted)
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    ConnectionConfig(ConnectionConfigError),
+    Generator(GeneratorError),
+    Executor(ExecutorError),
+    Database(DatabaseError),
+    Postgres(PGError),
+    PostgresBase(PostgresBaseError),
+    DataParse(DataParseError),
+    JoinTable(JoinTableError),
+    Condition(ConditionError),
+    QueryColumn(QueryColumnError),
+    UpdateSet(UpdateSetError),
+    InsertValue(InsertValueError),
+}
+
+impl Error {
+    /// Returns the error reported by the PostgreSQL server, if this is one.
+    pub fn get_database_error(&self) -> Option<&DatabaseError> {
+        match self {
+            Self::Database(e) => Some(e),
+            Self::Executor(e) => e.get_database_error(),
+            Self::PostgresBase(e) => e.get_database_error(),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the same operation may succeed when it's retried: the connection was closed,
+    /// or the server reported a connection exception, a serialization failure, a deadlock or a shutdown.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Database(e) => e.is_retryable(),
+            Self::Postgres(e) => e.is_closed(),
+            Self::Executor(e) => e.is_retryable(),
+            Self::PostgresBase(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    fn get_inner(&self) -> &(dyn std::error::Error + 'static) {
+        match self {
+            Self::ConnectionConfig(e) => e,
+            Self::Generator(e) => e,
+            Self::Executor(e) => e,
+            Self::Database(e) => e,
+            Self::Postgres(e) => e,
+            Self::PostgresBase(e) => e,
+            Self::DataParse(e) => e,
+            Self::JoinTable(e) => e,
+            Self::Condition(e) => e,
+            Self::QueryColumn(e) => e,
+            Self::UpdateSet(e) => e,
+            Self::InsertValue(e) => e,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.get_inner())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.get_inner().source()
+    }
+}
+
+impl From<PGError> for Error {
+    fn from(value: PGError) -> Self {
+        match DatabaseError::from_error(value) {
+            Ok(e) => Self::Database(e),
+            Err(e) => Self::Postgres(e),
+        }
+    }
+}
+
+macro_rules! impl_from_module_error {
+    ($($variant:ident($error:ty)),+ $(,)?) => {
+        $(
+            impl From<$error> for Error {
+                fn from(value: $error) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_module_error!(
+    ConnectionConfig(ConnectionConfigError),
+    Generator(GeneratorError),
+    Executor(ExecutorError),
+    Database(DatabaseError),
+    PostgresBase(PostgresBaseError),
+    DataParse(DataParseError),
+    JoinTable(JoinTableError),
+    Condition(ConditionError),
+    QueryColumn(QueryColumnError),
+    UpdateSet(UpdateSetError),
+    InsertValue(InsertValueError),
+);
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as StdError;
+    use crate::legacy::errors::{JoinTableError, PostgresBaseError};
+    use crate::utils::errors::GeneratorError;
+    use super::Error;
+
+    fn generate() -> Result<(), Error> {
+        Err(GeneratorError::InvalidInputError("bad input".to_string()))?
+    }
+
+    #[test]
+    fn test_from_module_errors() {
+        let e = generate().unwrap_err();
+        assert!(matches!(e, Error::Generator(GeneratorError::InvalidInputError(_))));
+        assert_eq!(e.to_string(), "Input data is invalid due to bad input");
+        assert!(e.source().is_none());
+        assert!(e.get_database_error().is_none());
+
+        let e = Error::from(PostgresBaseError::StaleRowError("'version' is no longer '3'".to_string()));
+        assert_eq!(e.to_string(), PostgresBaseError::StaleRowError("'version' is no longer '3'".to_string()).to_string());
+        assert!(matches!(Error::from(JoinTableError::InputInvalidError(String::new())), Error::JoinTable(_)));
+    }
+}
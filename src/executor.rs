@@ -1,6 +1,21 @@
 mod manipulations;
-mod definitions;
+pub mod definitions;
 mod controls;
 mod transactions;
-mod query;
-mod base;
\ No newline at end of file
+pub mod query;
+pub mod base;
+pub mod explain;
+pub mod dry_run;
+pub mod policy;
+pub mod audit;
+pub mod comment;
+pub mod retry;
+pub mod observer;
+pub mod introspection;
+pub mod validation;
+pub mod migrations;
+pub mod sequence;
+pub mod mock;
+pub mod capture;
+pub mod fixtures;
+pub mod repository;
@@ -0,0 +1,228 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_postgres::{Client, Error as PGError};
+use crate::executor::dry_run::DryRun;
+use crate::utils::errors::GeneratorError;
+use crate::utils::identifier::{quote_identifier, validate_identifier};
+
+/// A record of an executed statement passed to the audit log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditRecord {
+    /// The operation of the statement, e.g. `SELECT` or `UPDATE`.
+    pub operation: String,
+    /// The main table of the statement.
+    pub table_name: String,
+    /// The number of the returned (`SELECT`) or affected (DML) rows.
+    pub affected_rows: u64,
+    /// The execution time of the statement.
+    pub duration: Duration,
+    /// The actor id supplied by the caller via `AuditLog::with_actor`.
+    pub actor: Option<String>,
+    /// The statement text with the `$n` placeholders (the parameter values are not recorded).
+    pub statement: String,
+}
+
+impl AuditRecord {
+    pub(crate) fn new(statement: &str, table_name: &str, affected_rows: u64, duration: Duration, actor: Option<String>) -> Self {
+        let operation = statement
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_uppercase();
+        Self {
+            operation,
+            table_name: table_name.to_string(),
+            affected_rows,
+            duration,
+            actor,
+            statement: statement.to_string(),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum AuditSink {
+    Callback(Arc<dyn Fn(&AuditRecord) + Send + Sync>),
+    Table {
+        schema_name: Option<String>,
+        table_name: String,
+    },
+}
+
+/// Destination of the audit records of the executed statements.
+///
+/// The audit table should have the following columns (other columns with defaults are allowed):
+///
+/// ```sql
+/// CREATE TABLE audit_log (
+///     operation     text,
+///     table_name    text,
+///     affected_rows bigint,
+///     duration_ms   double precision,
+///     actor         text,
+///     statement     text,
+///     executed_at   timestamptz DEFAULT now()
+/// );
+/// ```
+///
+/// For INSERT, UPDATE and DELETE the audit row is inserted in the same transaction as the statement,
+/// so a modification is never committed without its audit record.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::audit::AuditLog;
+///
+/// let callback_log = AuditLog::to_callback(|record| {
+///     println!("{} on {} by {:?}", record.operation, record.table_name, record.actor);
+/// }).with_actor("user-42");
+///
+/// let table_log = AuditLog::to_table("audit.audit_log").expect("invalid table name");
+/// ```
+#[derive(Clone)]
+pub struct AuditLog {
+    sink: AuditSink,
+    actor: Option<String>,
+}
+
+impl AuditLog {
+    /// Creates an audit log passing each record to `callback`.
+    pub fn to_callback<F: Fn(&AuditRecord) + Send + Sync + 'static>(callback: F) -> Self {
+        Self {
+            sink: AuditSink::Callback(Arc::new(callback)),
+            actor: None,
+        }
+    }
+
+    /// Creates an audit log inserting each record into `table_name` (`"table"` or `"schema.table"`).
+    ///
+    /// The schema and table names are quoted by `quote_identifier` when the statement is rendered.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError::InvalidTableNameError` if the schema or table name isn't accepted
+    /// by the `IdentifierPolicy`.
+    pub fn to_table(table_name: &str) -> Result<Self, GeneratorError> {
+        let (schema_name, name) = match table_name.split_once('.') {
+            Some((schema_name, name)) => (Some(schema_name), name),
+            None => (None, table_name),
+        };
+        let is_valid = validate_identifier(name) && schema_name.is_none_or(validate_identifier);
+        if !is_valid {
+            return Err(GeneratorError::InvalidTableNameError(
+                format!("'{}' is not a valid audit table name.", table_name)))
+        }
+        Ok(Self {
+            sink: AuditSink::Table {
+                schema_name: schema_name.map(str::to_string),
+                table_name: name.to_string(),
+            },
+            actor: None,
+        })
+    }
+
+    /// Sets the actor id recorded with the following statements.
+    pub fn with_actor(mut self, actor: &str) -> Self {
+        self.actor = Some(actor.to_string());
+        self
+    }
+
+    /// Returns whether the records are inserted into an audit table.
+    pub(crate) fn is_table(&self) -> bool {
+        matches!(self.sink, AuditSink::Table { .. })
+    }
+
+    /// Returns the quoted name of the audit table, or `None` for a callback.
+    fn get_quoted_table_name(&self) -> Option<String> {
+        match &self.sink {
+            AuditSink::Callback(_) => None,
+            AuditSink::Table { schema_name, table_name } => Some(quote_table_name(schema_name.as_deref(), table_name)),
+        }
+    }
+
+    pub(crate) fn get_actor(&self) -> Option<String> {
+        self.actor.clone()
+    }
+
+    /// Returns the INSERT of the record into the audit table as a `DryRun`, or `None` for the callback sink.
+    pub(crate) fn dry_run(&self, record: &AuditRecord) -> Option<DryRun> {
+        match &self.sink {
+            AuditSink::Callback(_) => None,
+            AuditSink::Table { schema_name, table_name } => Some(DryRun::new(
+                insert_statement(schema_name.as_deref(), table_name),
+                vec![
+                    record.operation.clone(),
+                    record.table_name.clone(),
+                    record.affected_rows.to_string(),
+                    (record.duration.as_secs_f64() * 1000.0).to_string(),
+                    record.actor.clone().unwrap_or_else(|| "NULL".to_string()),
+                    record.statement.clone(),
+                ])),
+        }
+    }
+
+    /// Passes the record to the callback or inserts it into the audit table with `client`.
+    pub(crate) async fn write(&self, client: &Client, record: &AuditRecord) -> Result<(), PGError> {
+        match &self.sink {
+            AuditSink::Callback(callback) => {
+                callback(record);
+                Ok(())
+            }
+            AuditSink::Table { schema_name, table_name } => {
+                let statement = insert_statement(schema_name.as_deref(), table_name);
+                client.execute(
+                    statement.as_str(),
+                    &[&record.operation, &record.table_name, &(record.affected_rows as i64),
+                        &(record.duration.as_secs_f64() * 1000.0), &record.actor, &record.statement]).await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+fn insert_statement(schema_name: Option<&str>, table_name: &str) -> String {
+    format!(
+        "INSERT INTO {} (operation, table_name, affected_rows, duration_ms, actor, statement) VALUES ($1, $2, $3, $4, $5, $6)",
+        quote_table_name(schema_name, table_name))
+}
+
+fn quote_table_name(schema_name: Option<&str>, table_name: &str) -> String {
+    match schema_name {
+        Some(schema_name) => format!("{}.{}", quote_identifier(schema_name), quote_identifier(table_name)),
+        None => quote_identifier(table_name),
+    }
+}
+
+impl Debug for AuditLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.get_quoted_table_name() {
+            None => write!(f, "AuditLog(callback, actor: {:?})", self.actor),
+            Some(table_name) => write!(f, "AuditLog(table: {}, actor: {:?})", table_name, self.actor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::{AuditLog, AuditRecord};
+
+    #[test]
+    fn test_audit_log() {
+        let record = AuditRecord::new("update users SET name = $1 WHERE id = $2", "users", 1, Duration::from_millis(3), Some("admin".to_string()));
+        assert_eq!(record.operation, "UPDATE");
+        assert_eq!(record.table_name, "users");
+
+        let audit_log = AuditLog::to_callback(|_| {}).with_actor("admin");
+        assert!(!audit_log.is_table());
+        assert_eq!(audit_log.get_actor(), Some("admin".to_string()));
+
+        assert!(AuditLog::to_table("audit.audit_log").unwrap().is_table());
+        assert!(AuditLog::to_table("audit_log; DROP TABLE users").is_err());
+        assert!(AuditLog::to_table("a.b.c").is_err());
+        assert!(AuditLog::to_table(".audit_log").is_err());
+
+        let audit_log = AuditLog::to_table("audit.Log").unwrap();
+        assert_eq!(audit_log.get_quoted_table_name(), Some("audit.\"Log\"".to_string()));
+    }
+}
@@ -1,11 +1,150 @@
-use std::error::Error;
+use std::collections::HashMap;
+use std::future::Future;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio_postgres::{Row, Statement};
 use crate::connector::Connector;
+use crate::executor::comment::StatementComment;
+use crate::executor::dry_run::DryRun;
+use crate::executor::explain::{ExplainOptions, ExplainPlan};
+use crate::executor::introspection::Introspector;
+use crate::executor::sequence::Sequences;
+use crate::executor::validation::{validate_generator, ValidationReport};
 use crate::generator::base::MainGenerator;
+use crate::utils::errors::ExecutorError;
+use crate::Variable;
 
-pub(super) trait Executor {
+pub trait Executor {
     fn new(connector: Connector) -> Self;
-    async fn execute<T, R, E>(&self, generator: T) -> Result<R, E>
+
+    /// Returns the connector the executor runs the statements with.
+    fn get_connector(&self) -> &Connector;
+
+    /// Runs `EXPLAIN (FORMAT JSON, ...)` for the statement of the generator and returns the parsed plan.
+    ///
+    /// With `ExplainOptions::with_analyze` the statement is actually executed by PostgreSQL.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the execution fails or the plan can't be parsed.
+    fn explain<G: MainGenerator + Sync>(&self, generator: &G, options: ExplainOptions) -> impl Future<Output = Result<ExplainPlan, ExecutorError>> + Send
+    where
+        Self: Sync,
+    {
+        explain_plan(self.get_connector(), generator, options)
+    }
+
+    /// Returns the statement and the parameters of the generator which would be executed,
+    /// without touching the database.
+    fn dry_run<G: MainGenerator>(&self, generator: &G) -> DryRun {
+        DryRun::from_generator(generator)
+    }
+
+    /// Returns the introspector reading the structure of the tables from the PostgreSQL catalog.
+    fn introspect(&self) -> Introspector<'_> {
+        Introspector::new(self.get_connector())
+    }
+
+    /// Returns the helpers reading and setting the values of the sequences.
+    fn sequences(&self) -> Sequences<'_> {
+        Sequences::new(self.get_connector())
+    }
+
+    /// Checks the generator against the live schema without executing it: the referenced tables
+    /// and columns exist and the bound `Variable`s are compatible with the types of their placeholders.
+    ///
+    /// The statement is only prepared, so the issues are returned in the report instead of as the error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the catalog can't be read, e.g. the connection is closed.
+    fn validate<G: MainGenerator + Sync>(&self, generator: &G) -> impl Future<Output = Result<ValidationReport, ExecutorError>> + Send
     where
-        T: MainGenerator,
-        E: Error;
-}
\ No newline at end of file
+        Self: Sync,
+    {
+        validate_generator(self.get_connector(), generator)
+    }
+}
+
+/// Executes the queries of the generators, implemented by `Query` and by `MockExecutor`.
+///
+/// Write the application logic against this trait instead of `Query`, so that it can be unit-tested
+/// with `MockExecutor` without a database.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::base::Execute;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::generator::query::query_column::QueryColumns;
+/// use safety_postgres::utils::errors::ExecutorError;
+/// use safety_postgres::Table;
+///
+/// async fn count_users<E: Execute>(executor: &E) -> Result<usize, ExecutorError> {
+///     let table = Table::create_table(None, "users");
+///     let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+///     let rows = executor.fetch_maps(&generator).await?;
+///     Ok(rows.len())
+/// }
+/// ```
+pub trait Execute {
+    /// Executes the query and returns each row as a map from the column names to the values.
+    fn fetch_maps<G: MainGenerator + Sync>(&self, generator: &G) -> impl Future<Output = Result<Vec<HashMap<String, Variable>>, ExecutorError>> + Send;
+
+    /// Executes the query and deserializes each row into `T` via serde.
+    fn query_as<T: DeserializeOwned + Send, G: MainGenerator + Sync>(&self, generator: &G) -> impl Future<Output = Result<Vec<T>, ExecutorError>> + Send;
+}
+
+/// Prefixes the statement of the generator with `EXPLAIN` and parses the JSON plan.
+pub(super) async fn explain_plan<G: MainGenerator>(connector: &Connector, generator: &G, options: ExplainOptions) -> Result<ExplainPlan, ExecutorError> {
+    let client = connector.get_client()?;
+    let statement = format!("EXPLAIN ({}) {}", options.get_options_text(), generator.get_statement());
+    let parameters = generator.get_params();
+
+    let row = match client.query_one(statement.as_str(), &parameters.get_sql_params()).await {
+        Ok(row) => row,
+        Err(e) => return Err(ExecutorError::from(e).with_statement_snapshot(parameters.get_statement_snapshot(&statement))),
+    };
+    match row.try_get::<usize, Value>(0) {
+        Ok(raw) => ExplainPlan::from_json(raw),
+        Err(e) => Err(ExecutorError::ConvertRowError(e.to_string())),
+    }
+}
+
+/// Prepares the statement of the generator (with the comment appended if it's set)
+/// and queries it with the generator's parameters.
+///
+/// The prepared statement is returned with the rows, so that the result columns are available
+/// even if no row is returned.
+pub(super) async fn query_statement<G: MainGenerator>(connector: &Connector, generator: &G, comment: Option<&StatementComment>) -> Result<(Statement, Vec<Row>), ExecutorError> {
+    let client = connector.get_client()?;
+    let statement_text = match comment {
+        Some(comment) => comment.apply(generator.get_statement().as_str()),
+        None => generator.get_statement(),
+    };
+    let parameters = generator.get_params();
+    let statement = match client.prepare(statement_text.as_str()).await {
+        Ok(statement) => statement,
+        Err(e) => return Err(ExecutorError::from(e).with_statement_snapshot(parameters.get_statement_snapshot(&statement_text))),
+    };
+
+    match client.query(&statement, &parameters.get_sql_params()).await {
+        Ok(rows) => Ok((statement, rows)),
+        Err(e) => Err(ExecutorError::from(e).with_statement_snapshot(parameters.get_statement_snapshot(&statement_text))),
+    }
+}
+
+/// Executes the statement of the generator (with the comment appended if it's set), which returns no rows,
+/// and returns the number of the affected rows.
+pub(super) async fn execute_statement<G: MainGenerator>(connector: &Connector, generator: &G, comment: Option<&StatementComment>) -> Result<u64, ExecutorError> {
+    let client = connector.get_client()?;
+    let statement_text = match comment {
+        Some(comment) => comment.apply(generator.get_statement().as_str()),
+        None => generator.get_statement(),
+    };
+    let parameters = generator.get_params();
+
+    match client.execute(statement_text.as_str(), &parameters.get_sql_params()).await {
+        Ok(affected) => Ok(affected),
+        Err(e) => Err(ExecutorError::from(e).with_statement_snapshot(parameters.get_statement_snapshot(&statement_text))),
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+use crate::executor::dry_run::DryRun;
+
+/// The in-memory log of the statements executed through a connector made by `Connector::capture`.
+///
+/// The log is shared by its clones, so keep a clone to read the statements after handing the connector
+/// to an executor. `Display` writes one statement per line in the format of `DryRun`, which suits
+/// golden-file snapshot tests of the generated statements.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::connector::Connector;
+/// use safety_postgres::executor::base::Executor;
+/// use safety_postgres::executor::capture::StatementLog;
+/// use safety_postgres::executor::query::Query;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::generator::query::query_column::QueryColumns;
+/// use safety_postgres::Table;
+///
+/// async fn snapshot_users_query() {
+///     let statement_log = StatementLog::new();
+///     let query = Query::new(Connector::capture(statement_log.clone()));
+///
+///     let table = Table::create_table(None, "users");
+///     let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+///     query.fetch_column::<i32, _>(&generator).await.unwrap();
+///
+///     assert_eq!(statement_log.to_string(), "SELECT users.* FROM users\n");
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StatementLog {
+    statements: Arc<Mutex<Vec<DryRun>>>,
+}
+
+impl StatementLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the captured statements and their parameters in the executed order.
+    pub fn get_statements(&self) -> Vec<DryRun> {
+        self.statements.lock().unwrap().clone()
+    }
+
+    /// Removes all the captured statements.
+    pub fn clear(&self) {
+        self.statements.lock().unwrap().clear();
+    }
+
+    pub(crate) fn record(&self, dry_run: DryRun) {
+        self.statements.lock().unwrap().push(dry_run);
+    }
+}
+
+impl Display for StatementLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for dry_run in self.statements.lock().unwrap().iter() {
+            writeln!(f, "{}", dry_run)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connector::Connector;
+    use crate::executor::base::Executor;
+    use crate::executor::definitions::Definition;
+    use crate::executor::query::Query;
+    use crate::generator::definitions::maintenance::Analyze;
+    use crate::generator::query::QueryGenerator;
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::Table;
+    use super::StatementLog;
+
+    #[tokio::test]
+    async fn test_capture_statements() {
+        let statement_log = StatementLog::new();
+        let query = Query::new(Connector::capture(statement_log.clone()));
+        let definition = Definition::new(Connector::capture(statement_log.clone()));
+
+        let table = Table::create_table(None, "users");
+        let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+        assert!(query.fetch_column::<i32, _>(&generator).await.unwrap().is_empty());
+        definition.execute(&Analyze::new(&table)).await.unwrap();
+
+        assert_eq!(statement_log.get_statements().len(), 2);
+        assert_eq!(statement_log.to_string(), "SELECT users.* FROM users\nANALYZE users\n");
+
+        statement_log.clear();
+        assert!(statement_log.get_statements().is_empty());
+    }
+}
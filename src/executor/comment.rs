@@ -0,0 +1,70 @@
+use std::fmt::{Display, Formatter};
+
+/// A marginalia-style comment appended to every executed statement, e.g. `/* app=checkout, route=POST /orders */`,
+/// so the load recorded in `pg_stat_statements` or `pg_stat_activity` can be attributed to the application call sites.
+///
+/// The comment delimiters (`/*` and `*/`) in the tags are broken up, so a tag can't close the comment.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::comment::StatementComment;
+///
+/// let comment = StatementComment::new()
+///     .with_tag("app", "checkout")
+///     .with_tag("route", "POST /orders");
+/// assert_eq!(comment.to_string(), "/* app=checkout, route=POST /orders */");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StatementComment {
+    tags: Vec<(String, String)>,
+}
+
+impl StatementComment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `key=value` tag. The tags are written in the order they are added.
+    pub fn with_tag(mut self, key: &str, value: &str) -> Self {
+        self.tags.push((sanitize(key), sanitize(value)));
+        self
+    }
+
+    /// Returns `statement` with this comment appended, or as it is if no tag is set.
+    pub(crate) fn apply(&self, statement: &str) -> String {
+        if self.tags.is_empty() {
+            return statement.to_string()
+        }
+        format!("{} {}", statement, self)
+    }
+}
+
+impl Display for StatementComment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let tags = self.tags
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>();
+        write!(f, "/* {} */", tags.join(", "))
+    }
+}
+
+/// Breaks up the comment delimiters (PostgreSQL block comments nest, so `/*` matters as well as `*/`).
+fn sanitize(text: &str) -> String {
+    text.replace("*/", "* /").replace("/*", "/ *")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatementComment;
+
+    #[test]
+    fn test_statement_comment() {
+        let comment = StatementComment::new().with_tag("app", "checkout").with_tag("route", "POST /orders");
+        assert_eq!(comment.apply("SELECT 1"), "SELECT 1 /* app=checkout, route=POST /orders */");
+        assert_eq!(StatementComment::new().apply("SELECT 1"), "SELECT 1");
+
+        let comment = StatementComment::new().with_tag("route", "*/ DROP TABLE users; /*");
+        assert_eq!(comment.to_string(), "/* route=* / DROP TABLE users; / * */");
+    }
+}
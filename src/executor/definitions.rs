@@ -0,0 +1,123 @@
+use std::time::Instant;
+use crate::connector::Connector;
+use crate::executor::base::Executor;
+use crate::executor::comment::StatementComment;
+use crate::executor::dry_run::DryRun;
+use crate::generator::base::Parameters;
+use crate::generator::definitions::destructive::{AllowDestructive, DropTable, Truncate};
+use crate::generator::definitions::maintenance::{Analyze, Vacuum, VacuumOptions};
+use crate::generator::definitions::materialized_view::RefreshMaterializedView;
+use crate::generator::definitions::DefinitionGenerator;
+use crate::Table;
+use crate::utils::errors::ExecutorError;
+use crate::utils::logging::statement_event;
+
+/// Executes the DDL statements generated by the definition generators, e.g. `CreateTable`.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::connector::Connector;
+/// use safety_postgres::executor::base::Executor;
+/// use safety_postgres::executor::definitions::Definition;
+/// use safety_postgres::generator::definitions::create_table::{ColumnDefinition, CreateTable};
+/// use safety_postgres::Table;
+///
+/// async fn create_users(connector: Connector) {
+///     let definition = Definition::new(connector);
+///
+///     let table = Table::create_table(None, "users");
+///     let create_table = CreateTable::new(&table)
+///         .if_not_exists()
+///         .with_column(ColumnDefinition::new("id", "bigserial"))
+///         .with_primary_key(&["id"]);
+///     definition.execute(&create_table).await.expect("create table failed");
+/// }
+/// ```
+pub struct Definition {
+    connector: Connector,
+    statement_comment: Option<StatementComment>,
+}
+
+impl Executor for Definition {
+    fn new(connector: Connector) -> Self {
+        Self {
+            connector,
+            statement_comment: None,
+        }
+    }
+
+    fn get_connector(&self) -> &Connector {
+        &self.connector
+    }
+}
+
+impl Definition {
+    /// Appends `statement_comment` to every executed statement.
+    pub fn with_statement_comment(mut self, statement_comment: StatementComment) -> Self {
+        self.statement_comment = Some(statement_comment);
+        self
+    }
+
+    /// Executes the DDL statement of `definition`.
+    ///
+    /// The statement is sent with the simple query protocol, so the statements which can't run
+    /// in a transaction block (e.g. `CREATE INDEX CONCURRENTLY`) work as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::UnsafeExecutionError` if the definition is invalid,
+    /// or `ExecutorError` of the execution failure.
+    pub async fn execute<D: DefinitionGenerator>(&self, definition: &D) -> Result<(), ExecutorError> {
+        let statement = definition
+            .get_statement()
+            .map_err(|e| ExecutorError::UnsafeExecutionError(e.to_string()))?;
+        if let Some(statement_log) = self.connector.get_statement_log() {
+            statement_log.record(DryRun::new(statement, Vec::new()));
+            return Ok(())
+        }
+        let statement = match &self.statement_comment {
+            Some(comment) => comment.apply(statement.as_str()),
+            None => statement,
+        };
+
+        let client = self.connector.get_client()?;
+        let started = Instant::now();
+        if let Err(e) = client.batch_execute(statement.as_str()).await {
+            return Err(ExecutorError::from(e).with_statement_snapshot(Parameters::new().get_statement_snapshot(&statement)))
+        }
+        statement_event!(statement, 0, started.elapsed());
+        Ok(())
+    }
+
+    /// Drops `table` confirmed by `allow`. Execute `DropTable` for `IF EXISTS` or `CASCADE`.
+    pub async fn drop_table<'a>(&self, table: &'a Table<'a>, allow: &'a AllowDestructive) -> Result<(), ExecutorError> {
+        self.execute(&DropTable::new(table, allow)).await
+    }
+
+    /// Truncates `table` confirmed by `allow`. Execute `Truncate` for `RESTART IDENTITY` or `CASCADE`.
+    pub async fn truncate<'a>(&self, table: &'a Table<'a>, allow: &'a AllowDestructive) -> Result<(), ExecutorError> {
+        self.execute(&Truncate::new(table, allow)).await
+    }
+
+    /// Refreshes the materialized view `view`.
+    ///
+    /// With `concurrently` the view can be read during the refresh, which requires a unique index on the view.
+    pub async fn refresh_materialized_view<'a>(&self, view: &'a Table<'a>, concurrently: bool) -> Result<(), ExecutorError> {
+        self.execute(&RefreshMaterializedView::new(view, concurrently)).await
+    }
+
+    /// Updates the statistics of `table` used by the planner.
+    pub async fn analyze<'a>(&self, table: &'a Table<'a>) -> Result<(), ExecutorError> {
+        self.execute(&Analyze::new(table)).await
+    }
+
+    /// Vacuums `table` with `options`. `VACUUM FULL` is only issued when `options` carries the `AllowDestructive` of the table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::UnsafeExecutionError` if the table name is invalid or `FULL` is allowed for another table,
+    /// or `ExecutorError` of the execution failure (e.g. it's called in a transaction).
+    pub async fn vacuum<'a>(&self, table: &'a Table<'a>, options: VacuumOptions<'a>) -> Result<(), ExecutorError> {
+        self.execute(&Vacuum::new(table, options)).await
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+use crate::generator::base::MainGenerator;
+
+/// The statement and the bound parameters which would be executed, built without touching the database.
+///
+/// The parameters are rendered with their `Display`, so `Variable::Secret` values stay masked
+/// and the output can be logged or used as a test snapshot.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::base::Executor;
+/// use safety_postgres::executor::query::Query;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::generator::query::query_column::QueryColumns;
+/// use safety_postgres::Table;
+///
+/// fn log_statement(query: &Query) {
+///     let table = Table::create_table(None, "users");
+///     let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+///     println!("{}", query.dry_run(&generator));
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DryRun {
+    statement: String,
+    parameters: Vec<String>,
+}
+
+impl DryRun {
+    pub(crate) fn new(statement: String, parameters: Vec<String>) -> Self {
+        Self {
+            statement,
+            parameters,
+        }
+    }
+
+    pub(crate) fn from_generator<G: MainGenerator>(generator: &G) -> Self {
+        Self::new(generator.get_statement(), generator.get_params().get_display_values())
+    }
+
+    /// Returns the statement text with the `$n` placeholders.
+    pub fn get_statement(&self) -> &str {
+        self.statement.as_str()
+    }
+
+    /// Returns the parameters bound to the placeholders in order.
+    pub fn get_parameters(&self) -> &[String] {
+        &self.parameters
+    }
+}
+
+impl Display for DryRun {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.statement)?;
+        if !self.parameters.is_empty() {
+            let parameters = self.parameters
+                .iter()
+                .enumerate()
+                .map(|(index, parameter)| format!("${} = {}", index + 1, parameter))
+                .collect::<Vec<String>>();
+            write!(f, " -- {}", parameters.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DryRun;
+
+    #[test]
+    fn test_dry_run_display() {
+        let dry_run = DryRun::new(
+            "SELECT * FROM users WHERE id = $1 AND name = $2".to_string(),
+            vec!["1".to_string(), "John".to_string()]);
+
+        assert_eq!(dry_run.get_parameters(), &["1".to_string(), "John".to_string()]);
+        assert_eq!(dry_run.to_string(), "SELECT * FROM users WHERE id = $1 AND name = $2 -- $1 = 1, $2 = John");
+
+        let dry_run = DryRun::new("SELECT * FROM users".to_string(), Vec::new());
+        assert_eq!(dry_run.to_string(), "SELECT * FROM users");
+    }
+}
@@ -0,0 +1,236 @@
+use serde::Deserialize;
+use serde_json::Value;
+use crate::utils::errors::ExecutorError;
+
+/// Options of the `EXPLAIN` statement run by `Executor::explain`.
+///
+/// The plan is always requested with `FORMAT JSON`.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::explain::ExplainOptions;
+///
+/// let options = ExplainOptions::new().with_analyze(true).with_buffers(true);
+/// assert_eq!(options.get_options_text(), "FORMAT JSON, ANALYZE, BUFFERS");
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ExplainOptions {
+    analyze: bool,
+    verbose: bool,
+    buffers: bool,
+}
+
+impl ExplainOptions {
+    /// Creates options which only estimate the plan without executing the statement.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `ANALYZE`, which actually executes the statement to collect the actual times and rows.
+    ///
+    /// Note that the statement is really executed, so an analyzed `UPDATE` or `DELETE` modifies the data.
+    pub fn with_analyze(mut self, analyze: bool) -> Self {
+        self.analyze = analyze;
+        self
+    }
+
+    /// Sets `VERBOSE`, which adds the output columns and the schema qualified names to the plan.
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Sets `BUFFERS`, which adds the buffer usage to the plan. It's mainly useful with `ANALYZE`.
+    pub fn with_buffers(mut self, buffers: bool) -> Self {
+        self.buffers = buffers;
+        self
+    }
+
+    /// Returns the option list placed in the parentheses of `EXPLAIN (...)`.
+    pub fn get_options_text(&self) -> String {
+        let mut options = vec!["FORMAT JSON"];
+        if self.analyze {
+            options.push("ANALYZE");
+        }
+        if self.verbose {
+            options.push("VERBOSE");
+        }
+        if self.buffers {
+            options.push("BUFFERS");
+        }
+        options.join(", ")
+    }
+}
+
+/// A node of the query plan, e.g. `Seq Scan` or `Hash Join`.
+///
+/// The `actual_*` fields are only set when the plan is explained with `ANALYZE`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PlanNode {
+    #[serde(rename = "Node Type")]
+    pub node_type: String,
+    #[serde(rename = "Relation Name")]
+    pub relation_name: Option<String>,
+    #[serde(rename = "Startup Cost")]
+    pub startup_cost: f64,
+    #[serde(rename = "Total Cost")]
+    pub total_cost: f64,
+    #[serde(rename = "Plan Rows")]
+    pub plan_rows: f64,
+    #[serde(rename = "Plan Width")]
+    pub plan_width: i64,
+    #[serde(rename = "Actual Total Time")]
+    pub actual_total_time: Option<f64>,
+    #[serde(rename = "Actual Rows")]
+    pub actual_rows: Option<f64>,
+    #[serde(rename = "Plans", default)]
+    pub children: Vec<PlanNode>,
+}
+
+/// The query plan returned by `Executor::explain`.
+///
+/// `raw` keeps the whole JSON output of PostgreSQL for the properties which are not mapped to the fields.
+#[derive(Clone, Debug)]
+pub struct ExplainPlan {
+    pub root: PlanNode,
+    pub planning_time: Option<f64>,
+    pub execution_time: Option<f64>,
+    pub raw: Value,
+}
+
+impl ExplainPlan {
+    /// Parses the output of `EXPLAIN (FORMAT JSON)`.
+    pub(crate) fn from_json(raw: Value) -> Result<Self, ExecutorError> {
+        let plan = match raw.get(0) {
+            Some(plan) => plan,
+            None => return Err(ExecutorError::ConvertRowError(
+                format!("The explain output '{}' has no plan.", raw))),
+        };
+        let root = match plan.get("Plan").map(PlanNode::deserialize) {
+            Some(Ok(root)) => root,
+            Some(Err(e)) => return Err(ExecutorError::ConvertRowError(e.to_string())),
+            None => return Err(ExecutorError::ConvertRowError(
+                format!("The explain output '{}' has no plan.", raw))),
+        };
+        let planning_time = plan.get("Planning Time").and_then(Value::as_f64);
+        let execution_time = plan.get("Execution Time").and_then(Value::as_f64);
+
+        Ok(Self {
+            root,
+            planning_time,
+            execution_time,
+            raw,
+        })
+    }
+
+    /// Returns the estimated total cost of the whole plan.
+    pub fn get_total_cost(&self) -> f64 {
+        self.root.total_cost
+    }
+
+    /// Returns the estimated number of rows returned by the whole plan.
+    pub fn get_plan_rows(&self) -> f64 {
+        self.root.plan_rows
+    }
+}
+
+/// Thresholds of the estimated plan above which `Query` refuses to execute a statement.
+///
+/// The limits are checked against the root node of `EXPLAIN` without `ANALYZE`,
+/// so they are the planner's estimates and no statement is executed for the check.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::explain::CostLimit;
+///
+/// let cost_limit = CostLimit::new().with_max_rows(10_000.0).with_max_cost(100_000.0);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CostLimit {
+    max_rows: Option<f64>,
+    max_cost: Option<f64>,
+}
+
+impl CostLimit {
+    /// Creates a limit without any threshold.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of the estimated rows.
+    pub fn with_max_rows(mut self, max_rows: f64) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Sets the maximum estimated total cost (in the planner's cost units).
+    pub fn with_max_cost(mut self, max_cost: f64) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Returns `ExecutorError::UnsafeExecutionError` if the plan exceeds one of the thresholds.
+    pub(crate) fn check(&self, plan: &ExplainPlan) -> Result<(), ExecutorError> {
+        if let Some(max_rows) = self.max_rows {
+            if plan.get_plan_rows() > max_rows {
+                return Err(ExecutorError::UnsafeExecutionError(
+                    format!("the estimated {} rows exceeding the limit {}", plan.get_plan_rows(), max_rows)))
+            }
+        }
+        if let Some(max_cost) = self.max_cost {
+            if plan.get_total_cost() > max_cost {
+                return Err(ExecutorError::UnsafeExecutionError(
+                    format!("the estimated cost {} exceeding the limit {}", plan.get_total_cost(), max_cost)))
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::{CostLimit, ExplainOptions, ExplainPlan};
+
+    #[test]
+    fn test_explain_plan() {
+        assert_eq!(ExplainOptions::new().get_options_text(), "FORMAT JSON");
+        assert_eq!(
+            ExplainOptions::new().with_analyze(true).with_verbose(true).with_buffers(true).get_options_text(),
+            "FORMAT JSON, ANALYZE, VERBOSE, BUFFERS");
+
+        let raw = json!([{
+            "Plan": {
+                "Node Type": "Hash Join",
+                "Startup Cost": 1.09,
+                "Total Cost": 24.5,
+                "Plan Rows": 10,
+                "Plan Width": 64,
+                "Actual Total Time": 0.12,
+                "Actual Rows": 10,
+                "Plans": [
+                    {"Node Type": "Seq Scan", "Relation Name": "records", "Startup Cost": 0.0,
+                     "Total Cost": 20.7, "Plan Rows": 1070, "Plan Width": 32},
+                ],
+            },
+            "Planning Time": 0.3,
+            "Execution Time": 0.2,
+        }]);
+        let plan = ExplainPlan::from_json(raw).unwrap();
+
+        assert_eq!(plan.root.node_type, "Hash Join");
+        assert_eq!(plan.get_total_cost(), 24.5);
+        assert_eq!(plan.get_plan_rows(), 10.0);
+        assert_eq!(plan.root.actual_rows, Some(10.0));
+        assert_eq!(plan.root.children[0].relation_name.as_deref(), Some("records"));
+        assert_eq!(plan.root.children[0].actual_rows, None);
+        assert_eq!(plan.execution_time, Some(0.2));
+
+        assert!(ExplainPlan::from_json(json!([])).is_err());
+
+        assert!(CostLimit::new().check(&plan).is_ok());
+        assert!(CostLimit::new().with_max_rows(10.0).with_max_cost(24.5).check(&plan).is_ok());
+        assert!(CostLimit::new().with_max_rows(9.0).check(&plan).is_err());
+        assert!(CostLimit::new().with_max_cost(24.0).check(&plan).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt::Formatter;
+use std::path::Path;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde_json::{Map, Value};
+use tokio_postgres::Client;
+use crate::connector::Connector;
+use crate::executor::introspection::Introspector;
+use crate::generator::base::Parameters;
+use crate::Table;
+use crate::utils::errors::ExecutorError;
+use crate::utils::identifier::quote_identifier;
+use crate::utils::logging::warn_event;
+
+/// The rows of each table in the order of the fixture file.
+struct Fixtures(Vec<(String, Vec<Map<String, Value>>)>);
+
+impl<'de> Deserialize<'de> for Fixtures {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FixturesVisitor;
+
+        impl<'de> Visitor<'de> for FixturesVisitor {
+            type Value = Fixtures;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                write!(formatter, "an object mapping the table names to the arrays of the rows")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut tables = Vec::new();
+                while let Some(table) = map.next_entry::<String, Vec<Map<String, Value>>>()? {
+                    tables.push(table);
+                }
+                Ok(Fixtures(tables))
+            }
+        }
+
+        deserializer.deserialize_map(FixturesVisitor)
+    }
+}
+
+/// Inserts the rows of the fixture file at `path` in a transaction and returns the number of the inserted rows.
+///
+/// A file with the `.yaml` or `.yml` extension is read as YAML, which needs the `yaml` feature, and any other file as JSON.
+/// The file is an object mapping the table names (optionally `schema.table`) to the arrays of the rows,
+/// and the tables are inserted in the order of the file, so put the referenced tables first.
+/// Each row is an object mapping the column names to the values, which PostgreSQL converts to the column types.
+/// A column missing in a row is NULL, and a column missing in all the rows of a table has its default value.
+///
+/// ```json
+/// {
+///     "users": [{"id": 1, "name": "alice"}, {"id": 2, "name": "bob"}],
+///     "public.orders": [{"user_id": 1, "amount": "12.50"}]
+/// }
+/// ```
+///
+/// or in YAML:
+///
+/// ```yaml
+/// users:
+///   - {id: 1, name: alice}
+///   - {id: 2, name: bob}
+/// public.orders:
+///   - {user_id: 1, amount: "12.50"}
+/// ```
+///
+/// # Errors
+///
+/// Returns `ExecutorError::UnsafeExecutionError` if the file can't be read or parsed (including a YAML file without the `yaml` feature),
+/// a table name is invalid,
+/// or a row has a column the table doesn't have. Returns `ExecutorError` of the failed insertion,
+/// in which case nothing is inserted.
+pub async fn load_fixtures<P: AsRef<Path>>(connector: &Connector, path: P) -> Result<u64, ExecutorError> {
+    let content = tokio::fs::read_to_string(path.as_ref()).await.map_err(|e| ExecutorError::UnsafeExecutionError(
+        format!("reading the fixture file '{}' failed: {}", path.as_ref().display(), e)))?;
+    let is_yaml = path.as_ref().extension().is_some_and(|extension| extension == "yaml" || extension == "yml");
+    if !is_yaml {
+        return load_fixtures_json(connector, content.as_str()).await
+    }
+    #[cfg(feature = "yaml")]
+    return load_fixtures_yaml(connector, content.as_str()).await;
+    #[cfg(not(feature = "yaml"))]
+    Err(ExecutorError::UnsafeExecutionError(
+        format!("the fixture file '{}' is YAML, which needs the `yaml` feature", path.as_ref().display())))
+}
+
+/// Inserts the rows of the JSON fixtures in a transaction in the same way as `load_fixtures`.
+pub async fn load_fixtures_json(connector: &Connector, json: &str) -> Result<u64, ExecutorError> {
+    load_parsed_fixtures(connector, &parse_fixtures(json)?).await
+}
+
+/// Inserts the rows of the YAML fixtures in a transaction in the same way as `load_fixtures`.
+#[cfg(feature = "yaml")]
+pub async fn load_fixtures_yaml(connector: &Connector, yaml: &str) -> Result<u64, ExecutorError> {
+    load_parsed_fixtures(connector, &parse_yaml_fixtures(yaml)?).await
+}
+
+async fn load_parsed_fixtures(connector: &Connector, fixtures: &Fixtures) -> Result<u64, ExecutorError> {
+    let client = connector.get_client()?;
+
+    client.batch_execute("BEGIN").await?;
+    match insert_fixtures(connector, client, fixtures).await {
+        Ok(inserted) => {
+            client.batch_execute("COMMIT").await?;
+            Ok(inserted)
+        },
+        Err(e) => {
+            if let Err(rollback_error) = client.batch_execute("ROLLBACK").await {
+                warn_event!("Rolling back the fixtures failed due to {}", rollback_error);
+            }
+            Err(e)
+        },
+    }
+}
+
+fn parse_fixtures(json: &str) -> Result<Fixtures, ExecutorError> {
+    serde_json::from_str(json)
+        .map_err(|e| ExecutorError::UnsafeExecutionError(format!("the fixtures can not be parsed: {}", e)))
+}
+
+#[cfg(feature = "yaml")]
+fn parse_yaml_fixtures(yaml: &str) -> Result<Fixtures, ExecutorError> {
+    serde_yaml::from_str(yaml)
+        .map_err(|e| ExecutorError::UnsafeExecutionError(format!("the fixtures can not be parsed: {}", e)))
+}
+
+async fn insert_fixtures(connector: &Connector, client: &Client, fixtures: &Fixtures) -> Result<u64, ExecutorError> {
+    let introspector = Introspector::new(connector);
+    let mut inserted = 0;
+    for (table_name, rows) in &fixtures.0 {
+        let table = match table_name.split_once('.') {
+            Some((schema_name, table_name)) => Table::create_table(Some(schema_name), table_name),
+            None => Table::create_table(None, table_name),
+        };
+        table.validate_identifiers().map_err(|e| ExecutorError::UnsafeExecutionError(e.to_string()))?;
+
+        let table_columns = introspector.get_columns(&table).await?;
+        let mut columns: Vec<&str> = Vec::new();
+        for column in rows.iter().flat_map(|row| row.keys()) {
+            if !table_columns.iter().any(|table_column| &table_column.name == column) {
+                return Err(ExecutorError::UnsafeExecutionError(
+                    format!("'{}' column doesn't exist in the '{}' table.", column, table_name)))
+            }
+            if !columns.contains(&column.as_str()) {
+                columns.push(column.as_str());
+            }
+        }
+        if columns.is_empty() {
+            continue
+        }
+
+        let columns_text = columns.iter().map(|column| quote_identifier(column)).collect::<Vec<String>>().join(", ");
+        let statement = format!(
+            "INSERT INTO {table} ({columns}) SELECT {columns} FROM json_populate_recordset(NULL::{table}, $1)",
+            table = table.get_table_name(), columns = columns_text);
+        let values = Value::Array(rows.iter().cloned().map(Value::Object).collect());
+        inserted += match client.execute(statement.as_str(), &[&values]).await {
+            Ok(count) => count,
+            Err(e) => return Err(ExecutorError::from(e).with_statement_snapshot(Parameters::new().get_statement_snapshot(&statement))),
+        };
+    }
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_fixtures;
+
+    #[test]
+    fn test_parse_fixtures() {
+        let fixtures = parse_fixtures(r#"{"users": [{"id": 1}, {"id": 2}], "public.orders": [], "accounts": [{"id": 1}]}"#).unwrap();
+        let tables = fixtures.0.iter().map(|(table_name, rows)| (table_name.as_str(), rows.len())).collect::<Vec<(&str, usize)>>();
+        assert_eq!(tables, vec![("users", 2), ("public.orders", 0), ("accounts", 1)]);
+
+        assert!(parse_fixtures(r#"{"users": {"id": 1}}"#).is_err());
+        assert!(parse_fixtures(r#"[{"id": 1}]"#).is_err());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_parse_yaml_fixtures() {
+        let fixtures = super::parse_yaml_fixtures("users:\n  - {id: 1, name: alice}\n  - {id: 2}\npublic.orders: []\n").unwrap();
+        let tables = fixtures.0.iter().map(|(table_name, rows)| (table_name.as_str(), rows.len())).collect::<Vec<(&str, usize)>>();
+        assert_eq!(tables, vec![("users", 2), ("public.orders", 0)]);
+        assert_eq!(fixtures.0[0].1[0]["name"], "alice");
+
+        assert!(super::parse_yaml_fixtures("- {id: 1}").is_err());
+    }
+}
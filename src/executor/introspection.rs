@@ -0,0 +1,228 @@
+use chrono::{DateTime, Utc};
+use crate::connector::Connector;
+use crate::generator::foreign_key::cache_foreign_keys;
+use crate::Table;
+use crate::utils::errors::ExecutorError;
+
+/// A column of a table read from the PostgreSQL catalog.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnInfo {
+    pub name: String,
+    /// The name of the column type as `pg_type.typname`, e.g. `int4` or `varchar`.
+    pub type_name: String,
+    pub is_nullable: bool,
+    pub has_default: bool,
+}
+
+/// A foreign key of a table read from the PostgreSQL catalog.
+///
+/// `columns` and `referenced_columns` are in the order of the key, so the same index is a pair.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForeignKeyInfo {
+    pub constraint_name: String,
+    pub columns: Vec<String>,
+    pub referenced_schema: String,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+}
+
+/// The size and the maintenance status of a table read from the PostgreSQL statistics, e.g. for capacity dashboards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableStatistics {
+    /// The size of the table including its indexes and TOAST data in bytes (`pg_total_relation_size`).
+    pub total_size_bytes: i64,
+    /// The size of the main data of the table in bytes (`pg_relation_size`).
+    pub table_size_bytes: i64,
+    /// The estimated number of rows (`pg_class.reltuples`), or `None` if the table has never been vacuumed or analyzed.
+    pub estimated_rows: Option<i64>,
+    pub last_vacuum: Option<DateTime<Utc>>,
+    pub last_autovacuum: Option<DateTime<Utc>>,
+    pub last_analyze: Option<DateTime<Utc>>,
+    pub last_autoanalyze: Option<DateTime<Utc>>,
+}
+
+/// Reads the structure of the tables from the PostgreSQL catalog, returned by `Executor::introspect`.
+///
+/// A table without the schema is resolved with the `search_path` of the connection like in a statement.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::base::Executor;
+/// use safety_postgres::executor::query::Query;
+/// use safety_postgres::Table;
+///
+/// async fn print_columns(query: &Query) {
+///     let table = Table::create_table(Some("public"), "users");
+///     for column in query.introspect().get_columns(&table).await.expect("introspection failed") {
+///         println!("{}: {}", column.name, column.type_name);
+///     }
+/// }
+/// ```
+pub struct Introspector<'a> {
+    connector: &'a Connector,
+}
+
+impl<'a> Introspector<'a> {
+    pub(crate) fn new(connector: &'a Connector) -> Self {
+        Self { connector }
+    }
+
+    /// Returns whether the table exists.
+    pub async fn table_exists(&self, table: &Table<'_>) -> Result<bool, ExecutorError> {
+        Ok(self.get_table_oid(table).await?.is_some())
+    }
+
+    /// Returns the columns of the table in their definition order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::SQLExecutionError` if the table doesn't exist.
+    pub async fn get_columns(&self, table: &Table<'_>) -> Result<Vec<ColumnInfo>, ExecutorError> {
+        let oid = self.get_existing_table_oid(table).await?;
+        let client = self.connector.get_client()?;
+        let statement = "SELECT a.attname::text, t.typname::text, NOT a.attnotnull, a.atthasdef \
+            FROM pg_catalog.pg_attribute a JOIN pg_catalog.pg_type t ON t.oid = a.atttypid \
+            WHERE a.attrelid = $1 AND a.attnum > 0 AND NOT a.attisdropped ORDER BY a.attnum";
+        let rows = client.query(statement, &[&oid]).await?;
+
+        rows.iter()
+            .map(|row| Ok(ColumnInfo {
+                name: row.try_get(0)?,
+                type_name: row.try_get(1)?,
+                is_nullable: row.try_get(2)?,
+                has_default: row.try_get(3)?,
+            }))
+            .collect::<Result<Vec<ColumnInfo>, tokio_postgres::Error>>()
+            .map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+    }
+
+    /// Returns the columns of the primary key of the table in the key order, or an empty vector if it has no primary key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::SQLExecutionError` if the table doesn't exist.
+    pub async fn get_primary_key(&self, table: &Table<'_>) -> Result<Vec<String>, ExecutorError> {
+        let oid = self.get_existing_table_oid(table).await?;
+        let client = self.connector.get_client()?;
+        let statement = format!(
+            "SELECT {} FROM pg_catalog.pg_constraint c WHERE c.conrelid = $1 AND c.contype = 'p'",
+            key_columns_expression("c.conkey", "c.conrelid"));
+        let rows = client.query(statement.as_str(), &[&oid]).await?;
+
+        match rows.first() {
+            Some(row) => row.try_get::<usize, Vec<String>>(0).map_err(|e| ExecutorError::ConvertRowError(e.to_string())),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Returns the foreign keys defined on the table (the keys referencing other tables) ordered by the constraint name.
+    ///
+    /// The foreign keys are also cached for `QueryGenerator::auto_join` under the table name as it's referenced,
+    /// i.e. `"schema.table"` for a table with a schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::SQLExecutionError` if the table doesn't exist.
+    pub async fn get_foreign_keys(&self, table: &Table<'_>) -> Result<Vec<ForeignKeyInfo>, ExecutorError> {
+        let oid = self.get_existing_table_oid(table).await?;
+        let client = self.connector.get_client()?;
+        let statement = format!(
+            "SELECT c.conname::text, n.nspname::text, r.relname::text, {}, {} \
+            FROM pg_catalog.pg_constraint c \
+            JOIN pg_catalog.pg_class r ON r.oid = c.confrelid \
+            JOIN pg_catalog.pg_namespace n ON n.oid = r.relnamespace \
+            WHERE c.conrelid = $1 AND c.contype = 'f' ORDER BY c.conname",
+            key_columns_expression("c.conkey", "c.conrelid"),
+            key_columns_expression("c.confkey", "c.confrelid"));
+        let rows = client.query(statement.as_str(), &[&oid]).await?;
+
+        let foreign_keys = rows.iter()
+            .map(|row| Ok(ForeignKeyInfo {
+                constraint_name: row.try_get(0)?,
+                referenced_schema: row.try_get(1)?,
+                referenced_table: row.try_get(2)?,
+                columns: row.try_get(3)?,
+                referenced_columns: row.try_get(4)?,
+            }))
+            .collect::<Result<Vec<ForeignKeyInfo>, tokio_postgres::Error>>()
+            .map_err(|e| ExecutorError::ConvertRowError(e.to_string()))?;
+
+        cache_foreign_keys(table.get_plain_table_name().as_str(), foreign_keys.clone());
+        Ok(foreign_keys)
+    }
+
+    /// Returns the size, the estimated number of rows and the last vacuum and analyze times of the table.
+    ///
+    /// The values are the estimates kept by PostgreSQL, so they are cheap to read even for a large table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::SQLExecutionError` if the table doesn't exist.
+    pub async fn get_table_statistics(&self, table: &Table<'_>) -> Result<TableStatistics, ExecutorError> {
+        let oid = self.get_existing_table_oid(table).await?;
+        let client = self.connector.get_client()?;
+        let statement = "SELECT pg_total_relation_size(c.oid), pg_relation_size(c.oid), c.reltuples::float8, \
+            s.last_vacuum, s.last_autovacuum, s.last_analyze, s.last_autoanalyze \
+            FROM pg_catalog.pg_class c LEFT JOIN pg_catalog.pg_stat_all_tables s ON s.relid = c.oid \
+            WHERE c.oid = $1";
+        let row = client.query_one(statement, &[&oid]).await?;
+
+        let statistics = || -> Result<TableStatistics, tokio_postgres::Error> {
+            Ok(TableStatistics {
+                total_size_bytes: row.try_get(0)?,
+                table_size_bytes: row.try_get(1)?,
+                estimated_rows: estimate_rows(row.try_get(2)?),
+                last_vacuum: row.try_get(3)?,
+                last_autovacuum: row.try_get(4)?,
+                last_analyze: row.try_get(5)?,
+                last_autoanalyze: row.try_get(6)?,
+            })
+        };
+        statistics().map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+    }
+
+    pub(crate) async fn get_existing_table_oid(&self, table: &Table<'_>) -> Result<u32, ExecutorError> {
+        match self.get_table_oid(table).await? {
+            Some(oid) => Ok(oid),
+            None => Err(ExecutorError::SQLExecutionError(
+                format!("The table '{}' does not exist.", table.get_plain_table_name()))),
+        }
+    }
+
+    async fn get_table_oid(&self, table: &Table<'_>) -> Result<Option<u32>, ExecutorError> {
+        if let Table::SubQueryAsTable(_) = table {
+            return Ok(None)
+        }
+        let client = self.connector.get_client()?;
+        let row = client.query_one("SELECT to_regclass($1)::oid", &[&table.get_table_name()]).await?;
+        row.try_get::<usize, Option<u32>>(0).map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+    }
+}
+
+/// Converts `pg_class.reltuples` to the number of rows. It's negative (PostgreSQL 14 or later)
+/// or zero (earlier versions) until the table is vacuumed or analyzed, which is treated as unknown.
+fn estimate_rows(reltuples: f64) -> Option<i64> {
+    if reltuples <= 0.0 {
+        return None
+    }
+    Some(reltuples.round() as i64)
+}
+
+/// Returns the expression converting the attribute numbers of a constraint key to the column names in the key order.
+fn key_columns_expression(key: &str, relation: &str) -> String {
+    format!(
+        "ARRAY(SELECT a.attname::text FROM unnest({key}) WITH ORDINALITY AS k(attnum, ord) \
+        JOIN pg_catalog.pg_attribute a ON a.attrelid = {relation} AND a.attnum = k.attnum ORDER BY k.ord)")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::estimate_rows;
+
+    #[test]
+    fn test_estimate_rows() {
+        assert_eq!(estimate_rows(-1.0), None);
+        assert_eq!(estimate_rows(0.0), None);
+        assert_eq!(estimate_rows(1234.6), Some(1235));
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+use tokio_postgres::Client;
+use crate::connector::Connector;
+use crate::generator::base::Parameters;
+use crate::generator::definitions::DefinitionGenerator;
+use crate::utils::errors::{ExecutorError, GeneratorError};
+use crate::utils::logging::warn_event;
+
+const MIGRATIONS_TABLE: &str = "_safety_postgres_migrations";
+
+/// A versioned schema change applied by `Migrator`.
+///
+/// The statements of a migration are applied in one transaction, so they should be transactional
+/// (e.g. `CREATE INDEX CONCURRENTLY` can't be a part of a migration).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Migration {
+    version: i64,
+    name: String,
+    statements: Vec<String>,
+}
+
+impl Migration {
+    /// Creates a migration of SQL statements separated by `;`. The SQL is executed as it is,
+    /// so it must be written by the developer (e.g. embedded with `include_str!`), not built from the user input.
+    pub fn sql(version: i64, name: &str, sql: &str) -> Self {
+        Self {
+            version,
+            name: name.to_string(),
+            statements: vec![sql.to_string()],
+        }
+    }
+
+    /// Creates a migration of the statements of the definition generators, e.g. `CreateTable` and `AlterTable`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError` if a definition is invalid.
+    pub fn from_definitions(version: i64, name: &str, definitions: &[&dyn DefinitionGenerator]) -> Result<Self, GeneratorError> {
+        let statements = definitions
+            .iter()
+            .map(|definition| definition.get_statement())
+            .collect::<Result<Vec<String>, GeneratorError>>()?;
+        Ok(Self {
+            version,
+            name: name.to_string(),
+            statements,
+        })
+    }
+
+    pub fn get_version(&self) -> i64 {
+        self.version
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the statements applied by the migration.
+    pub fn get_statements(&self) -> &[String] {
+        &self.statements
+    }
+}
+
+/// Applies the registered migrations which are not applied yet, in the order of their versions.
+///
+/// The applied versions are recorded in the `_safety_postgres_migrations` table, created on the first run.
+/// Each migration is applied in its own transaction with the record of its version, and the table is locked
+/// during the transaction, so the migrations are applied once even if several instances start at the same time.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::connector::Connector;
+/// use safety_postgres::executor::migrations::{Migration, Migrator};
+/// use safety_postgres::generator::definitions::create_table::{ColumnDefinition, CreateTable};
+/// use safety_postgres::Table;
+///
+/// async fn migrate(connector: &Connector) {
+///     let users = Table::create_table(None, "users");
+///     let create_users = CreateTable::new(&users)
+///         .with_column(ColumnDefinition::new("id", "bigserial"))
+///         .with_primary_key(&["id"]);
+///
+///     let migrator = Migrator::new()
+///         .with_migration(Migration::from_definitions(1, "create users", &[&create_users]).expect("invalid definition"))
+///         .with_migration(Migration::sql(2, "add email", "ALTER TABLE users ADD COLUMN email text"));
+///
+///     for migration in migrator.get_pending(connector).await.expect("listing failed") {
+///         println!("pending: {} {}", migration.get_version(), migration.get_name());
+///     }
+///     migrator.run(connector).await.expect("migration failed");
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+}
+
+impl Migrator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a migration. The migrations can be registered in any order.
+    pub fn with_migration(mut self, migration: Migration) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// Returns the registered migrations which are not applied yet in the order they would be applied,
+    /// without applying them (dry run).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::UnsafeExecutionError` if a version is registered twice,
+    /// or `ExecutorError` if the applied versions can't be read.
+    pub async fn get_pending(&self, connector: &Connector) -> Result<Vec<&Migration>, ExecutorError> {
+        let migrations = self.get_sorted_migrations()?;
+        let client = connector.get_client()?;
+        let applied = get_applied_versions(client).await?;
+        Ok(migrations.into_iter().filter(|migration| !applied.contains(&migration.version)).collect())
+    }
+
+    /// Applies the pending migrations and returns their versions.
+    ///
+    /// A failed migration is rolled back and its error is returned, so the migrations after it are not applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::UnsafeExecutionError` if a version is registered twice,
+    /// or `ExecutorError` of the failed statement.
+    pub async fn run(&self, connector: &Connector) -> Result<Vec<i64>, ExecutorError> {
+        let migrations = self.get_sorted_migrations()?;
+        let client = connector.get_client()?;
+        execute(client, format!(
+            "CREATE TABLE IF NOT EXISTS {} (version bigint PRIMARY KEY, name text NOT NULL, \
+            applied_at timestamptz NOT NULL DEFAULT now())", MIGRATIONS_TABLE).as_str()).await?;
+
+        let mut applied_versions = Vec::new();
+        for migration in migrations {
+            execute(client, "BEGIN").await?;
+            match apply(client, migration).await {
+                Ok(true) => {
+                    execute(client, "COMMIT").await?;
+                    applied_versions.push(migration.version);
+                },
+                Ok(false) => execute(client, "COMMIT").await?,
+                Err(e) => {
+                    if let Err(rollback_error) = execute(client, "ROLLBACK").await {
+                        warn_event!("Rolling back the migration {} failed due to {}", migration.version, rollback_error);
+                    }
+                    return Err(e)
+                },
+            }
+        }
+        Ok(applied_versions)
+    }
+
+    fn get_sorted_migrations(&self) -> Result<Vec<&Migration>, ExecutorError> {
+        let mut versions = HashSet::new();
+        for migration in &self.migrations {
+            if !versions.insert(migration.version) {
+                return Err(ExecutorError::UnsafeExecutionError(
+                    format!("the migration version {} is registered twice.", migration.version)))
+            }
+        }
+        let mut migrations = self.migrations.iter().collect::<Vec<&Migration>>();
+        migrations.sort_by_key(|migration| migration.version);
+        Ok(migrations)
+    }
+}
+
+/// Applies `migration` in the current transaction unless another instance has applied it,
+/// and returns whether it's applied.
+async fn apply(client: &Client, migration: &Migration) -> Result<bool, ExecutorError> {
+    execute(client, format!("LOCK TABLE {} IN EXCLUSIVE MODE", MIGRATIONS_TABLE).as_str()).await?;
+    if get_applied_versions(client).await?.contains(&migration.version) {
+        return Ok(false)
+    }
+    for statement in &migration.statements {
+        execute(client, statement).await?;
+    }
+    let record = format!("INSERT INTO {} (version, name) VALUES ($1, $2)", MIGRATIONS_TABLE);
+    client.execute(record.as_str(), &[&migration.version, &migration.name]).await?;
+    Ok(true)
+}
+
+async fn get_applied_versions(client: &Client) -> Result<HashSet<i64>, ExecutorError> {
+    let exists = client
+        .query_one("SELECT to_regclass($1) IS NOT NULL", &[&MIGRATIONS_TABLE])
+        .await?
+        .try_get::<usize, bool>(0)
+        .map_err(|e| ExecutorError::ConvertRowError(e.to_string()))?;
+    if !exists {
+        return Ok(HashSet::new())
+    }
+
+    let rows = client.query(format!("SELECT version FROM {}", MIGRATIONS_TABLE).as_str(), &[]).await?;
+    rows.iter()
+        .map(|row| row.try_get::<usize, i64>(0).map_err(|e| ExecutorError::ConvertRowError(e.to_string())))
+        .collect()
+}
+
+async fn execute(client: &Client, statement: &str) -> Result<(), ExecutorError> {
+    match client.batch_execute(statement).await {
+        Ok(_) => Ok(()),
+        Err(e) => Err(ExecutorError::from(e).with_statement_snapshot(Parameters::new().get_statement_snapshot(statement))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::definitions::create_table::{ColumnDefinition, CreateTable};
+    use crate::Table;
+    use super::{Migration, Migrator};
+
+    #[test]
+    fn test_migrations() {
+        let table = Table::create_table(None, "users");
+        let create_table = CreateTable::new(&table).with_column(ColumnDefinition::new("id", "bigint"));
+        let migration = Migration::from_definitions(1, "create users", &[&create_table]).unwrap();
+        assert_eq!(migration.get_statements(), ["CREATE TABLE users (id bigint)"]);
+        assert!(Migration::from_definitions(1, "invalid", &[&CreateTable::new(&table)]).is_err());
+
+        let migrator = Migrator::new()
+            .with_migration(Migration::sql(3, "third", "SELECT 3"))
+            .with_migration(migration);
+        let versions = migrator.get_sorted_migrations().unwrap().iter().map(|migration| migration.get_version()).collect::<Vec<i64>>();
+        assert_eq!(versions, vec![1, 3]);
+
+        let migrator = migrator.with_migration(Migration::sql(3, "duplicated", "SELECT 3"));
+        assert!(migrator.get_sorted_migrations().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Mutex;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::converter::row_converter::variable_to_json;
+use crate::executor::base::Execute;
+use crate::executor::dry_run::DryRun;
+use crate::generator::base::MainGenerator;
+use crate::utils::errors::ExecutorError;
+use crate::Variable;
+
+type MockRows = Vec<HashMap<String, Variable>>;
+
+/// An `Execute` implementation for unit tests, which records the executed statements
+/// and returns the canned rows instead of touching the database.
+///
+/// Each execution takes the next result queued by `with_rows` or `with_error`,
+/// and returns no row once the queue is empty.
+///
+/// # Example
+/// ```rust
+/// use std::collections::HashMap;
+/// use safety_postgres::executor::base::Execute;
+/// use safety_postgres::executor::mock::MockExecutor;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::generator::query::query_column::QueryColumns;
+/// use safety_postgres::{Table, Variable};
+///
+/// async fn test_fetch_users() {
+///     let executor = MockExecutor::new()
+///         .with_rows(vec![HashMap::from([("name".to_string(), Variable::from("John"))])]);
+///
+///     let table = Table::create_table(None, "users");
+///     let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+///     let rows = executor.fetch_maps(&generator).await.unwrap();
+///
+///     assert_eq!(rows.len(), 1);
+///     assert_eq!(executor.get_executed()[0].get_statement(), "SELECT users.* FROM users");
+/// }
+/// ```
+#[derive(Default)]
+pub struct MockExecutor {
+    results: Mutex<VecDeque<Result<MockRows, ExecutorError>>>,
+    executed: Mutex<Vec<DryRun>>,
+}
+
+impl MockExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues `rows` as the result of the next execution.
+    pub fn with_rows(self, rows: Vec<HashMap<String, Variable>>) -> Self {
+        self.results.lock().unwrap().push_back(Ok(rows));
+        self
+    }
+
+    /// Queues `error` as the result of the next execution.
+    pub fn with_error(self, error: ExecutorError) -> Self {
+        self.results.lock().unwrap().push_back(Err(error));
+        self
+    }
+
+    /// Returns the statements and the parameters executed so far in order.
+    pub fn get_executed(&self) -> Vec<DryRun> {
+        self.executed.lock().unwrap().clone()
+    }
+
+    fn execute<G: MainGenerator>(&self, generator: &G) -> Result<MockRows, ExecutorError> {
+        self.executed.lock().unwrap().push(DryRun::from_generator(generator));
+        self.results.lock().unwrap().pop_front().unwrap_or_else(|| Ok(Vec::new()))
+    }
+}
+
+impl Execute for MockExecutor {
+    fn fetch_maps<G: MainGenerator + Sync>(&self, generator: &G) -> impl Future<Output = Result<Vec<HashMap<String, Variable>>, ExecutorError>> + Send {
+        let result = self.execute(generator);
+        async move { result }
+    }
+
+    fn query_as<T: DeserializeOwned + Send, G: MainGenerator + Sync>(&self, generator: &G) -> impl Future<Output = Result<Vec<T>, ExecutorError>> + Send {
+        let result = self.execute(generator).and_then(|rows| {
+            rows.iter()
+                .map(|row| {
+                    let map = row.iter().map(|(name, value)| (name.clone(), variable_to_json(value))).collect();
+                    serde_json::from_value(Value::Object(map))
+                        .map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+                })
+                .collect()
+        });
+        async move { result }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use serde::Deserialize;
+    use crate::executor::base::Execute;
+    use crate::generator::query::QueryGenerator;
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::{Table, Variable};
+    use super::MockExecutor;
+
+    #[derive(Deserialize)]
+    struct User {
+        id: i32,
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_mock_executor() {
+        let executor = MockExecutor::new()
+            .with_rows(vec![HashMap::from([
+                ("id".to_string(), Variable::Int(1)),
+                ("name".to_string(), Variable::from("John"))])]);
+
+        let table = Table::create_table(None, "users");
+        let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+        let users = executor.query_as::<User, _>(&generator).await.unwrap();
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].id, 1);
+        assert_eq!(users[0].name, "John");
+
+        assert!(executor.fetch_maps(&generator).await.unwrap().is_empty());
+        let executed = executor.get_executed();
+        assert_eq!(executed.len(), 2);
+        assert_eq!(executed[0].get_statement(), "SELECT users.* FROM users");
+    }
+}
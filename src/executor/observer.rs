@@ -0,0 +1,98 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use crate::utils::errors::ExecutorError;
+
+/// Kind of an executed statement, used as a label of the metrics.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StatementTag {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    Other,
+}
+
+impl StatementTag {
+    pub(crate) fn from_statement(statement: &str) -> Self {
+        match statement.split_whitespace().next().unwrap_or_default().to_uppercase().as_str() {
+            "SELECT" | "WITH" => Self::Select,
+            "INSERT" => Self::Insert,
+            "UPDATE" => Self::Update,
+            "DELETE" => Self::Delete,
+            _ => Self::Other,
+        }
+    }
+
+    /// Returns the tag as an uppercase keyword, e.g. `"SELECT"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Select => "SELECT",
+            Self::Insert => "INSERT",
+            Self::Update => "UPDATE",
+            Self::Delete => "DELETE",
+            Self::Other => "OTHER",
+        }
+    }
+}
+
+impl Display for StatementTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Observer notified of every statement the executor runs, e.g. to record Prometheus or OpenTelemetry metrics.
+///
+/// `on_query_start` is called before the checks of the executor (table policy, cost limit),
+/// and `on_query_end` is called once for each start, including the executions refused by the checks.
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::time::Duration;
+/// use safety_postgres::connector::Connector;
+/// use safety_postgres::executor::base::Executor;
+/// use safety_postgres::executor::observer::{QueryObserver, StatementTag};
+/// use safety_postgres::executor::query::Query;
+/// use safety_postgres::utils::errors::ExecutorError;
+///
+/// #[derive(Default)]
+/// struct ErrorCounter {
+///     errors: AtomicU64,
+/// }
+///
+/// impl QueryObserver for ErrorCounter {
+///     fn on_query_end(&self, _tag: StatementTag, _duration: Duration, _rows: u64, error: Option<&ExecutorError>) {
+///         if error.is_some() {
+///             self.errors.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// fn observed_query(connector: Connector, counter: Arc<ErrorCounter>) -> Query {
+///     Query::new(connector).with_observer(counter)
+/// }
+/// ```
+pub trait QueryObserver: Send + Sync {
+    /// Called when the execution of a statement starts.
+    fn on_query_start(&self, _tag: StatementTag) {}
+
+    /// Called when the execution finishes with the elapsed time, the number of the returned rows
+    /// (0 on failure) and the error if the execution failed.
+    fn on_query_end(&self, tag: StatementTag, duration: Duration, rows: u64, error: Option<&ExecutorError>);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatementTag;
+
+    #[test]
+    fn test_statement_tag() {
+        assert_eq!(StatementTag::from_statement("SELECT * FROM users"), StatementTag::Select);
+        assert_eq!(StatementTag::from_statement("  with t AS (SELECT 1) SELECT * FROM t"), StatementTag::Select);
+        assert_eq!(StatementTag::from_statement("delete FROM users"), StatementTag::Delete);
+        assert_eq!(StatementTag::from_statement(""), StatementTag::Other);
+        assert_eq!(StatementTag::Update.to_string(), "UPDATE");
+    }
+}
@@ -0,0 +1,140 @@
+use crate::generator::base::MainGenerator;
+use crate::utils::errors::ExecutorError;
+use crate::Table;
+
+/// Allow and deny lists of the tables and schemas an executor may access.
+///
+/// Every table referenced by a generator (the main table, the joined tables and the tables
+/// of the sub queries) is checked before the execution:
+///
+/// - A table is refused if its schema is denied or the table is denied.
+/// - If any allow list is set, a table is refused unless it or its schema is allowed.
+///
+/// A table entry with a schema (e.g. `"tenant_a.users"`) matches only that table, and an entry
+/// without a schema (e.g. `"users"`) matches the table in any schema. A table referenced without a schema
+/// is never matched by the schema lists because its schema depends on the `search_path`.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::policy::TablePolicy;
+///
+/// let policy = TablePolicy::new()
+///     .allow_schema("tenant_a")
+///     .deny_table("tenant_a.secrets");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TablePolicy {
+    allowed_tables: Vec<String>,
+    denied_tables: Vec<String>,
+    allowed_schemas: Vec<String>,
+    denied_schemas: Vec<String>,
+}
+
+impl TablePolicy {
+    /// Creates a policy which allows every table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a table (`"table"` or `"schema.table"`) to the allow list.
+    pub fn allow_table(mut self, table_name: &str) -> Self {
+        self.allowed_tables.push(table_name.to_string());
+        self
+    }
+
+    /// Adds a table (`"table"` or `"schema.table"`) to the deny list.
+    pub fn deny_table(mut self, table_name: &str) -> Self {
+        self.denied_tables.push(table_name.to_string());
+        self
+    }
+
+    /// Adds a schema to the allow list.
+    pub fn allow_schema(mut self, schema_name: &str) -> Self {
+        self.allowed_schemas.push(schema_name.to_string());
+        self
+    }
+
+    /// Adds a schema to the deny list.
+    pub fn deny_schema(mut self, schema_name: &str) -> Self {
+        self.denied_schemas.push(schema_name.to_string());
+        self
+    }
+
+    /// Returns `ExecutorError::UnsafeExecutionError` if the generator references a table refused by the policy.
+    /// The generators which don't know all the tables they read (see `MainGenerator::has_known_tables`) are refused as well.
+    pub(crate) fn check<G: MainGenerator>(&self, generator: &G) -> Result<(), ExecutorError> {
+        if !generator.has_known_tables() {
+            return Err(ExecutorError::UnsafeExecutionError(
+                "a statement whose tables are unknown, so the table policy can't check it".to_string()))
+        }
+        for table in generator.get_referenced_tables() {
+            self.check_table(&table)?;
+        }
+        Ok(())
+    }
+
+    fn check_table(&self, table: &Table) -> Result<(), ExecutorError> {
+        let (schema_name, table_name) = match table {
+            Table::WithSchema { schema_name, table_name } => (Some(*schema_name), *table_name),
+            Table::NonSchema { table_name } => (None, *table_name),
+            Table::SubQueryAsTable(_) => return Ok(()),
+        };
+        let matches_table = |entries: &[String]| entries.iter().any(|entry| {
+            match (entry.split_once('.'), schema_name) {
+                (Some((entry_schema, entry_table)), Some(schema_name)) => entry_schema == schema_name && entry_table == table_name,
+                (Some(_), None) => false,
+                (None, _) => entry == table_name,
+            }
+        });
+        let matches_schema = |entries: &[String]| match schema_name {
+            Some(schema_name) => entries.iter().any(|entry| entry == schema_name),
+            None => false,
+        };
+
+        if matches_schema(&self.denied_schemas) || matches_table(&self.denied_tables) {
+            return Err(ExecutorError::UnsafeExecutionError(
+                format!("'{}' table which is denied by the table policy", table.get_table_name())))
+        }
+        let has_allow_list = !self.allowed_tables.is_empty() || !self.allowed_schemas.is_empty();
+        if has_allow_list && !matches_schema(&self.allowed_schemas) && !matches_table(&self.allowed_tables) {
+            return Err(ExecutorError::UnsafeExecutionError(
+                format!("'{}' table which is not allowed by the table policy", table.get_table_name())))
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::base::join_table::{JoinTable, JoinType};
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::generator::query::QueryGenerator;
+    use crate::Table;
+    use super::TablePolicy;
+
+    #[test]
+    fn test_table_policy() {
+        let users = Table::create_table(Some("tenant_a"), "users");
+        let secrets = Table::create_table(Some("tenant_a"), "secrets");
+        let records = Table::create_table(None, "records");
+
+        let users_columns = QueryColumns::create_all_columns(&users);
+        let generator = QueryGenerator::new(&users, QueryColumns::create_all_columns(&users));
+        assert!(TablePolicy::new().check(&generator).is_ok());
+        assert!(TablePolicy::new().allow_schema("tenant_a").check(&generator).is_ok());
+        assert!(TablePolicy::new().allow_table("users").check(&generator).is_ok());
+        assert!(TablePolicy::new().allow_table("tenant_b.users").check(&generator).is_err());
+        assert!(TablePolicy::new().deny_schema("tenant_a").check(&generator).is_err());
+
+        let sub_query = Table::create_sub_query_table(&generator);
+        let mut join_generator = QueryGenerator::new(&records, QueryColumns::create_all_columns(&records));
+        join_generator.add_join_table(JoinTable::new(&sub_query, &users_columns, JoinType::Inner)).unwrap();
+        let policy = TablePolicy::new().allow_table("records").allow_schema("tenant_a");
+        assert!(policy.check(&join_generator).is_ok());
+        assert!(policy.clone().deny_table("tenant_a.users").check(&join_generator).is_err());
+        assert!(TablePolicy::new().allow_table("records").check(&join_generator).is_err());
+
+        let secret_generator = QueryGenerator::new(&secrets, QueryColumns::create_all_columns(&secrets));
+        assert!(policy.deny_table("secrets").check(&secret_generator).is_err());
+    }
+}
@@ -1,26 +1,657 @@
-use std::error::Error;
+use futures_util::future::try_join_all;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 use tokio_postgres::Row;
+use tokio_postgres::types::FromSql;
 use crate::connector::Connector;
-use crate::executor::base::Executor;
-use crate::generator::base::MainGenerator;
+use crate::converter::row_converter::{get_variable, row_to_json_map, FromRow};
+use std::collections::HashMap;
+use std::future::Future;
+use std::mem::{discriminant, Discriminant};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio_postgres::Statement;
+use crate::executor::audit::{AuditLog, AuditRecord};
+use crate::executor::base::{execute_statement, explain_plan, query_statement, Execute, Executor};
+use crate::executor::comment::StatementComment;
+use crate::executor::dry_run::DryRun;
+use crate::executor::explain::{CostLimit, ExplainOptions};
+use crate::executor::observer::{QueryObserver, StatementTag};
+use crate::executor::policy::TablePolicy;
+use crate::generator::base::{BindMethod, ConditionOperator, MainGenerator};
+use crate::generator::base::condition::Condition;
+use crate::generator::query::query_column::QueryColumns;
+use crate::generator::query::QueryGenerator;
+use crate::utils::errors::ExecutorError;
+use crate::utils::logging::{slow_statement_event, statement_event, warn_event};
+use crate::{Table, Variable};
 
-struct Query {
+/// The maximum number of the ids bound to a query of `Query::fetch_by_ids`.
+const FETCH_BY_IDS_CHUNK_SIZE: usize = 1000;
+
+/// Executes SELECT statements generated by the query generators.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::connector::Connector;
+/// use safety_postgres::connector::connection_config::ConnectionConfig;
+/// use safety_postgres::converter::row_converter::Row;
+/// use safety_postgres::executor::base::Executor;
+/// use safety_postgres::executor::query::Query;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::generator::query::query_column::QueryColumns;
+/// use safety_postgres::Table;
+///
+/// async fn fetch_users() {
+///     let config = ConnectionConfig::config_from_env().expect("config not found");
+///     let connector = Connector::connect(config).await.expect("connect failed");
+///     let query = Query::new(connector);
+///
+///     let table = Table::create_table(None, "users");
+///     let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+///     let rows = query.fetch_all::<Row, _>(&generator).await.expect("query failed");
+/// }
+/// ```
+pub struct Query {
     connector: Connector,
-    result: Vec<Row>
+    cost_limit: Option<CostLimit>,
+    table_policy: Option<TablePolicy>,
+    audit_log: Option<AuditLog>,
+    observer: Option<Arc<dyn QueryObserver>>,
+    slow_query_threshold: Option<Duration>,
+    statement_comment: Option<StatementComment>,
+    max_affected: Option<u64>,
 }
 
 impl Executor for Query {
     fn new(connector: Connector) -> Self {
         Self {
             connector,
-            result: Vec::<Row>::new()
+            cost_limit: None,
+            table_policy: None,
+            audit_log: None,
+            observer: None,
+            slow_query_threshold: None,
+            statement_comment: None,
+            max_affected: None,
+        }
+    }
+
+    fn get_connector(&self) -> &Connector {
+        &self.connector
+    }
+}
+
+impl Execute for Query {
+    fn fetch_maps<G: MainGenerator + Sync>(&self, generator: &G) -> impl Future<Output = Result<Vec<HashMap<String, Variable>>, ExecutorError>> + Send {
+        self.fetch_all(generator)
+    }
+
+    fn query_as<T: DeserializeOwned + Send, G: MainGenerator + Sync>(&self, generator: &G) -> impl Future<Output = Result<Vec<T>, ExecutorError>> + Send {
+        Query::query_as(self, generator)
+    }
+}
+
+impl Query {
+    /// Enables the cost guard: every query is explained first (without `ANALYZE`)
+    /// and refused with `ExecutorError::UnsafeExecutionError` if its estimate exceeds `cost_limit`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use safety_postgres::connector::Connector;
+    /// use safety_postgres::executor::base::Executor;
+    /// use safety_postgres::executor::explain::CostLimit;
+    /// use safety_postgres::executor::query::Query;
+    ///
+    /// fn guarded_query(connector: Connector) -> Query {
+    ///     Query::new(connector).with_cost_limit(CostLimit::new().with_max_rows(10_000.0))
+    /// }
+    /// ```
+    pub fn with_cost_limit(mut self, cost_limit: CostLimit) -> Self {
+        self.cost_limit = Some(cost_limit);
+        self
+    }
+
+    /// Sets the table policy: every query referencing a table refused by `table_policy`
+    /// fails with `ExecutorError::UnsafeExecutionError` before it's sent to the database.
+    pub fn with_table_policy(mut self, table_policy: TablePolicy) -> Self {
+        self.table_policy = Some(table_policy);
+        self
+    }
+
+    /// Sets the audit log recording every executed query with the number of the returned rows.
+    ///
+    /// The writes of a `Repository` with this query insert their audit rows into the audit table
+    /// in the same transaction as the statements.
+    pub fn with_audit_log(mut self, audit_log: AuditLog) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Sets the observer notified of the start and the end of every query (see `QueryObserver`).
+    pub fn with_observer(mut self, observer: Arc<dyn QueryObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Logs every query taking longer than `threshold` as a `tracing` warning with the statement,
+    /// the parameter types (the values are redacted) and an EXPLAIN hint.
+    ///
+    /// Nothing is logged unless the `tracing` feature is enabled.
+    pub fn with_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_query_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets the comment appended to every executed query for attributing it in `pg_stat_statements`.
+    pub fn with_statement_comment(mut self, statement_comment: StatementComment) -> Self {
+        self.statement_comment = Some(statement_comment);
+        self
+    }
+
+    /// Sets the maximum number of the rows which a write (e.g. `Repository::update`) can affect.
+    ///
+    /// The write runs in a transaction and is rolled back with `ExecutorError::UnsafeExecutionError`
+    /// if it affects more rows than `max_affected`.
+    pub fn with_max_affected(mut self, max_affected: u64) -> Self {
+        self.max_affected = Some(max_affected);
+        self
+    }
+
+    /// Executes the query and converts each row with `FromRow`.
+    ///
+    /// Use `Row` as `T` to get the rows as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the execution fails or a row can't be converted to `T`.
+    pub async fn fetch_all<T: FromRow, G: MainGenerator>(&self, generator: &G) -> Result<Vec<T>, ExecutorError> {
+        let rows = self.query_rows(generator).await?;
+        rows.into_iter().map(T::from_row).collect()
+    }
+
+    /// Executes the queries of `generators` pipelined on the connection of this executor
+    /// and returns their rows in the order of `generators`.
+    ///
+    /// The queries are sent together, which saves the round trips, but the server still runs them one by one
+    /// on the single connection, so a slow query delays the ones behind it. Use `Query::fetch_many_across`
+    /// to run them in parallel on several connections. Use `QueryGenerator::to_owned_query` to pass
+    /// the generators of different shapes together.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `ExecutorError` of the queries.
+    ///
+    /// # Example
+    /// ```rust
+    /// use safety_postgres::converter::row_converter::Row;
+    /// use safety_postgres::executor::query::Query;
+    /// use safety_postgres::generator::query::QueryGenerator;
+    /// use safety_postgres::generator::query::query_column::QueryColumns;
+    /// use safety_postgres::Table;
+    ///
+    /// async fn fetch_dashboard(query: &Query) {
+    ///     let (users, teams) = (Table::create_table(None, "users"), Table::create_table(None, "teams"));
+    ///     let generators = vec![
+    ///         QueryGenerator::new(&users, QueryColumns::create_all_columns(&users)).to_owned_query(),
+    ///         QueryGenerator::new(&teams, QueryColumns::create_all_columns(&teams)).to_owned_query(),
+    ///     ];
+    ///     let results = query.fetch_many::<Row, _>(&generators).await.expect("query failed");
+    /// }
+    /// ```
+    pub async fn fetch_many<T: FromRow, G: MainGenerator>(&self, generators: &[G]) -> Result<Vec<Vec<T>>, ExecutorError> {
+        try_join_all(generators.iter().map(|generator| self.fetch_all::<T, G>(generator))).await
+    }
+
+    /// Executes the queries of `generators` in parallel on the connections of `queries` (e.g. the `Query`s
+    /// of the connectors taken from a pool) and returns their rows in the order of `generators`.
+    ///
+    /// Each connection takes the next query when its previous one finishes, so a slow query only holds its own connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::ConnectionNotFoundError` if `queries` is empty while `generators` isn't,
+    /// or the first `ExecutorError` of the queries.
+    ///
+    /// # Example
+    /// ```rust
+    /// use safety_postgres::converter::row_converter::Row;
+    /// use safety_postgres::executor::query::Query;
+    /// use safety_postgres::generator::query::owned::OwnedQuery;
+    ///
+    /// async fn fetch_dashboard(queries: &[Query], generators: &[OwnedQuery]) {
+    ///     let results = Query::fetch_many_across::<Row, _>(queries, generators).await.expect("query failed");
+    /// }
+    /// ```
+    pub async fn fetch_many_across<T: FromRow, G: MainGenerator>(queries: &[Query], generators: &[G]) -> Result<Vec<Vec<T>>, ExecutorError> {
+        if queries.is_empty() && !generators.is_empty() {
+            return Err(ExecutorError::ConnectionNotFoundError("no connection is given to execute the queries.".to_string()))
+        }
+        let next_index = AtomicUsize::new(0);
+        let workers = queries.iter().map(|query| async {
+            let mut results = Vec::new();
+            loop {
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+                match generators.get(index) {
+                    Some(generator) => results.push((index, query.fetch_all::<T, G>(generator).await?)),
+                    None => return Ok::<_, ExecutorError>(results),
+                }
+            }
+        });
+
+        let mut results = try_join_all(workers).await?.into_iter().flatten().collect::<Vec<(usize, Vec<T>)>>();
+        results.sort_by_key(|(index, _)| *index);
+        Ok(results.into_iter().map(|(_, rows)| rows).collect())
+    }
+
+    /// Executes the query and deserializes each row into `T` via serde.
+    ///
+    /// Each row is converted to a JSON object keyed by the column names first, so `T` can be any
+    /// `Deserialize` struct whose field names match the columns, without implementing `FromRow`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the execution fails or a row can't be deserialized into `T`.
+    pub async fn query_as<T: DeserializeOwned, G: MainGenerator>(&self, generator: &G) -> Result<Vec<T>, ExecutorError> {
+        let rows = self.query_rows(generator).await?;
+        rows.iter()
+            .map(|row| {
+                let map = row_to_json_map(row)?;
+                serde_json::from_value(Value::Object(map))
+                    .map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+            })
+            .collect()
+    }
+
+    /// Executes the query and returns the single value of the result, e.g. for `COUNT` or `MAX` queries.
+    ///
+    /// Use `Option<T>` as `T` if the value can be NULL (e.g. `MAX` of an empty table).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the execution fails, the result isn't exactly one row with one column,
+    /// or the value can't be converted to `T`.
+    pub async fn fetch_scalar<T: for<'a> FromSql<'a>, G: MainGenerator>(&self, generator: &G) -> Result<T, ExecutorError> {
+        let rows = self.query_rows(generator).await?;
+        if rows.len() != 1 {
+            return Err(ExecutorError::ConvertRowError(
+                format!("fetch_scalar expects exactly 1 row but the query returned {} row(s).", rows.len())))
+        }
+        get_single_column(&rows[0])
+    }
+
+    /// Fetches the rows of `table` whose `id_column` is one of `ids` and returns them in the order of `ids`,
+    /// with `None` for an id which has no row. An id given more than once gets its row at every position.
+    ///
+    /// The rows are matched to the ids by the value and its type (the integer types are compared by the value),
+    /// so e.g. `Variable::Text("1")` doesn't match the row of `Variable::Int(1)`.
+    ///
+    /// The ids are queried by `= ANY($1)` in chunks of 1000, so a long list doesn't make a huge parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::UnsafeExecutionError` if the ids have mixed types,
+    /// or `ExecutorError` if the execution fails or a row can't be converted to `T`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use safety_postgres::executor::query::Query;
+    /// use safety_postgres::{Table, Variable};
+    ///
+    /// async fn fill_cache(query: &Query, ids: &[Variable]) {
+    ///     let table = Table::create_table(None, "users");
+    ///     let users = query.fetch_by_ids::<HashMap<String, Variable>>(&table, "id", ids).await.expect("query failed");
+    /// }
+    /// ```
+    pub async fn fetch_by_ids<T: FromRow + Clone>(&self, table: &Table<'_>, id_column: &str, ids: &[Variable]) -> Result<Vec<Option<T>>, ExecutorError> {
+        let column = table.get_column(id_column);
+        let mut rows = HashMap::new();
+        for chunk in ids.chunks(FETCH_BY_IDS_CHUNK_SIZE) {
+            let chunk_ids = Variable::create_array(chunk.to_vec())
+                .map_err(|e| ExecutorError::UnsafeExecutionError(e.to_string()))?;
+            let generator = QueryGenerator::new(table, QueryColumns::create_all_columns(table))
+                .filter(Condition::new(&column, chunk_ids.into(), ConditionOperator::Any), BindMethod::FirstCondition)
+                .map_err(|e| ExecutorError::UnsafeExecutionError(e.to_string()))?;
+            for row in self.query_rows(&generator).await? {
+                let index = match row.columns().iter().position(|row_column| row_column.name() == id_column) {
+                    Some(index) => index,
+                    None => return Err(ExecutorError::ConvertRowError(
+                        format!("'{}' column is not in the result.", id_column))),
+                };
+                rows.insert(IdKey::from(&get_variable(&row, index)?), T::from_row(row)?);
+            }
+        }
+
+        Ok(ids.iter().map(|id| rows.get(&IdKey::from(id)).cloned()).collect())
+    }
+
+    /// Executes the query and returns the values of its single column.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the execution fails, the result doesn't have exactly one column,
+    /// or a value can't be converted to `T`.
+    pub async fn fetch_column<T: for<'a> FromSql<'a>, G: MainGenerator>(&self, generator: &G) -> Result<Vec<T>, ExecutorError> {
+        let rows = self.query_rows(generator).await?;
+        rows.iter().map(get_single_column).collect()
+    }
+
+    /// Executes the query and converts the result into an Arrow `RecordBatch`.
+    ///
+    /// The schema is taken from the prepared statement, so an empty result still has the query's columns.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the execution fails or a value can't be converted.
+    #[cfg(feature = "arrow")]
+    pub async fn fetch_record_batch<G: MainGenerator>(&self, generator: &G) -> Result<arrow_array::RecordBatch, ExecutorError> {
+        let (statement, rows) = self.query_statement(generator).await?;
+        crate::converter::arrow_converter::rows_to_record_batch(statement.columns(), &rows)
+    }
+
+    /// Executes the query and converts the result into a polars `DataFrame`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the execution fails or a value can't be converted.
+    #[cfg(feature = "polars")]
+    pub async fn fetch_dataframe<G: MainGenerator>(&self, generator: &G) -> Result<polars::prelude::DataFrame, ExecutorError> {
+        let (statement, rows) = self.query_statement(generator).await?;
+        crate::converter::polars_converter::rows_to_dataframe(statement.columns(), &rows)
+    }
+}
+
+impl Query {
+    /// Checks the table policy and the estimated plan against the cost limit if they are set.
+    async fn check_before_execution<G: MainGenerator>(&self, generator: &G) -> Result<(), ExecutorError> {
+        for table in generator.get_referenced_tables() {
+            table.validate_identifiers().map_err(|e| ExecutorError::UnsafeExecutionError(e.to_string()))?;
+        }
+        if let Some(table_policy) = &self.table_policy {
+            table_policy.check(generator)?;
         }
+        match &self.cost_limit {
+            Some(cost_limit) => {
+                let plan = explain_plan(&self.connector, generator, ExplainOptions::new()).await?;
+                cost_limit.check(&plan)
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn query_rows<G: MainGenerator>(&self, generator: &G) -> Result<Vec<Row>, ExecutorError> {
+        if let Some(statement_log) = self.connector.get_statement_log() {
+            statement_log.record(DryRun::from_generator(generator));
+            return Ok(Vec::new())
+        }
+        let (_, rows) = self.query_statement(generator).await?;
+        Ok(rows)
+    }
+
+    async fn query_statement<G: MainGenerator>(&self, generator: &G) -> Result<(Statement, Vec<Row>), ExecutorError> {
+        let observer = match &self.observer {
+            Some(observer) => observer,
+            None => return self.execute_statement(generator).await,
+        };
+        let tag = StatementTag::from_statement(generator.get_statement().as_str());
+        observer.on_query_start(tag);
+        let started = Instant::now();
+        let result = self.execute_statement(generator).await;
+        match &result {
+            Ok((_, rows)) => observer.on_query_end(tag, started.elapsed(), rows.len() as u64, None),
+            Err(e) => observer.on_query_end(tag, started.elapsed(), 0, Some(e)),
+        }
+        result
+    }
+
+    async fn execute_statement<G: MainGenerator>(&self, generator: &G) -> Result<(Statement, Vec<Row>), ExecutorError> {
+        self.check_before_execution(generator).await?;
+        let started = Instant::now();
+        let (statement, rows) = query_statement(&self.connector, generator, self.statement_comment.as_ref()).await?;
+        let elapsed = started.elapsed();
+        statement_event!(generator.get_statement(), rows.len(), elapsed);
+        if let Some(threshold) = self.slow_query_threshold {
+            if elapsed > threshold {
+                slow_statement_event!(generator.get_statement(), elapsed, threshold, generator.get_params().get_redacted_summary());
+            }
+        }
+
+        self.write_audit_record(generator, rows.len() as u64, started.elapsed()).await?;
+        Ok((statement, rows))
+    }
+
+    /// Executes the statement of the generator, which returns no rows (e.g. INSERT, UPDATE or DELETE),
+    /// through the same checks, observer, statement comment and audit log as the queries,
+    /// and returns the number of the affected rows.
+    ///
+    /// With the audit table set by `AuditLog::to_table` or the limit set by `with_max_affected`,
+    /// the statement and the insert of its audit record run in a transaction, which is rolled back
+    /// if either of them fails or the statement affects more rows than the limit.
+    /// With a capturing connector the statements are recorded and 0 is returned.
+    pub(super) async fn execute<G: MainGenerator>(&self, generator: &G) -> Result<u64, ExecutorError> {
+        if let Some(statement_log) = self.connector.get_statement_log() {
+            let in_transaction = self.is_transactional();
+            if in_transaction {
+                statement_log.record(DryRun::new("BEGIN".to_string(), Vec::new()));
+            }
+            statement_log.record(DryRun::from_generator(generator));
+            if let Some(audit_log) = &self.audit_log {
+                if let Some(dry_run) = audit_log.dry_run(&self.new_audit_record(audit_log, generator, 0, Duration::ZERO)) {
+                    statement_log.record(dry_run);
+                }
+            }
+            if in_transaction {
+                statement_log.record(DryRun::new("COMMIT".to_string(), Vec::new()));
+            }
+            return Ok(0)
+        }
+        let observer = match &self.observer {
+            Some(observer) => observer,
+            None => return self.execute_in_transaction(generator).await,
+        };
+        let tag = StatementTag::from_statement(generator.get_statement().as_str());
+        observer.on_query_start(tag);
+        let started = Instant::now();
+        let result = self.execute_in_transaction(generator).await;
+        match &result {
+            Ok(affected) => observer.on_query_end(tag, started.elapsed(), *affected, None),
+            Err(e) => observer.on_query_end(tag, started.elapsed(), 0, Some(e)),
+        }
+        result
+    }
+
+    /// Whether `execute` runs the statement with the following writes in a transaction.
+    fn is_transactional(&self) -> bool {
+        self.max_affected.is_some() || self.audit_log.as_ref().is_some_and(|audit_log| audit_log.is_table())
+    }
+
+    /// Checks the number of the affected rows against the limit set by `with_max_affected`.
+    fn check_affected(&self, statement: &str, affected: u64) -> Result<(), ExecutorError> {
+        match self.max_affected {
+            Some(max_affected) if affected > max_affected => Err(ExecutorError::UnsafeExecutionError(
+                format!("'{}' affecting {} record(s) over the limit {}, so it was rolled back", statement, affected, max_affected))),
+            _ => Ok(()),
+        }
+    }
+
+    async fn execute_in_transaction<G: MainGenerator>(&self, generator: &G) -> Result<u64, ExecutorError> {
+        self.check_before_execution(generator).await?;
+        if !self.is_transactional() {
+            return self.execute_without_rows(generator).await
+        }
+        let client = self.connector.get_client()?;
+
+        client.batch_execute("BEGIN").await?;
+        match self.execute_without_rows(generator).await {
+            Ok(affected) => {
+                client.batch_execute("COMMIT").await?;
+                Ok(affected)
+            },
+            Err(e) => {
+                if let Err(rollback_error) = client.batch_execute("ROLLBACK").await {
+                    warn_event!("Rolling back the statement failed due to {}", rollback_error);
+                }
+                Err(e)
+            },
+        }
+    }
+
+    async fn execute_without_rows<G: MainGenerator>(&self, generator: &G) -> Result<u64, ExecutorError> {
+        let started = Instant::now();
+        let affected = execute_statement(&self.connector, generator, self.statement_comment.as_ref()).await?;
+        let elapsed = started.elapsed();
+        statement_event!(generator.get_statement(), affected, elapsed);
+        if let Some(threshold) = self.slow_query_threshold {
+            if elapsed > threshold {
+                slow_statement_event!(generator.get_statement(), elapsed, threshold, generator.get_params().get_redacted_summary());
+            }
+        }
+
+        self.check_affected(generator.get_statement().as_str(), affected)?;
+        self.write_audit_record(generator, affected, started.elapsed()).await?;
+        Ok(affected)
+    }
+
+    /// Records the executed statement to the audit log if it's set.
+    async fn write_audit_record<G: MainGenerator>(&self, generator: &G, rows: u64, elapsed: Duration) -> Result<(), ExecutorError> {
+        if let Some(audit_log) = &self.audit_log {
+            let record = self.new_audit_record(audit_log, generator, rows, elapsed);
+            if let Err(e) = audit_log.write(self.connector.get_client()?, &record).await {
+                return Err(ExecutorError::SQLExecutionError(format!("writing the audit record failed: {}", e)))
+            }
+        }
+        Ok(())
+    }
+
+    fn new_audit_record<G: MainGenerator>(&self, audit_log: &AuditLog, generator: &G, rows: u64, elapsed: Duration) -> AuditRecord {
+        let table_name = match generator.get_referenced_tables().first() {
+            Some(table) => table.get_table_name(),
+            None => String::new(),
+        };
+        AuditRecord::new(generator.get_statement().as_str(), table_name.as_str(), rows, elapsed, audit_log.get_actor())
+    }
+}
+
+/// The key matching an id to the id of a fetched row by the value and its type.
+///
+/// The integers are compared by the value regardless of their width, and a `Secret` as the `Text` of its value.
+#[derive(PartialEq, Eq, Hash)]
+pub(super) enum IdKey {
+    Integer(i64),
+    Text(String),
+    Other(Discriminant<Variable>, String),
+}
+
+impl From<&Variable> for IdKey {
+    fn from(variable: &Variable) -> Self {
+        match variable {
+            Variable::SmallInt(value) => Self::Integer(i64::from(*value)),
+            Variable::Int(value) => Self::Integer(i64::from(*value)),
+            Variable::BigInt(value) => Self::Integer(*value),
+            Variable::Text(value) | Variable::Secret(value) => Self::Text(value.clone()),
+            other => Self::Other(discriminant(other), other.to_string()),
+        }
+    }
+}
+
+fn get_single_column<T: for<'a> FromSql<'a>>(row: &Row) -> Result<T, ExecutorError> {
+    if row.len() != 1 {
+        return Err(ExecutorError::ConvertRowError(
+            format!("The query should return exactly 1 column but returned {} column(s).", row.len())))
+    }
+    row.try_get(0).map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::connector::Connector;
+    use crate::converter::row_converter::Row;
+    use crate::executor::base::Executor;
+    use crate::executor::capture::StatementLog;
+    use crate::generator::query::owned::OwnedQuery;
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::generator::query::QueryGenerator;
+    use crate::utils::errors::ExecutorError;
+    use crate::{Table, Variable};
+    use super::{IdKey, Query};
+
+    #[tokio::test]
+    async fn test_fetch_by_ids_chunks() {
+        let statement_log = StatementLog::new();
+        let query = Query::new(Connector::capture(statement_log.clone()));
+        let table = Table::create_table(None, "users");
+
+        let ids = (0..1001).map(Variable::Int).collect::<Vec<Variable>>();
+        let users = query.fetch_by_ids::<HashMap<String, Variable>>(&table, "id", &ids).await.unwrap();
+        assert_eq!(users.len(), 1001);
+        assert!(users.iter().all(|user| user.is_none()));
+
+        let statements = statement_log.get_statements();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[1].to_string(), "SELECT users.* FROM users WHERE  users.id = ANY($1) -- $1 = {1000}");
+
+        assert!(query.fetch_by_ids::<HashMap<String, Variable>>(&table, "id", &[Variable::Int(1), Variable::from("a")]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_many_in_order() {
+        let statement_log = StatementLog::new();
+        let query = Query::new(Connector::capture(statement_log.clone()));
+        let (users, teams) = (Table::create_table(None, "users"), Table::create_table(None, "teams"));
+        let generators = vec![
+            QueryGenerator::new(&users, QueryColumns::create_all_columns(&users)).to_owned_query(),
+            QueryGenerator::new(&teams, QueryColumns::create_all_columns(&teams)).to_owned_query(),
+        ];
+
+        assert_eq!(query.fetch_many::<Row, _>(&generators).await.unwrap().len(), 2);
+        assert_eq!(statement_log.to_string(), "SELECT users.* FROM users\nSELECT teams.* FROM teams\n");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_many_across() {
+        let statement_logs = [StatementLog::new(), StatementLog::new()];
+        let queries = statement_logs
+            .iter()
+            .map(|statement_log| Query::new(Connector::capture(statement_log.clone())))
+            .collect::<Vec<Query>>();
+        let tables = ["users", "teams", "posts"].map(|table_name| Table::create_table(None, table_name));
+        let generators = tables
+            .iter()
+            .map(|table| QueryGenerator::new(table, QueryColumns::create_all_columns(table)).to_owned_query())
+            .collect::<Vec<_>>();
+
+        assert_eq!(Query::fetch_many_across::<Row, _>(&queries, &generators).await.unwrap().len(), 3);
+        assert_eq!(statement_logs.iter().map(|statement_log| statement_log.get_statements().len()).sum::<usize>(), 3);
+        assert!(Query::fetch_many_across::<Row, _>(&[], &generators).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_max_affected() {
+        let statement_log = StatementLog::new();
+        let query = Query::new(Connector::capture(statement_log.clone())).with_max_affected(1);
+        let statement = "DELETE FROM users WHERE name = $1";
+        let generator = OwnedQuery::new(statement.to_string(), vec![Variable::from("John")], 1, vec![(None, "users".to_string())]);
+
+        assert_eq!(query.execute(&generator).await.unwrap(), 0);
+        assert_eq!(statement_log.to_string(), "BEGIN\nDELETE FROM users WHERE name = $1 -- $1 = John\nCOMMIT\n");
+
+        assert!(query.check_affected(statement, 1).is_ok());
+        assert_eq!(
+            query.check_affected(statement, 2).unwrap_err().to_string(),
+            ExecutorError::UnsafeExecutionError(
+                "'DELETE FROM users WHERE name = $1' affecting 2 record(s) over the limit 1, so it was rolled back".to_string()).to_string());
+        assert!(Query::new(Connector::capture(StatementLog::new())).check_affected(statement, 2).is_ok());
     }
 
-    async fn execute<T, R, E>(&self, generator: T) -> Result<R, E>
-        where
-            T: MainGenerator, E: Error
-    {
-        todo!()
+    #[test]
+    fn test_id_key() {
+        assert!(IdKey::from(&Variable::Int(1)) == IdKey::from(&Variable::BigInt(1)));
+        assert!(IdKey::from(&Variable::Text("1".to_string())) != IdKey::from(&Variable::Int(1)));
+        assert!(IdKey::from(&Variable::Secret("a".to_string())) != IdKey::from(&Variable::Secret("b".to_string())));
+        assert!(IdKey::from(&Variable::Secret("a".to_string())) == IdKey::from(&Variable::Text("a".to_string())));
+        assert!(IdKey::from(&Variable::Double(1.0)) != IdKey::from(&Variable::Int(1)));
     }
 }
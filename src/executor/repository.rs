@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+#[cfg(feature = "derive")]
+pub use safety_postgres_derive::Entity;
+use crate::connector::Connector;
+use crate::converter::row_converter::FromRow;
+use crate::executor::base::Executor;
+use crate::executor::query::{IdKey, Query};
+use crate::generator::base::{BindMethod, ConditionOperator};
+use crate::generator::base::condition::{Condition, ConditionSet};
+use crate::generator::query::owned::OwnedQuery;
+use crate::generator::query::query_column::QueryColumns;
+use crate::generator::query::QueryGenerator;
+use crate::generator::soft_delete::get_soft_delete_column;
+use crate::{Table, Variable};
+use crate::utils::errors::{ExecutorError, GeneratorError};
+use crate::utils::identifier::quote_identifier;
+
+/// Maps a Rust type to a table for `Repository`.
+///
+/// With the `derive` feature, `#[derive(Entity)]` implements this trait and `FromRow` from the fields of a struct.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::repository::TableMeta;
+/// use safety_postgres::Variable;
+///
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// impl TableMeta for User {
+///     fn table_name() -> &'static str {
+///         "users"
+///     }
+///
+///     fn primary_key() -> &'static str {
+///         "id"
+///     }
+///
+///     fn columns() -> &'static [&'static str] {
+///         &["name"]
+///     }
+///
+///     fn values(&self) -> Vec<Variable> {
+///         vec![Variable::from(self.name.as_str())]
+///     }
+///
+///     fn primary_key_value(&self) -> Variable {
+///         Variable::Int(self.id)
+///     }
+/// }
+/// ```
+pub trait TableMeta {
+    /// Returns the schema of the table, or `None` to resolve the table with the `search_path`.
+    fn schema_name() -> Option<&'static str> {
+        None
+    }
+
+    fn table_name() -> &'static str;
+
+    /// Returns the column of the primary key.
+    fn primary_key() -> &'static str;
+
+    /// Returns the columns written by `Repository::insert` and `Repository::update`.
+    /// Leave out the primary key if the database generates it.
+    fn columns() -> &'static [&'static str];
+
+    /// Returns the values of `columns` in the same order.
+    fn values(&self) -> Vec<Variable>;
+
+    fn primary_key_value(&self) -> Variable;
+}
+
+/// Maps a table whose rows reference the rows of `P` by a foreign key, for `Repository::load_related`.
+///
+/// With the `derive` feature, `#[column(belongs_to = P)]` on the field of the foreign key of an `Entity` implements this trait.
+pub trait Related<P: TableMeta>: TableMeta {
+    /// Returns the column referencing the primary key of `P`.
+    fn foreign_key() -> &'static str;
+
+    fn foreign_key_value(&self) -> Variable;
+}
+
+/// Reads and writes the rows of the table of `T` by its primary key, for the services which only need simple CRUD.
+///
+/// The reads and the writes are executed by `Query`, so the rows are converted with `FromRow`,
+/// and the table policy, observer, statement comment and audit log set with `Repository::with_query` apply to both.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::connector::Connector;
+/// use safety_postgres::converter::row_converter::FromRow;
+/// use safety_postgres::executor::base::Executor;
+/// use safety_postgres::executor::repository::{Repository, TableMeta};
+/// use safety_postgres::utils::errors::ExecutorError;
+///
+/// async fn rename<T: FromRow + TableMeta>(connector: Connector, id: i32, rename: impl Fn(&mut T)) -> Result<(), ExecutorError> {
+///     let repository = Repository::<T>::new(connector);
+///     if let Some(mut row) = repository.find_by_id(id).await? {
+///         rename(&mut row);
+///         repository.update(&row).await?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Repository<T> {
+    query: Query,
+    row_type: PhantomData<fn() -> T>,
+}
+
+impl<T: FromRow + TableMeta> Executor for Repository<T> {
+    fn new(connector: Connector) -> Self {
+        Self {
+            query: Query::new(connector),
+            row_type: PhantomData,
+        }
+    }
+
+    fn get_connector(&self) -> &Connector {
+        self.query.get_connector()
+    }
+}
+
+impl<T: FromRow + TableMeta> Repository<T> {
+    /// Runs the reads and the writes by `query` instead of a plain `Query` on the connector,
+    /// e.g. a `Query` with a table policy or an audit log.
+    pub fn with_query(mut self, query: Query) -> Self {
+        self.query = query;
+        self
+    }
+
+    /// Returns the row whose primary key is `id`, or `None` if it doesn't exist.
+    pub async fn find_by_id<V: Into<Variable>>(&self, id: V) -> Result<Option<T>, ExecutorError> {
+        let table = get_table::<T>();
+        let primary_key = table.get_column(T::primary_key());
+        let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table))
+            .filter(Condition::new(&primary_key, id.into().into(), ConditionOperator::Equal), BindMethod::FirstCondition)
+            .map_err(to_executor_error)?;
+        Ok(self.query.fetch_all::<T, _>(&generator).await?.into_iter().next())
+    }
+
+    /// Returns the rows matching `condition_set`, whose columns should be of the table of `T`.
+    pub async fn find_where(&self, condition_set: &ConditionSet<'_>) -> Result<Vec<T>, ExecutorError> {
+        let table = get_table::<T>();
+        let mut generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+        generator.add_condition_set(condition_set).map_err(to_executor_error)?;
+        self.query.fetch_all::<T, _>(&generator).await
+    }
+
+    /// Loads the rows of `C` referencing `parents` by a single `= ANY($1)` query instead of a query per parent,
+    /// and returns them grouped in the order of `parents`.
+    ///
+    /// The rows are matched to the parents by the value and the type of the keys, like `Query::fetch_by_ids`. A parent given twice gets its rows only at the first position.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError::UnsafeExecutionError` if the primary keys of `parents` have mixed types,
+    /// or `ExecutorError` if the execution fails or a row can't be converted to `C`.
+    pub async fn load_related<C: FromRow + Related<T>>(&self, parents: &[T]) -> Result<Vec<Vec<C>>, ExecutorError> {
+        if parents.is_empty() {
+            return Ok(Vec::new())
+        }
+        let ids = Variable::create_array(parents.iter().map(|parent| parent.primary_key_value()).collect())
+            .map_err(to_executor_error)?;
+
+        let table = get_table::<C>();
+        let foreign_key = table.get_column(C::foreign_key());
+        let generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table))
+            .filter(Condition::new(&foreign_key, ids.into(), ConditionOperator::Any), BindMethod::FirstCondition)
+            .map_err(to_executor_error)?;
+
+        let mut children: HashMap<IdKey, Vec<C>> = HashMap::new();
+        for child in self.query.fetch_all::<C, _>(&generator).await? {
+            children.entry(IdKey::from(&child.foreign_key_value())).or_default().push(child);
+        }
+        Ok(parents
+            .iter()
+            .map(|parent| children.remove(&IdKey::from(&parent.primary_key_value())).unwrap_or_default())
+            .collect())
+    }
+
+    /// Inserts `row` with the values of `TableMeta::columns` and returns the number of the inserted rows.
+    pub async fn insert(&self, row: &T) -> Result<u64, ExecutorError> {
+        let columns = T::columns().iter().map(|column| quote_identifier(column)).collect::<Vec<String>>();
+        let placeholders = (1..=columns.len()).map(|index| format!("${}", index)).collect::<Vec<String>>();
+        let statement = format!(
+            "INSERT INTO {} ({}) VALUES ({})", get_table::<T>().get_table_name(), columns.join(", "), placeholders.join(", "));
+        self.execute(statement, row.values()).await
+    }
+
+    /// Updates `TableMeta::columns` of the row with the primary key of `row` and returns the number of the updated rows.
+    pub async fn update(&self, row: &T) -> Result<u64, ExecutorError> {
+        let columns = T::columns();
+        let sets = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| format!("{} = ${}", quote_identifier(column), index + 1))
+            .collect::<Vec<String>>();
+        let statement = format!(
+            "UPDATE {} SET {} WHERE {} = ${}",
+            get_table::<T>().get_table_name(), sets.join(", "), quote_identifier(T::primary_key()), columns.len() + 1);
+        let mut values = row.values();
+        values.push(row.primary_key_value());
+        self.execute(statement, values).await
+    }
+
+    /// Deletes the row whose primary key is `id` and returns the number of the deleted rows.
+    ///
+    /// For a soft-delete table (see `register_soft_delete`) the row is marked as deleted
+    /// by setting the soft-delete column to `now()` instead, unless it's already marked.
+    pub async fn delete_by_id<V: Into<Variable>>(&self, id: V) -> Result<u64, ExecutorError> {
+        let table = get_table::<T>();
+        let primary_key = quote_identifier(T::primary_key());
+        let statement = match get_soft_delete_column(table.get_plain_table_name().as_str()) {
+            Some(column) => {
+                let column = quote_identifier(column.as_str());
+                format!(
+                    "UPDATE {} SET {} = now() WHERE {} = $1 AND {} IS NULL", table.get_table_name(), column, primary_key, column)
+            },
+            None => format!("DELETE FROM {} WHERE {} = $1", table.get_table_name(), primary_key),
+        };
+        self.execute(statement, vec![id.into()]).await
+    }
+
+    /// Executes the write statement by `Query`, so that its table policy, observer, statement comment
+    /// and audit log apply as they do to the reads, and its limit of the affected rows to the writes.
+    async fn execute(&self, statement: String, values: Vec<Variable>) -> Result<u64, ExecutorError> {
+        let parameters_num = values.len() as u16;
+        let table = (T::schema_name().map(|schema_name| schema_name.to_string()), T::table_name().to_string());
+        self.query.execute(&OwnedQuery::new(statement, values, parameters_num, vec![table])).await
+    }
+}
+
+fn get_table<T: TableMeta>() -> Table<'static> {
+    Table::create_table(T::schema_name(), T::table_name())
+}
+
+fn to_executor_error(error: GeneratorError) -> ExecutorError {
+    ExecutorError::UnsafeExecutionError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connector::Connector;
+    use crate::converter::row_converter::{FromRow, Row};
+    use crate::executor::base::Executor;
+    use crate::executor::audit::AuditLog;
+    use crate::executor::capture::StatementLog;
+    use crate::executor::query::Query;
+    use crate::generator::soft_delete::{register_soft_delete, unregister_soft_delete};
+    use crate::utils::errors::ExecutorError;
+    use crate::Variable;
+    use super::{Related, Repository, TableMeta};
+
+    struct User {
+        id: i32,
+        name: String,
+    }
+
+    impl FromRow for User {
+        fn from_row(row: Row) -> Result<Self, ExecutorError> {
+            let convert_error = |e: tokio_postgres::Error| ExecutorError::ConvertRowError(e.to_string());
+            Ok(Self {
+                id: row.try_get("id").map_err(convert_error)?,
+                name: row.try_get("name").map_err(convert_error)?,
+            })
+        }
+    }
+
+    impl TableMeta for User {
+        fn table_name() -> &'static str {
+            "users"
+        }
+
+        fn primary_key() -> &'static str {
+            "id"
+        }
+
+        fn columns() -> &'static [&'static str] {
+            &["name"]
+        }
+
+        fn values(&self) -> Vec<Variable> {
+            vec![Variable::from(self.name.as_str())]
+        }
+
+        fn primary_key_value(&self) -> Variable {
+            Variable::Int(self.id)
+        }
+    }
+
+    struct Post {
+        user_id: i32,
+    }
+
+    impl FromRow for Post {
+        fn from_row(row: Row) -> Result<Self, ExecutorError> {
+            Ok(Self {
+                user_id: row.try_get("user_id").map_err(|e| ExecutorError::ConvertRowError(e.to_string()))?,
+            })
+        }
+    }
+
+    impl TableMeta for Post {
+        fn table_name() -> &'static str {
+            "posts"
+        }
+
+        fn primary_key() -> &'static str {
+            "id"
+        }
+
+        fn columns() -> &'static [&'static str] {
+            &["user_id"]
+        }
+
+        fn values(&self) -> Vec<Variable> {
+            vec![Variable::Int(self.user_id)]
+        }
+
+        fn primary_key_value(&self) -> Variable {
+            Variable::Null
+        }
+    }
+
+    impl Related<User> for Post {
+        fn foreign_key() -> &'static str {
+            "user_id"
+        }
+
+        fn foreign_key_value(&self) -> Variable {
+            Variable::Int(self.user_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_related() {
+        let statement_log = StatementLog::new();
+        let repository = Repository::<User>::new(Connector::capture(statement_log.clone()));
+        let users = vec![User { id: 1, name: "John".to_string() }, User { id: 2, name: "Jane".to_string() }];
+
+        let posts = repository.load_related::<Post>(&users).await.unwrap();
+        assert_eq!(posts.len(), 2);
+        assert!(repository.load_related::<Post>(&[]).await.unwrap().is_empty());
+        assert_eq!(statement_log.to_string(), "SELECT posts.* FROM posts WHERE  posts.user_id = ANY($1) -- $1 = {1, 2}\n");
+    }
+
+    #[tokio::test]
+    async fn test_repository_statements() {
+        let statement_log = StatementLog::new();
+        let repository = Repository::<User>::new(Connector::capture(statement_log.clone()));
+        let user = User { id: 1, name: "John".to_string() };
+
+        assert!(repository.find_by_id(1).await.unwrap().is_none());
+        repository.insert(&user).await.unwrap();
+        repository.update(&user).await.unwrap();
+        repository.delete_by_id(1).await.unwrap();
+
+        assert_eq!(statement_log.to_string(), "SELECT users.* FROM users WHERE  users.id = $1 -- $1 = 1\n\
+            INSERT INTO users (name) VALUES ($1) -- $1 = John\n\
+            UPDATE users SET name = $1 WHERE id = $2 -- $1 = John, $2 = 1\n\
+            DELETE FROM users WHERE id = $1 -- $1 = 1\n");
+    }
+
+    #[tokio::test]
+    async fn test_repository_audit_table_transaction() {
+        let statement_log = StatementLog::new();
+        let query = Query::new(Connector::capture(statement_log.clone()))
+            .with_audit_log(AuditLog::to_table("audit_log").unwrap().with_actor("admin"));
+        let repository = Repository::<User>::new(Connector::capture(statement_log.clone())).with_query(query);
+
+        repository.delete_by_id(1).await.unwrap();
+
+        assert_eq!(statement_log.to_string(), "BEGIN\n\
+            DELETE FROM users WHERE id = $1 -- $1 = 1\n\
+            INSERT INTO audit_log (operation, table_name, affected_rows, duration_ms, actor, statement) \
+            VALUES ($1, $2, $3, $4, $5, $6) -- $1 = DELETE, $2 = users, $3 = 0, $4 = 0, $5 = admin, $6 = DELETE FROM users WHERE id = $1\n\
+            COMMIT\n");
+    }
+
+    struct Session;
+
+    impl FromRow for Session {
+        fn from_row(_: Row) -> Result<Self, ExecutorError> {
+            Ok(Self)
+        }
+    }
+
+    impl TableMeta for Session {
+        fn table_name() -> &'static str {
+            "repository_test_sessions"
+        }
+
+        fn primary_key() -> &'static str {
+            "id"
+        }
+
+        fn columns() -> &'static [&'static str] {
+            &[]
+        }
+
+        fn values(&self) -> Vec<Variable> {
+            Vec::new()
+        }
+
+        fn primary_key_value(&self) -> Variable {
+            Variable::Null
+        }
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete_by_id() {
+        register_soft_delete("repository_test_sessions", "expired_at").unwrap();
+        let statement_log = StatementLog::new();
+        let repository = Repository::<Session>::new(Connector::capture(statement_log.clone()));
+
+        repository.delete_by_id(1).await.unwrap();
+        unregister_soft_delete("repository_test_sessions");
+        assert_eq!(
+            statement_log.to_string(),
+            "UPDATE repository_test_sessions SET expired_at = now() WHERE id = $1 AND expired_at IS NULL -- $1 = 1\n");
+    }
+}
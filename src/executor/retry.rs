@@ -0,0 +1,153 @@
+use std::future::Future;
+use std::time::Duration;
+use crate::legacy::errors::PostgresBaseError;
+use crate::utils::errors::{DatabaseError, ExecutorError};
+
+/// Errors which can tell whether the failed operation may succeed when it's retried.
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for crate::Error {
+    fn is_retryable(&self) -> bool {
+        crate::Error::is_retryable(self)
+    }
+}
+
+impl Retryable for ExecutorError {
+    fn is_retryable(&self) -> bool {
+        ExecutorError::is_retryable(self)
+    }
+}
+
+impl Retryable for PostgresBaseError {
+    fn is_retryable(&self) -> bool {
+        PostgresBaseError::is_retryable(self)
+    }
+}
+
+impl Retryable for DatabaseError {
+    fn is_retryable(&self) -> bool {
+        DatabaseError::is_retryable(self)
+    }
+}
+
+/// How many times and how long apart `retry` runs an operation.
+///
+/// The wait starts from the initial backoff and doubles after each failure up to the max backoff.
+/// The default is 3 attempts with 100 ms initial and 5 s max backoff.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of the attempts including the first one (at least 1).
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    fn get_backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// Runs `operation` and runs it again while it fails with a retryable error, following `policy`.
+///
+/// A non-retryable error and the error of the last attempt are returned as they are.
+/// `operation` is called for each attempt, so it can reconnect when the connection was closed.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::query::Query;
+/// use safety_postgres::executor::retry::{retry, RetryPolicy};
+/// use safety_postgres::generator::query::QueryGenerator;
+///
+/// async fn count_users(query: &Query, generator: &QueryGenerator<'_>) -> i64 {
+///     let policy = RetryPolicy::new().with_max_attempts(5);
+///     retry(&policy, || query.fetch_scalar::<i64, _>(generator)).await.expect("query failed")
+/// }
+/// ```
+pub async fn retry<T, E, F, Fut>(policy: &RetryPolicy, mut operation: F) -> Result<T, E>
+where
+    E: Retryable,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < policy.max_attempts && e.is_retryable() => {
+                tokio::time::sleep(policy.get_backoff(attempt)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::utils::errors::ExecutorError;
+    use super::{retry, RetryPolicy};
+
+    #[tokio::test]
+    async fn test_retry() {
+        let policy = RetryPolicy::new().with_initial_backoff(Duration::ZERO);
+
+        let mut calls = 0;
+        let result = retry(&policy, || {
+            calls += 1;
+            let result = if calls < 3 {
+                Err(ExecutorError::ConnectionClosedError("connection closed".to_string()))
+            } else {
+                Ok(calls)
+            };
+            async move { result }
+        }).await;
+        assert_eq!(result, Ok(3));
+
+        let mut calls = 0;
+        let result: Result<(), ExecutorError> = retry(&policy, || {
+            calls += 1;
+            async { Err(ExecutorError::UnsafeExecutionError("policy".to_string())) }
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        assert_eq!(policy.get_backoff(1), Duration::ZERO);
+        let policy = RetryPolicy::new().with_max_backoff(Duration::from_millis(300));
+        assert_eq!(policy.get_backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.get_backoff(3), Duration::from_millis(300));
+    }
+}
@@ -0,0 +1,81 @@
+use tokio_postgres::types::ToSql;
+use crate::connector::Connector;
+use crate::utils::errors::ExecutorError;
+use crate::Table;
+
+/// Reads and sets the values of the sequences, returned by `Executor::sequences`.
+///
+/// A sequence is specified as a `Table` of its name and schema, whose names are validated like the table names.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::executor::base::Executor;
+/// use safety_postgres::executor::query::Query;
+/// use safety_postgres::Table;
+///
+/// async fn allocate_ids(query: &Query) -> Vec<i64> {
+///     let sequence = Table::create_table(None, "users_id_seq");
+///     // Pre-allocate the ids of 1000 records loaded by COPY.
+///     query.sequences().next_values(&sequence, 1000).await.expect("nextval failed")
+/// }
+/// ```
+pub struct Sequences<'a> {
+    connector: &'a Connector,
+}
+
+impl<'a> Sequences<'a> {
+    pub(crate) fn new(connector: &'a Connector) -> Self {
+        Self { connector }
+    }
+
+    /// Advances the sequence and returns the new value (`nextval`).
+    pub async fn next_value(&self, sequence: &Table<'_>) -> Result<i64, ExecutorError> {
+        self.query_value("SELECT nextval($1::text::regclass)", sequence, &[]).await
+    }
+
+    /// Advances the sequence `count` times and returns the values, e.g. to allocate the ids before a bulk load.
+    pub async fn next_values(&self, sequence: &Table<'_>, count: u32) -> Result<Vec<i64>, ExecutorError> {
+        let name = get_sequence_name(sequence)?;
+        let client = self.connector.get_client()?;
+        let rows = client
+            .query("SELECT nextval($1::text::regclass) FROM generate_series(1, $2::bigint)", &[&name, &(count as i64)])
+            .await?;
+        rows.iter()
+            .map(|row| row.try_get::<usize, i64>(0).map_err(|e| ExecutorError::ConvertRowError(e.to_string())))
+            .collect()
+    }
+
+    /// Returns the value most recently returned by `nextval` of the sequence in this session (`currval`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if `nextval` hasn't been called for the sequence in this session.
+    pub async fn current_value(&self, sequence: &Table<'_>) -> Result<i64, ExecutorError> {
+        self.query_value("SELECT currval($1::text::regclass)", sequence, &[]).await
+    }
+
+    /// Sets the value of the sequence (`setval`). With `is_called` the next `nextval` returns `value + increment`,
+    /// otherwise it returns `value`.
+    pub async fn set_value(&self, sequence: &Table<'_>, value: i64, is_called: bool) -> Result<i64, ExecutorError> {
+        self.query_value("SELECT setval($1::text::regclass, $2, $3)", sequence, &[&value, &is_called]).await
+    }
+
+    async fn query_value(&self, statement: &str, sequence: &Table<'_>, parameters: &[&(dyn ToSql + Sync)]) -> Result<i64, ExecutorError> {
+        let name = get_sequence_name(sequence)?;
+        let client = self.connector.get_client()?;
+        let mut all_parameters: Vec<&(dyn ToSql + Sync)> = vec![&name];
+        all_parameters.extend_from_slice(parameters);
+
+        let row = client.query_one(statement, &all_parameters).await?;
+        row.try_get::<usize, i64>(0).map_err(|e| ExecutorError::ConvertRowError(e.to_string()))
+    }
+}
+
+/// Returns the quoted name of the sequence passed as `regclass` after validating it.
+fn get_sequence_name(sequence: &Table<'_>) -> Result<String, ExecutorError> {
+    if let Table::SubQueryAsTable(_) = sequence {
+        return Err(ExecutorError::UnsafeExecutionError("a sub query can't be a sequence.".to_string()))
+    }
+    sequence.validate_identifiers().map_err(|e| ExecutorError::UnsafeExecutionError(e.to_string()))?;
+    Ok(sequence.get_table_name())
+}
@@ -0,0 +1,143 @@
+use std::fmt::{Display, Formatter};
+use bytes::BytesMut;
+use crate::connector::Connector;
+use crate::executor::introspection::Introspector;
+use crate::generator::base::MainGenerator;
+use crate::utils::errors::ExecutorError;
+
+/// A problem of a generator found by `Executor::validate`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationIssue {
+    /// A referenced table doesn't exist.
+    MissingTable(String),
+    /// PostgreSQL refused the statement, e.g. a referenced column doesn't exist (SQLSTATE `42703`).
+    InvalidStatement { code: String, message: String },
+    /// The number of the bound parameters differs from the placeholders of the statement.
+    ParameterCountMismatch { expected: usize, actual: usize },
+    /// A bound parameter can't be sent as the type PostgreSQL inferred for its placeholder.
+    IncompatibleParameter { position: usize, expected: String, actual: String },
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingTable(table) => write!(f, "the table '{}' does not exist", table),
+            Self::InvalidStatement { code, message } => write!(f, "{} (SQLSTATE {})", message, code),
+            Self::ParameterCountMismatch { expected, actual } =>
+                write!(f, "{} parameter(s) are expected but {} are bound", expected, actual),
+            Self::IncompatibleParameter { position, expected, actual } =>
+                write!(f, "${} is '{}' but {} is bound", position, expected, actual),
+        }
+    }
+}
+
+/// The result of `Executor::validate`, listing the issues found before the statement runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Returns whether no issue is found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn get_issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    fn push(&mut self, issue: ValidationIssue) {
+        self.issues.push(issue);
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_valid() {
+            return write!(f, "no issue found")
+        }
+        let issues = self.issues
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "{} issue(s) found: {}", issues.len(), issues.join("; "))
+    }
+}
+
+/// Checks the tables exist, prepares the statement so that PostgreSQL resolves the columns,
+/// and encodes every parameter as the type inferred for its placeholder.
+pub(super) async fn validate_generator<G: MainGenerator>(connector: &Connector, generator: &G) -> Result<ValidationReport, ExecutorError> {
+    let mut report = ValidationReport::default();
+
+    let introspector = Introspector::new(connector);
+    let mut checked_tables = Vec::new();
+    for table in generator.get_referenced_tables() {
+        let table_name = table.get_plain_table_name();
+        if checked_tables.contains(&table_name) {
+            continue
+        }
+        if !introspector.table_exists(&table).await? {
+            report.push(ValidationIssue::MissingTable(table_name.clone()));
+        }
+        checked_tables.push(table_name);
+    }
+    if !report.is_valid() {
+        // The statement can't be prepared without the tables.
+        return Ok(report)
+    }
+
+    let client = connector.get_client()?;
+    let statement = match client.prepare(generator.get_statement().as_str()).await {
+        Ok(statement) => statement,
+        Err(e) => {
+            let error = ExecutorError::from(e);
+            match error.get_database_error() {
+                Some(db_error) => {
+                    report.push(ValidationIssue::InvalidStatement {
+                        code: db_error.code().code().to_string(),
+                        message: db_error.message().to_string(),
+                    });
+                    return Ok(report)
+                }
+                None => return Err(error),
+            }
+        }
+    };
+
+    let parameters = generator.get_params();
+    let types = statement.params();
+    if types.len() != parameters.len() {
+        report.push(ValidationIssue::ParameterCountMismatch { expected: types.len(), actual: parameters.len() });
+        return Ok(report)
+    }
+    let type_names = parameters.get_type_names();
+    for (index, (param, ty)) in parameters.get_sql_params().into_iter().zip(types).enumerate() {
+        if param.to_sql_checked(ty, &mut BytesMut::new()).is_err() {
+            report.push(ValidationIssue::IncompatibleParameter {
+                position: index + 1,
+                expected: ty.name().to_string(),
+                actual: type_names[index].clone(),
+            });
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ValidationIssue, ValidationReport};
+
+    #[test]
+    fn test_validation_report() {
+        let mut report = ValidationReport::default();
+        assert!(report.is_valid());
+        assert_eq!(report.to_string(), "no issue found");
+
+        report.push(ValidationIssue::MissingTable("public.users".to_string()));
+        report.push(ValidationIssue::IncompatibleParameter { position: 2, expected: "int4".to_string(), actual: "Text".to_string() });
+        assert!(!report.is_valid());
+        assert_eq!(report.get_issues().len(), 2);
+        assert_eq!(report.to_string(), "2 issue(s) found: the table 'public.users' does not exist; $2 is 'int4' but Text is bound");
+    }
+}
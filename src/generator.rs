@@ -1,3 +1,9 @@
 pub mod manipulations;
 pub mod query;
-pub mod base;
\ No newline at end of file
+pub mod base;
+pub mod soft_delete;
+pub mod foreign_key;
+pub mod definitions;
+mod macros;
+#[cfg(feature = "raw-sql")]
+pub mod raw;
@@ -1,9 +1,11 @@
 use std::fmt::{Display, Formatter};
 use std::ops::{Add, AddAssign};
+use tokio_postgres::types::ToSql;
 use crate::generator::query::QueryGenerator;
-use crate::{Column, Variable};
-use crate::utils::errors::GeneratorError;
+use crate::{Column, Table, Variable};
+use crate::utils::errors::{GeneratorError, StatementSnapshot};
 use crate::utils::helpers::check_aggregation;
+use crate::utils::literal::inline_parameters;
 
 pub mod condition;
 pub mod join_table;
@@ -12,6 +14,65 @@ pub trait MainGenerator {
     fn get_statement(&self) -> String;
     fn get_params(&self) -> Parameters;
     fn get_all_parameters_num(&self) -> u16;
+    fn get_referenced_tables(&self) -> Vec<Table<'_>>;
+
+    /// Returns whether `get_referenced_tables` covers all the tables the statement reads.
+    ///
+    /// The `TablePolicy` of the executors refuses a generator returning `false` (e.g. a raw statement whose tables
+    /// aren't declared), since the policy can't check the tables it doesn't know.
+    fn has_known_tables(&self) -> bool {
+        true
+    }
+
+    /// Returns the statement with the `$n` placeholders and the parameters bound to them in the placeholder order,
+    /// so the statement can be executed by another driver (e.g. raw tokio-postgres or deadpool-postgres).
+    ///
+    /// # Example
+    /// ```rust
+    /// use safety_postgres::generator::base::{as_sql_params, BindMethod, ConditionOperator, MainGenerator};
+    /// use safety_postgres::generator::base::condition::Condition;
+    /// use safety_postgres::generator::query::query_column::QueryColumns;
+    /// use safety_postgres::generator::query::QueryGenerator;
+    /// use safety_postgres::{Table, Variable};
+    ///
+    /// # async fn run(client: &tokio_postgres::Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let users = Table::create_table(None, "users");
+    /// let user_id = users.get_column("id");
+    /// let generator = QueryGenerator::new(&users, QueryColumns::create_all_columns(&users))
+    ///     .filter(Condition::new(&user_id, Variable::Int(1).into(), ConditionOperator::Equal), BindMethod::FirstCondition)?;
+    ///
+    /// let (statement, params) = generator.get_statement_and_params();
+    /// let rows = client.query(statement.as_str(), &as_sql_params(&params)).await?;
+    /// # let _ = rows;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_statement_and_params(&self) -> (String, Vec<Variable>) {
+        (self.get_statement(), self.get_params().into())
+    }
+
+    /// Returns the statement with the placeholders replaced by the quoted literals of the parameters,
+    /// so it can be pasted into psql while investigating an issue.
+    ///
+    /// The output starts with a `/* DEBUG ONLY ... */` comment and the secrets are rendered as `'****'`.
+    /// It's only for debugging, so execute the statement with the parameters instead of the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError::InvalidInputError` if a parameter can't be rendered as a literal (e.g. a custom type).
+    fn to_debug_sql(&self) -> Result<String, GeneratorError> {
+        let statement = inline_parameters(self.get_statement().as_str(), &self.get_params())?;
+        Ok(format!("/* DEBUG ONLY: parameters are inlined */ {}", statement))
+    }
+}
+
+/// Converts `variables` to the parameter slice of tokio-postgres (and the drivers built on it),
+/// e.g. to execute the statement of `MainGenerator::get_statement_and_params` with `Client::query`.
+pub fn as_sql_params(variables: &[Variable]) -> Vec<&(dyn ToSql + Sync)> {
+    variables
+        .iter()
+        .map(|variable| variable as &(dyn ToSql + Sync))
+        .collect()
 }
 
 pub trait GeneratorPlaceholder {
@@ -43,6 +104,37 @@ pub enum ConditionOperator {
     NotILike,
     IsNull,
     IsNotNull,
+    Any,
+}
+
+impl ConditionOperator {
+    /// Renders the comparison between `left` and the placeholder `$placeholder_number`.
+    ///
+    /// `Any` compares against an array parameter, so the placeholder is wrapped as `= ANY($n)`.
+    pub(crate) fn get_placeholder_statement<T: Display>(&self, left: T, placeholder_number: u16) -> String {
+        match self {
+            ConditionOperator::Any => format!("{} {}(${})", left, self, placeholder_number),
+            _ => format!("{} {} ${}", left, self, placeholder_number),
+        }
+    }
+
+    /// Renders the comparison between `left` and `literal`, for the statements which can't take the parameters.
+    ///
+    /// `IS NULL` and `IS NOT NULL` don't take the right side, and `IN` takes the elements of an `ARRAY[...]` literal.
+    pub(crate) fn get_literal_statement<T: Display>(&self, left: T, literal: &str) -> String {
+        match self {
+            ConditionOperator::IsNull | ConditionOperator::IsNotNull => format!("{} {}", left, self),
+            ConditionOperator::In | ConditionOperator::NotIn => {
+                let elements = literal
+                    .strip_prefix("ARRAY[")
+                    .and_then(|elements| elements.strip_suffix(']'))
+                    .unwrap_or(literal);
+                format!("{} {} ({})", left, self, elements)
+            },
+            ConditionOperator::Any => format!("{} {}({})", left, self, literal),
+            _ => format!("{} {} {}", left, self, literal),
+        }
+    }
 }
 
 impl Display for ConditionOperator {
@@ -62,6 +154,7 @@ impl Display for ConditionOperator {
             ConditionOperator::NotILike => write!(f, "{}", "NOT ILIKE"),
             ConditionOperator::IsNull => write!(f, "{}", "IS NULL"),
             ConditionOperator::IsNotNull => write!(f, "{}", "IS NOT NULL"),
+            ConditionOperator::Any => write!(f, "= ANY"),
         }
     }
 }
@@ -98,6 +191,7 @@ impl Display for SortMethod {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct SortRules<'a> {
     sort_rules: Vec<SortRule<'a>>,
 }
@@ -126,6 +220,7 @@ impl <'a> SortRules<'a> {
     }
 }
 
+#[derive(Clone)]
 pub struct  SortRule<'a> {
     column: &'a Column<'a>,
     sort_method: SortMethod,
@@ -148,6 +243,7 @@ impl <'a> SortRule<'a> {
     }
 }
 
+#[derive(Clone)]
 pub enum Aggregation<'a> {
     Avg(Column<'a>),
     Count(Column<'a>),
@@ -180,6 +276,7 @@ impl Display for Aggregation<'_> {
     }
 }
 
+#[derive(Clone)]
 pub enum ReferenceValue<'a> {
     Variable(Variable),
     SubQueryAggregation(QueryGenerator<'a>)
@@ -199,6 +296,13 @@ impl ReferenceValue<'_> {
             Self::SubQueryAggregation(query) => query.get_params(),
         }
     }
+
+    pub(crate) fn get_referenced_tables(&self) -> Vec<Table<'_>> {
+        match self {
+            Self::Variable(_) => Vec::new(),
+            Self::SubQueryAggregation(query) => query.get_referenced_tables(),
+        }
+    }
 }
 
 impl From<Variable> for ReferenceValue<'_> {
@@ -244,6 +348,7 @@ impl Display for ReferenceValue<'_> {
     }
 }
 
+#[derive(Clone)]
 pub struct Parameters {
     parameters: Vec<Variable>,
 }
@@ -263,12 +368,60 @@ impl Parameters {
         self.parameters.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    /// Returns the parameter bound to the placeholder `$index + 1`, or `None` if it's out of range.
+    pub fn get(&self, index: usize) -> Option<&Variable> {
+        self.parameters.get(index)
+    }
+
+    /// Returns an iterator over the parameters in the placeholder order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Variable> {
+        self.parameters.iter()
+    }
+
+    pub(crate) fn get_variables(&self) -> &[Variable] {
+        &self.parameters
+    }
+
+    pub(crate) fn get_sql_params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        as_sql_params(&self.parameters)
+    }
+
     pub fn join(&self, delimiter: &str) -> String {
+        self.get_display_values().join(delimiter)
+    }
+
+    /// Returns the summary of the parameters without their values, e.g. `2 parameter(s): [Int, Text]`.
+    pub(crate) fn get_redacted_summary(&self) -> String {
+        let types = self.get_type_names();
+        format!("{} parameter(s): [{}]", types.len(), types.join(", "))
+    }
+
+    /// Returns the snapshot of `statement` with these parameters for the error context.
+    pub(crate) fn get_statement_snapshot(&self, statement: &str) -> StatementSnapshot {
+        let parameters = self.get_type_names()
+            .into_iter()
+            .zip(self.get_display_values())
+            .collect();
+        StatementSnapshot::new(statement, parameters)
+    }
+
+    pub(crate) fn get_type_names(&self) -> Vec<String> {
+        // The Debug name of a variant is its type, so the value part is dropped.
+        self.parameters
+            .iter()
+            .map(|param| format!("{:?}", param).split('(').next().unwrap_or_default().to_string())
+            .collect()
+    }
+
+    pub(crate) fn get_display_values(&self) -> Vec<String> {
         self.parameters
             .iter()
             .map(|param| format!("{}", param))
-            .collect::<Vec<String>>()
-            .join(delimiter)
+            .collect()
     }
 }
 
@@ -280,6 +433,30 @@ impl From<Vec<Variable>> for Parameters {
     }
 }
 
+impl From<Parameters> for Vec<Variable> {
+    fn from(value: Parameters) -> Self {
+        value.parameters
+    }
+}
+
+impl IntoIterator for Parameters {
+    type Item = Variable;
+    type IntoIter = std::vec::IntoIter<Variable>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.parameters.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Parameters {
+    type Item = &'a Variable;
+    type IntoIter = std::slice::Iter<'a, Variable>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.parameters.iter()
+    }
+}
+
 impl Add for Parameters {
     type Output = Self;
     fn add(self, rhs: Self) -> Self::Output {
@@ -301,3 +478,72 @@ impl Display for Parameters {
         write!(f, "{}", self.join(", "))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::base::{as_sql_params, BindMethod, ConditionOperator, MainGenerator, Parameters};
+    use crate::generator::base::condition::Condition;
+    use crate::generator::query::owned::OwnedQuery;
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::generator::query::QueryGenerator;
+    use crate::{Table, Variable};
+
+    fn assert_shareable<T: Clone + Send + Sync>() {}
+
+    /// The generators are shared across the tokio tasks, so they have to stay `Clone + Send + Sync`.
+    #[test]
+    fn test_generators_are_shareable() {
+        assert_shareable::<QueryGenerator<'static>>();
+        assert_shareable::<OwnedQuery>();
+        assert_shareable::<Parameters>();
+    }
+
+    #[test]
+    fn test_parameters_access() {
+        let parameters = Parameters::from(vec![Variable::Int(3), Variable::Text("x".to_string())]);
+        assert!(matches!(parameters.get(0), Some(Variable::Int(3))));
+        assert!(parameters.get(2).is_none());
+        assert_eq!(parameters.iter().count(), 2);
+        assert_eq!((&parameters).into_iter().filter(|param| matches!(param, Variable::Text(_))).count(), 1);
+
+        let variables: Vec<Variable> = parameters.into();
+        assert_eq!(variables.len(), 2);
+    }
+
+    #[test]
+    fn test_statement_and_params() {
+        let users = Table::create_table(None, "users");
+        let user_id = users.get_column("id");
+        let generator = QueryGenerator::new(&users, QueryColumns::create_all_columns(&users))
+            .filter(Condition::new(&user_id, Variable::Int(1).into(), ConditionOperator::Equal), BindMethod::FirstCondition)
+            .unwrap();
+
+        let (statement, params) = generator.get_statement_and_params();
+        assert_eq!(statement, "SELECT users.* FROM users WHERE  users.id = $1");
+        assert!(matches!(params.as_slice(), [Variable::Int(1)]));
+        assert_eq!(as_sql_params(&params).len(), 1);
+        assert_eq!(
+            generator.to_debug_sql().unwrap(),
+            "/* DEBUG ONLY: parameters are inlined */ SELECT users.* FROM users WHERE  users.id = 1");
+    }
+
+    #[test]
+    fn test_redacted_summary() {
+        let parameters = Parameters::from(vec![
+            Variable::Int(3), Variable::Text("secret@example.com".to_string()), Variable::Null]);
+        let summary = parameters.get_redacted_summary();
+        assert_eq!(summary, "3 parameter(s): [Int, Text, Null]");
+        assert!(!summary.contains("secret"));
+    }
+
+    #[test]
+    fn test_statement_snapshot() {
+        let parameters = Parameters::from(vec![
+            Variable::Int(3), Variable::Secret("password".to_string()), Variable::Text("x".repeat(40))]);
+        let snapshot = parameters.get_statement_snapshot("SELECT * FROM users WHERE id = $1 AND password = $2 AND bio = $3");
+        assert_eq!(snapshot.get_parameters(), &["Int(3)".to_string(), "Secret(****)".to_string(), format!("Text({}…)", "x".repeat(32))]);
+        assert_eq!(
+            snapshot.to_string(),
+            format!("`SELECT * FROM users WHERE id = $1 AND password = $2 AND bio = $3` with [$1 = Int(3), $2 = Secret(****), $3 = Text({}…)]", "x".repeat(32)));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::generator::base::{BindMethod, ConditionOperator, GeneratorPlaceholder, GeneratorPlaceholderWrapper, MainGenerator, Parameters, ReferenceValue};
 use crate::utils::errors::GeneratorError;
-use crate::Column;
+use crate::utils::identifier::quote_identifier;
+use crate::utils::literal::to_literal;
+use crate::{Column, Table};
 
+#[derive(Clone)]
 pub(crate) struct Conditions<'a> {
     conditions: Vec<Condition<'a>>,
     bind_methods: Vec<BindMethod>,
@@ -35,6 +38,12 @@ impl <'a> Conditions<'a> {
         self.conditions.push(condition);
         Ok(())
     }
+
+    pub(crate) fn get_referenced_tables(&self) -> Vec<Table<'_>> {
+        self.conditions.iter()
+            .flat_map(|condition| condition.ref_value.get_referenced_tables())
+            .collect()
+    }
 }
 
 impl GeneratorPlaceholderWrapper for Conditions<'_> {
@@ -66,6 +75,52 @@ impl GeneratorPlaceholderWrapper for Conditions<'_> {
     }
 }
 
+/// A list of conditions which own their columns, so it can be built in place (e.g. by the `conditions!` macro)
+/// and added to a generator at once by `QueryGenerator::add_condition_set`.
+#[derive(Clone)]
+pub struct ConditionSet<'a> {
+    conditions: Vec<(Column<'a>, ReferenceValue<'a>, ConditionOperator, BindMethod)>,
+}
+
+impl <'a> ConditionSet<'a> {
+    pub fn new() -> ConditionSet<'a> {
+        Self {
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Adds a condition comparing the column with the value by the operator.
+    pub fn push(&mut self,
+                column: Column<'a>,
+                condition_ref_value: ReferenceValue<'a>,
+                condition_operator: ConditionOperator,
+                bind_method: BindMethod) {
+        self.conditions.push((column, condition_ref_value, condition_operator, bind_method));
+    }
+
+    pub fn len(&self) -> usize {
+        self.conditions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// Returns the conditions borrowing the columns of this set, with their bind methods.
+    pub(crate) fn get_conditions(&'a self) -> Vec<(Condition<'a>, BindMethod)> {
+        self.conditions.iter()
+            .map(|(column, ref_value, operator, bind_method)| (Condition::new(column, ref_value.clone(), *operator), *bind_method))
+            .collect()
+    }
+}
+
+impl Default for ConditionSet<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone)]
 pub struct Condition<'a> {
     column: &'a Column<'a>,
     ref_value: ReferenceValue<'a>,
@@ -84,12 +139,23 @@ impl <'a> Condition<'a> {
             operator: condition_operator,
         }
     }
+
+    /// Renders the condition with the value inlined as a literal and the column without the table name,
+    /// e.g. for the predicate of a partial index.
+    pub(crate) fn get_literal_statement(&self) -> Result<String, GeneratorError> {
+        match &self.ref_value {
+            ReferenceValue::Variable(variable) => Ok(self.operator.get_literal_statement(
+                quote_identifier(self.column.get_column_name()), to_literal(variable)?.as_str())),
+            ReferenceValue::SubQueryAggregation(_) => Err(GeneratorError::InvalidInputError(
+                "a condition with a sub query can't be rendered with a literal.".to_string())),
+        }
+    }
 }
 
 impl GeneratorPlaceholder for Condition<'_> {
     fn get_statement(&self, start_placeholder_number: u16) -> String {
         match &self.ref_value {
-            ReferenceValue::Variable(_) => format!("{} {} ${}", self.column, self.operator, start_placeholder_number),
+            ReferenceValue::Variable(_) => self.operator.get_placeholder_statement(self.column, start_placeholder_number),
             ReferenceValue::SubQueryAggregation(query) => {
                 query.get_statement()
             }
@@ -2,8 +2,10 @@ use std::fmt::Display;
 use crate::generator::base::{BindMethod, ConditionOperator, GeneratorPlaceholder, GeneratorPlaceholderWrapper, Parameters};
 use crate::generator::query::query_column::QueryColumns;
 use crate::utils::helpers::Pair;
+use crate::utils::identifier::quote_identifier;
 use crate::{Column, Table};
 
+#[derive(Clone)]
 pub(crate) struct JoinTables<'a> {
     join_tables: Vec<JoinTable<'a>>,
 }
@@ -19,6 +21,18 @@ impl <'a> JoinTables<'a> {
         self.join_tables.push(join_table)
     }
 
+    pub(crate) fn get_tables(&self) -> Vec<&'a Table<'a>> {
+        self.join_tables.iter()
+            .map(|join_table| join_table.table)
+            .collect()
+    }
+
+    pub(crate) fn get_referenced_tables(&self) -> Vec<Table<'_>> {
+        self.join_tables.iter()
+            .flat_map(|join_table| join_table.table.get_referenced_tables())
+            .collect()
+    }
+
     pub(crate) fn get_query_columns(&self) -> String {
         self.join_tables.iter()
             .map(|join_table| join_table.query_columns.get_query_columns_statement())
@@ -53,10 +67,11 @@ impl GeneratorPlaceholderWrapper for JoinTables<'_> {
     }
 }
 
+#[derive(Clone)]
 pub struct JoinTable<'a> {
     table: &'a Table<'a>,
     query_columns: &'a QueryColumns<'a>,
-    join_columns: Vec<JoinColumn<'a>>,
+    join_columns: Vec<JoinColumn>,
     join_type: JoinType,
 }
 
@@ -67,7 +82,7 @@ impl <'a> JoinTable<'a> {
         Self {
             table,
             query_columns,
-            join_columns: Vec::<JoinColumn<'a>>::new(),
+            join_columns: Vec::<JoinColumn>::new(),
             join_type }
     }
 
@@ -79,9 +94,13 @@ impl <'a> JoinTable<'a> {
         );
     }
 
+    pub(crate) fn add_join_column(&mut self, join_column: JoinColumn) {
+        self.join_columns.push(join_column);
+    }
+
     pub(crate) fn get_join_dist_table_names(&self) -> Vec<String> {
         self.join_columns.iter()
-            .map(|join_column| join_column.columns.get_first().get_table_name())
+            .map(|join_column| join_column.dist_table_name.clone())
             .collect()
     }
 }
@@ -122,25 +141,43 @@ impl GeneratorPlaceholder for JoinTable<'_> {
     }
 }
 
-pub struct JoinColumn<'a> {
-    columns: Pair<&'a Column<'a>>,
+#[derive(Clone)]
+pub struct JoinColumn {
+    columns: Pair<String>,
+    dist_table_name: String,
     operator: ConditionOperator,
     bind_method: BindMethod,
 }
 
-impl <'a> JoinColumn<'a> {
-    pub fn new(columns: Pair<&'a Column<'a>>,
+impl JoinColumn {
+    pub fn new(columns: Pair<&Column<'_>>,
                operator: ConditionOperator,
-               bind_method: BindMethod) -> JoinColumn<'a> {
+               bind_method: BindMethod) -> JoinColumn {
+        let (dist_column, src_column) = columns.get_values();
         Self {
-            columns,
+            columns: Pair::new(dist_column.to_string(), src_column.to_string()),
+            dist_table_name: dist_column.get_table_name(),
             operator,
             bind_method
         }
     }
-}
 
+    /// Creates the `dist_table.dist_column = src_table.src_column` join column from the names.
+    pub(crate) fn from_names(dist_table: &Table<'_>, dist_column: &str,
+                             src_table: &Table<'_>, src_column: &str,
+                             bind_method: BindMethod) -> JoinColumn {
+        Self {
+            columns: Pair::new(
+                format!("{}.{}", dist_table.get_table_name(), quote_identifier(dist_column)),
+                format!("{}.{}", src_table.get_table_name(), quote_identifier(src_column))),
+            dist_table_name: dist_table.get_table_name(),
+            operator: ConditionOperator::Equal,
+            bind_method,
+        }
+    }
+}
 
+#[derive(Clone)]
 pub enum JoinType {
     Inner,
     Left,
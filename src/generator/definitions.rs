@@ -0,0 +1,94 @@
+use regex::Regex;
+use std::sync::OnceLock;
+use crate::utils::errors::GeneratorError;
+use crate::utils::identifier::validate_identifier;
+
+pub mod create_table;
+pub mod create_index;
+pub mod alter_table;
+pub mod destructive;
+pub mod materialized_view;
+pub mod maintenance;
+
+/// Generators of the DDL statements, executed by `executor::definitions::Definition`.
+///
+/// The DDL statements can't take the parameters, so the statement is validated as a whole when it's generated.
+pub trait DefinitionGenerator {
+    /// Returns the validated DDL statement.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError` if a name, a type or an expression isn't safe or the definition is inconsistent.
+    fn get_statement(&self) -> Result<String, GeneratorError>;
+}
+
+/// Checks `name` is accepted by the `IdentifierPolicy`.
+pub(crate) fn validate_definition_name(name: &str, kind: &str) -> Result<(), GeneratorError> {
+    if !validate_identifier(name) {
+        return Err(GeneratorError::InvalidInputError(
+            format!("'{}' is not a valid {} name.", name, kind)))
+    }
+    Ok(())
+}
+
+/// Checks `type_name` is a type name like `varchar(255)`, `numeric(10, 2)`, `timestamp with time zone` or `int4[]`.
+pub(crate) fn validate_type_name(type_name: &str) -> Result<(), GeneratorError> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*(?: [A-Za-z_][A-Za-z0-9_]*)*(?:\(\d+(?:, ?\d+)?\))?(?:\[\])*$")
+            .expect("the type name pattern is invalid")
+    });
+    if !pattern.is_match(type_name) {
+        return Err(GeneratorError::InvalidInputError(
+            format!("'{}' is not a valid type name.", type_name)))
+    }
+    Ok(())
+}
+
+/// Checks an SQL expression (e.g. of a `CHECK` constraint) can't end the statement or comment out the rest of it.
+///
+/// The expression is still inserted as it is, so it must not come from the user input.
+pub(crate) fn validate_expression(expression: &str) -> Result<(), GeneratorError> {
+    if expression.trim().is_empty() {
+        return Err(GeneratorError::InvalidInputError("The expression is empty.".to_string()))
+    }
+    if ["--", "/*", "*/", ";"].iter().any(|token| expression.contains(token)) {
+        return Err(GeneratorError::InvalidInputError(
+            format!("'{}' contains a statement delimiter or a comment.", expression)))
+    }
+    let mut depth = 0i32;
+    for c in expression.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {},
+        }
+        if depth < 0 {
+            break
+        }
+    }
+    if depth != 0 {
+        return Err(GeneratorError::InvalidInputError(
+            format!("'{}' has unbalanced parentheses.", expression)))
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_expression, validate_type_name};
+
+    #[test]
+    fn test_definition_validators() {
+        assert!(validate_type_name("varchar(255)").is_ok());
+        assert!(validate_type_name("numeric(10, 2)").is_ok());
+        assert!(validate_type_name("timestamp with time zone").is_ok());
+        assert!(validate_type_name("int4[]").is_ok());
+        assert!(validate_type_name("int; DROP TABLE users").is_err());
+
+        assert!(validate_expression("price > 0 AND (stock >= 0)").is_ok());
+        assert!(validate_expression("price > 0); DROP TABLE users; --").is_err());
+        assert!(validate_expression("price > 0) OR (true").is_err());
+        assert!(validate_expression(" ").is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::generator::definitions::create_table::ColumnDefinition;
+use crate::generator::definitions::{validate_definition_name, validate_expression, DefinitionGenerator};
+use crate::utils::errors::GeneratorError;
+use crate::utils::identifier::quote_identifier;
+use crate::Table;
+
+enum AlterAction<'a> {
+    AddColumn(ColumnDefinition),
+    DropColumn(String),
+    SetNotNull(String),
+    DropNotNull(String),
+    AddUnique { name: String, columns: Vec<String> },
+    AddCheck { name: String, expression: String },
+    AddForeignKey { name: String, columns: Vec<String>, referenced_table: &'a Table<'a>, referenced_columns: Vec<String> },
+    DropConstraint(String),
+    RenameColumn { from: String, to: String },
+    RenameTo(String),
+}
+
+impl AlterAction<'_> {
+    fn is_rename(&self) -> bool {
+        matches!(self, Self::RenameColumn { .. } | Self::RenameTo(_))
+    }
+
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        let statement = match self {
+            Self::AddColumn(column) => format!("ADD COLUMN {}", column.get_statement()?),
+            Self::DropColumn(column) => format!("DROP COLUMN {}", get_name(column, "column")?),
+            Self::SetNotNull(column) => format!("ALTER COLUMN {} SET NOT NULL", get_name(column, "column")?),
+            Self::DropNotNull(column) => format!("ALTER COLUMN {} DROP NOT NULL", get_name(column, "column")?),
+            Self::AddUnique { name, columns } =>
+                format!("ADD CONSTRAINT {} UNIQUE ({})", get_name(name, "constraint")?, get_names(columns)?),
+            Self::AddCheck { name, expression } => {
+                validate_expression(expression)?;
+                format!("ADD CONSTRAINT {} CHECK ({})", get_name(name, "constraint")?, expression)
+            },
+            Self::AddForeignKey { name, columns, referenced_table, referenced_columns } => {
+                if columns.len() != referenced_columns.len() {
+                    return Err(GeneratorError::InconsistentConfigError(
+                        format!("The foreign key '{}' has {} columns but references {} columns.", name, columns.len(), referenced_columns.len())))
+                }
+                if let Table::SubQueryAsTable(_) = referenced_table {
+                    return Err(GeneratorError::InvalidTableNameError("a sub query can't be referenced by a foreign key.".to_string()))
+                }
+                referenced_table.validate_identifiers()?;
+                format!("ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+                        get_name(name, "constraint")?,
+                        get_names(columns)?,
+                        referenced_table.get_table_name(),
+                        get_names(referenced_columns)?)
+            },
+            Self::DropConstraint(name) => format!("DROP CONSTRAINT {}", get_name(name, "constraint")?),
+            Self::RenameColumn { from, to } =>
+                format!("RENAME COLUMN {} TO {}", get_name(from, "column")?, get_name(to, "column")?),
+            Self::RenameTo(name) => format!("RENAME TO {}", get_name(name, "table")?),
+        };
+        Ok(statement)
+    }
+}
+
+fn get_name(name: &str, kind: &str) -> Result<String, GeneratorError> {
+    validate_definition_name(name, kind)?;
+    Ok(quote_identifier(name))
+}
+
+fn get_names(names: &[String]) -> Result<String, GeneratorError> {
+    if names.is_empty() {
+        return Err(GeneratorError::InconsistentConfigError("A constraint must have at least one column.".to_string()))
+    }
+    let names = names
+        .iter()
+        .map(|name| get_name(name, "column"))
+        .collect::<Result<Vec<String>, GeneratorError>>()?;
+    Ok(names.join(", "))
+}
+
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+/// Generates `ALTER TABLE` for the lightweight schema changes.
+///
+/// Several actions are combined in one statement, except a rename, which PostgreSQL only accepts alone.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::definitions::alter_table::AlterTable;
+/// use safety_postgres::generator::definitions::create_table::ColumnDefinition;
+/// use safety_postgres::generator::definitions::DefinitionGenerator;
+/// use safety_postgres::Table;
+///
+/// let table = Table::create_table(None, "users");
+/// let alter_table = AlterTable::new(&table)
+///     .add_column(ColumnDefinition::new("email", "text"))
+///     .set_not_null("name")
+///     .add_unique("users_email_key", &["email"]);
+///
+/// assert_eq!(
+///     alter_table.get_statement().unwrap(),
+///     "ALTER TABLE users ADD COLUMN email text, ALTER COLUMN name SET NOT NULL, \
+///     ADD CONSTRAINT users_email_key UNIQUE (email)");
+/// ```
+pub struct AlterTable<'a> {
+    table: &'a Table<'a>,
+    actions: Vec<AlterAction<'a>>,
+}
+
+impl<'a> AlterTable<'a> {
+    pub fn new(table: &'a Table<'a>) -> Self {
+        Self {
+            table,
+            actions: Vec::new(),
+        }
+    }
+
+    pub fn add_column(mut self, column: ColumnDefinition) -> Self {
+        self.actions.push(AlterAction::AddColumn(column));
+        self
+    }
+
+    pub fn drop_column(mut self, column: &str) -> Self {
+        self.actions.push(AlterAction::DropColumn(column.to_string()));
+        self
+    }
+
+    pub fn set_not_null(mut self, column: &str) -> Self {
+        self.actions.push(AlterAction::SetNotNull(column.to_string()));
+        self
+    }
+
+    pub fn drop_not_null(mut self, column: &str) -> Self {
+        self.actions.push(AlterAction::DropNotNull(column.to_string()));
+        self
+    }
+
+    pub fn add_unique(mut self, name: &str, columns: &[&str]) -> Self {
+        self.actions.push(AlterAction::AddUnique { name: name.to_string(), columns: to_strings(columns) });
+        self
+    }
+
+    /// Adds the `CHECK (expression)` constraint.
+    ///
+    /// The expression is inserted as it is (only the statement delimiters and the comments are refused),
+    /// so it must not come from the user input.
+    pub fn add_check(mut self, name: &str, expression: &str) -> Self {
+        self.actions.push(AlterAction::AddCheck { name: name.to_string(), expression: expression.to_string() });
+        self
+    }
+
+    /// Adds the foreign key of `columns` referencing `referenced_columns` of `referenced_table` in the same order.
+    pub fn add_foreign_key(mut self, name: &str, columns: &[&str], referenced_table: &'a Table<'a>, referenced_columns: &[&str]) -> Self {
+        self.actions.push(AlterAction::AddForeignKey {
+            name: name.to_string(),
+            columns: to_strings(columns),
+            referenced_table,
+            referenced_columns: to_strings(referenced_columns),
+        });
+        self
+    }
+
+    pub fn drop_constraint(mut self, name: &str) -> Self {
+        self.actions.push(AlterAction::DropConstraint(name.to_string()));
+        self
+    }
+
+    pub fn rename_column(mut self, from: &str, to: &str) -> Self {
+        self.actions.push(AlterAction::RenameColumn { from: from.to_string(), to: to.to_string() });
+        self
+    }
+
+    /// Renames the table. The schema of the table doesn't change.
+    pub fn rename_to(mut self, name: &str) -> Self {
+        self.actions.push(AlterAction::RenameTo(name.to_string()));
+        self
+    }
+}
+
+impl DefinitionGenerator for AlterTable<'_> {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        if let Table::SubQueryAsTable(_) = self.table {
+            return Err(GeneratorError::InvalidTableNameError("a sub query can't be altered.".to_string()))
+        }
+        self.table.validate_identifiers()?;
+        if self.actions.is_empty() {
+            return Err(GeneratorError::InconsistentConfigError("No action is set to alter the table.".to_string()))
+        }
+        if self.actions.len() > 1 && self.actions.iter().any(AlterAction::is_rename) {
+            return Err(GeneratorError::InconsistentConfigError(
+                "A rename can't be combined with the other actions.".to_string()))
+        }
+
+        let actions = self.actions
+            .iter()
+            .map(AlterAction::get_statement)
+            .collect::<Result<Vec<String>, GeneratorError>>()?;
+        Ok(format!("ALTER TABLE {} {}", self.table.get_table_name(), actions.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::definitions::DefinitionGenerator;
+    use crate::Table;
+    use super::AlterTable;
+
+    #[test]
+    fn test_alter_table() {
+        let orders = Table::create_table(Some("shop"), "orders");
+        let users = Table::create_table(Some("shop"), "users");
+        let alter_table = AlterTable::new(&orders)
+            .drop_column("note")
+            .add_foreign_key("orders_user_id_fkey", &["user_id"], &users, &["id"])
+            .add_check("orders_amount_check", "amount >= 0");
+        assert_eq!(
+            alter_table.get_statement().unwrap(),
+            "ALTER TABLE shop.orders DROP COLUMN note, \
+            ADD CONSTRAINT orders_user_id_fkey FOREIGN KEY (user_id) REFERENCES shop.users (id), \
+            ADD CONSTRAINT orders_amount_check CHECK (amount >= 0)");
+
+        let alter_table = AlterTable::new(&orders).rename_column("amount", "total");
+        assert_eq!(alter_table.get_statement().unwrap(), "ALTER TABLE shop.orders RENAME COLUMN amount TO total");
+
+        assert!(AlterTable::new(&orders).rename_to("purchases").drop_column("note").get_statement().is_err());
+        assert!(AlterTable::new(&orders).drop_column("note; DROP TABLE users").get_statement().is_err());
+        assert!(AlterTable::new(&orders).add_foreign_key("fk", &["a", "b"], &users, &["id"]).get_statement().is_err());
+        assert!(AlterTable::new(&orders).get_statement().is_err());
+    }
+}
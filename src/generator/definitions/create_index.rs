@@ -0,0 +1,175 @@
+use crate::generator::base::{BindMethod, GeneratorPlaceholder};
+use crate::generator::base::condition::Condition;
+use crate::generator::definitions::{validate_definition_name, DefinitionGenerator};
+use crate::utils::errors::GeneratorError;
+use crate::utils::identifier::quote_identifier;
+use crate::Table;
+
+/// Generates `CREATE INDEX` of one or more columns, optionally unique, partial or built concurrently.
+///
+/// The `WHERE` predicate of a partial index is built from `Condition`s. An index predicate can't take
+/// the parameters, so the values of the conditions are inlined as literals.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::base::{BindMethod, ConditionOperator};
+/// use safety_postgres::generator::base::condition::Condition;
+/// use safety_postgres::generator::definitions::create_index::CreateIndex;
+/// use safety_postgres::generator::definitions::DefinitionGenerator;
+/// use safety_postgres::{Table, Variable};
+///
+/// let table = Table::create_table(None, "orders");
+/// let status = table.get_column("status");
+/// let create_index = CreateIndex::new("orders_open_idx", &table)
+///     .with_column("customer_id")
+///     .with_column("created_at")
+///     .with_condition(Condition::new(&status, Variable::from("open".to_string()).into(), ConditionOperator::Equal), BindMethod::FirstCondition)
+///     .concurrently();
+///
+/// assert_eq!(
+///     create_index.get_statement().unwrap(),
+///     "CREATE INDEX CONCURRENTLY orders_open_idx ON orders (customer_id, created_at) WHERE status = 'open'");
+/// ```
+pub struct CreateIndex<'a> {
+    name: String,
+    table: &'a Table<'a>,
+    columns: Vec<String>,
+    unique: bool,
+    concurrently: bool,
+    if_not_exists: bool,
+    conditions: Vec<(Condition<'a>, BindMethod)>,
+}
+
+impl<'a> CreateIndex<'a> {
+    pub fn new(name: &str, table: &'a Table<'a>) -> Self {
+        Self {
+            name: name.to_string(),
+            table,
+            columns: Vec::new(),
+            unique: false,
+            concurrently: false,
+            if_not_exists: false,
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Adds a column of the index. The columns are indexed in the order they are added.
+    pub fn with_column(mut self, column: &str) -> Self {
+        self.columns.push(column.to_string());
+        self
+    }
+
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    /// Builds the index without locking out the writes of the table.
+    ///
+    /// `CREATE INDEX CONCURRENTLY` can't run in a transaction block, so execute it with
+    /// `Definition::execute` outside of a transaction.
+    pub fn concurrently(mut self) -> Self {
+        self.concurrently = true;
+        self
+    }
+
+    pub fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+
+    /// Adds a condition of the `WHERE` predicate, which makes the index partial.
+    /// The condition should be on a column of the indexed table.
+    pub fn with_condition(mut self, condition: Condition<'a>, bind_method: BindMethod) -> Self {
+        self.conditions.push((condition, bind_method));
+        self
+    }
+
+    fn get_where_statement(&self) -> Result<String, GeneratorError> {
+        let mut statement = vec!["WHERE".to_string()];
+        for (index, (condition, bind_method)) in self.conditions.iter().enumerate() {
+            match (index, bind_method) {
+                (0, _) => {},
+                (_, BindMethod::FirstCondition) => return Err(GeneratorError::InconsistentConfigError(
+                    "'FirstCondition' indicates the first condition but already exist some conditions.".to_string())),
+                (_, bind_method) => statement.push(bind_method.to_string()),
+            }
+            if condition.get_table_name() != self.table.get_table_name() {
+                return Err(GeneratorError::InconsistentConfigError(
+                    format!("'{}' is not the indexed table.", condition.get_table_name())))
+            }
+            statement.push(condition.get_literal_statement()?);
+        }
+        Ok(statement.join(" "))
+    }
+}
+
+impl DefinitionGenerator for CreateIndex<'_> {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        if let Table::SubQueryAsTable(_) = self.table {
+            return Err(GeneratorError::InvalidTableNameError("a sub query can't be indexed.".to_string()))
+        }
+        self.table.validate_identifiers()?;
+        validate_definition_name(&self.name, "index")?;
+        if self.columns.is_empty() {
+            return Err(GeneratorError::InconsistentConfigError("The index must have at least one column.".to_string()))
+        }
+        for column in &self.columns {
+            validate_definition_name(column, "column")?;
+        }
+
+        let mut statement = vec!["CREATE".to_string()];
+        if self.unique {
+            statement.push("UNIQUE".to_string());
+        }
+        statement.push("INDEX".to_string());
+        if self.concurrently {
+            statement.push("CONCURRENTLY".to_string());
+        }
+        if self.if_not_exists {
+            statement.push("IF NOT EXISTS".to_string());
+        }
+        let columns = self.columns.iter().map(|column| quote_identifier(column)).collect::<Vec<String>>();
+        statement.push(format!("{} ON {} ({})", quote_identifier(&self.name), self.table.get_table_name(), columns.join(", ")));
+        if !self.conditions.is_empty() {
+            statement.push(self.get_where_statement()?);
+        }
+        Ok(statement.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::base::{BindMethod, ConditionOperator};
+    use crate::generator::base::condition::Condition;
+    use crate::generator::definitions::DefinitionGenerator;
+    use crate::{Table, Variable};
+    use super::CreateIndex;
+
+    #[test]
+    fn test_create_index() {
+        let table = Table::create_table(Some("shop"), "items");
+        let create_index = CreateIndex::new("items_code_key", &table).with_column("code").unique().if_not_exists();
+        assert_eq!(create_index.get_statement().unwrap(), "CREATE UNIQUE INDEX IF NOT EXISTS items_code_key ON shop.items (code)");
+
+        let deleted_at = table.get_column("deleted_at");
+        let stock = table.get_column("stock");
+        let create_index = CreateIndex::new("items_alive_idx", &table)
+            .with_column("name")
+            .with_condition(Condition::new(&deleted_at, Variable::Null.into(), ConditionOperator::IsNull), BindMethod::FirstCondition)
+            .with_condition(Condition::new(&stock, Variable::Int(0).into(), ConditionOperator::Greater), BindMethod::And);
+        assert_eq!(
+            create_index.get_statement().unwrap(),
+            "CREATE INDEX items_alive_idx ON shop.items (name) WHERE deleted_at IS NULL AND stock > 0");
+
+        let other = Table::create_table(None, "users");
+        let name = other.get_column("name");
+        let create_index = CreateIndex::new("items_name_idx", &table)
+            .with_column("name")
+            .with_condition(Condition::new(&name, Variable::Text("x".to_string()).into(), ConditionOperator::Equal), BindMethod::FirstCondition);
+        assert!(create_index.get_statement().is_err());
+
+        assert!(CreateIndex::new("items_idx", &table).get_statement().is_err());
+        assert!(CreateIndex::new("items_idx; DROP TABLE users", &table).with_column("name").get_statement().is_err());
+    }
+}
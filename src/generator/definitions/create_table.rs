@@ -0,0 +1,258 @@
+use crate::generator::definitions::{validate_definition_name, validate_expression, validate_type_name, DefinitionGenerator};
+use crate::utils::errors::GeneratorError;
+use crate::utils::identifier::quote_identifier;
+use crate::utils::literal::quote_literal;
+use crate::Table;
+
+/// The default value of a column.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DefaultValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    Text(String),
+    /// `CURRENT_TIMESTAMP`
+    CurrentTimestamp,
+    /// An SQL expression inserted as it is, e.g. `gen_random_uuid()`. It must not come from the user input.
+    Expression(String),
+}
+
+impl DefaultValue {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        match self {
+            Self::Null => Ok("NULL".to_string()),
+            Self::Bool(value) => Ok(value.to_string().to_uppercase()),
+            Self::Int(value) => Ok(value.to_string()),
+            Self::Double(value) if value.is_finite() => Ok(value.to_string()),
+            Self::Double(value) => Err(GeneratorError::InvalidInputError(
+                format!("'{}' can't be a default value.", value))),
+            Self::Text(value) => Ok(quote_literal(value)),
+            Self::CurrentTimestamp => Ok("CURRENT_TIMESTAMP".to_string()),
+            Self::Expression(expression) => {
+                validate_expression(expression)?;
+                Ok(expression.to_string())
+            },
+        }
+    }
+}
+
+/// A column of `CreateTable`.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::definitions::create_table::{ColumnDefinition, DefaultValue};
+///
+/// let column = ColumnDefinition::new("created_at", "timestamp with time zone")
+///     .not_null()
+///     .with_default(DefaultValue::CurrentTimestamp);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnDefinition {
+    name: String,
+    type_name: String,
+    not_null: bool,
+    unique: bool,
+    default: Option<DefaultValue>,
+}
+
+impl ColumnDefinition {
+    /// Creates a nullable column of `type_name`, e.g. `varchar(255)` or `numeric(10, 2)`.
+    pub fn new(name: &str, type_name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            not_null: false,
+            unique: false,
+            default: None,
+        }
+    }
+
+    pub fn not_null(mut self) -> Self {
+        self.not_null = true;
+        self
+    }
+
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    pub fn with_default(mut self, default: DefaultValue) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    pub(crate) fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn get_statement(&self) -> Result<String, GeneratorError> {
+        validate_definition_name(&self.name, "column")?;
+        validate_type_name(&self.type_name)?;
+
+        let mut statement = vec![quote_identifier(&self.name), self.type_name.clone()];
+        if self.not_null {
+            statement.push("NOT NULL".to_string());
+        }
+        if self.unique {
+            statement.push("UNIQUE".to_string());
+        }
+        if let Some(default) = &self.default {
+            statement.push(format!("DEFAULT {}", default.get_statement()?));
+        }
+        Ok(statement.join(" "))
+    }
+}
+
+/// Generates `CREATE TABLE`, e.g. to set up the tables of the tests or to provision a database.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::definitions::create_table::{ColumnDefinition, CreateTable, DefaultValue};
+/// use safety_postgres::generator::definitions::DefinitionGenerator;
+/// use safety_postgres::Table;
+///
+/// let table = Table::create_table(Some("shop"), "items");
+/// let create_table = CreateTable::new(&table)
+///     .if_not_exists()
+///     .with_column(ColumnDefinition::new("id", "bigint").not_null())
+///     .with_column(ColumnDefinition::new("name", "text").not_null().unique())
+///     .with_column(ColumnDefinition::new("price", "numeric(10, 2)").with_default(DefaultValue::Int(0)))
+///     .with_primary_key(&["id"])
+///     .with_check("price_positive", "price >= 0");
+///
+/// assert_eq!(
+///     create_table.get_statement().unwrap(),
+///     "CREATE TABLE IF NOT EXISTS shop.items (id bigint NOT NULL, name text NOT NULL UNIQUE, \
+///     price numeric(10, 2) DEFAULT 0, PRIMARY KEY (id), CONSTRAINT price_positive CHECK (price >= 0))");
+/// ```
+pub struct CreateTable<'a> {
+    table: &'a Table<'a>,
+    if_not_exists: bool,
+    columns: Vec<ColumnDefinition>,
+    primary_key: Vec<String>,
+    unique_constraints: Vec<Vec<String>>,
+    check_constraints: Vec<(String, String)>,
+}
+
+impl<'a> CreateTable<'a> {
+    pub fn new(table: &'a Table<'a>) -> Self {
+        Self {
+            table,
+            if_not_exists: false,
+            columns: Vec::new(),
+            primary_key: Vec::new(),
+            unique_constraints: Vec::new(),
+            check_constraints: Vec::new(),
+        }
+    }
+
+    pub fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+
+    pub fn with_column(mut self, column: ColumnDefinition) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Sets the (composite) primary key of the columns.
+    pub fn with_primary_key(mut self, columns: &[&str]) -> Self {
+        self.primary_key = columns.iter().map(|column| column.to_string()).collect();
+        self
+    }
+
+    /// Adds a (composite) unique constraint of the columns.
+    pub fn with_unique(mut self, columns: &[&str]) -> Self {
+        self.unique_constraints.push(columns.iter().map(|column| column.to_string()).collect());
+        self
+    }
+
+    /// Adds the `CONSTRAINT name CHECK (expression)` constraint.
+    ///
+    /// The expression is inserted as it is (only the statement delimiters and the comments are refused),
+    /// so it must not come from the user input.
+    pub fn with_check(mut self, name: &str, expression: &str) -> Self {
+        self.check_constraints.push((name.to_string(), expression.to_string()));
+        self
+    }
+
+    fn get_key_columns(&self, columns: &[String]) -> Result<String, GeneratorError> {
+        if columns.is_empty() {
+            return Err(GeneratorError::InconsistentConfigError("A key must have at least one column.".to_string()))
+        }
+        for column in columns {
+            if !self.columns.iter().any(|definition| definition.get_name() == column) {
+                return Err(GeneratorError::InconsistentConfigError(
+                    format!("'{}' is not a column of the table.", column)))
+            }
+        }
+        Ok(columns.iter().map(|column| quote_identifier(column)).collect::<Vec<String>>().join(", "))
+    }
+}
+
+impl DefinitionGenerator for CreateTable<'_> {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        if let Table::SubQueryAsTable(_) = self.table {
+            return Err(GeneratorError::InvalidTableNameError("a sub query can't be created as a table.".to_string()))
+        }
+        self.table.validate_identifiers()?;
+        if self.columns.is_empty() {
+            return Err(GeneratorError::InconsistentConfigError("The table must have at least one column.".to_string()))
+        }
+
+        let mut elements = Vec::new();
+        for column in &self.columns {
+            elements.push(column.get_statement()?);
+        }
+        if !self.primary_key.is_empty() {
+            elements.push(format!("PRIMARY KEY ({})", self.get_key_columns(&self.primary_key)?));
+        }
+        for columns in &self.unique_constraints {
+            elements.push(format!("UNIQUE ({})", self.get_key_columns(columns)?));
+        }
+        for (name, expression) in &self.check_constraints {
+            validate_definition_name(name, "constraint")?;
+            validate_expression(expression)?;
+            elements.push(format!("CONSTRAINT {} CHECK ({})", quote_identifier(name), expression));
+        }
+
+        let if_not_exists = if self.if_not_exists { "IF NOT EXISTS " } else { "" };
+        Ok(format!("CREATE TABLE {}{} ({})", if_not_exists, self.table.get_table_name(), elements.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::definitions::DefinitionGenerator;
+    use crate::Table;
+    use super::{ColumnDefinition, CreateTable, DefaultValue};
+
+    #[test]
+    fn test_create_table() {
+        let table = Table::create_table(None, "users");
+        let create_table = CreateTable::new(&table)
+            .with_column(ColumnDefinition::new("id", "serial"))
+            .with_column(ColumnDefinition::new("email", "varchar(255)").not_null())
+            .with_column(ColumnDefinition::new("nickname", "text").with_default(DefaultValue::Text("it's me".to_string())))
+            .with_primary_key(&["id"])
+            .with_unique(&["email", "nickname"]);
+        assert_eq!(
+            create_table.get_statement().unwrap(),
+            "CREATE TABLE users (id serial, email varchar(255) NOT NULL, nickname text DEFAULT 'it''s me', \
+            PRIMARY KEY (id), UNIQUE (email, nickname))");
+
+        let create_table = CreateTable::new(&table)
+            .with_column(ColumnDefinition::new("id", "serial"))
+            .with_primary_key(&["user_id"]);
+        assert!(create_table.get_statement().is_err());
+
+        let create_table = CreateTable::new(&table)
+            .with_column(ColumnDefinition::new("id", "int); DROP TABLE users; --"));
+        assert!(create_table.get_statement().is_err());
+
+        assert!(CreateTable::new(&table).get_statement().is_err());
+    }
+}
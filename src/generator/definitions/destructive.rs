@@ -0,0 +1,156 @@
+use crate::generator::definitions::DefinitionGenerator;
+use crate::utils::errors::GeneratorError;
+use crate::Table;
+
+/// The capability to drop or truncate one table, obtained by typing the table name again.
+///
+/// `DropTable` and `Truncate` can only be created with the capability of the same table,
+/// so a destructive statement can't be issued for a table by mistake (e.g. a variable pointing to the wrong table).
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::definitions::destructive::{AllowDestructive, Truncate};
+/// use safety_postgres::generator::definitions::DefinitionGenerator;
+/// use safety_postgres::Table;
+///
+/// let table = Table::create_table(Some("shop"), "carts");
+/// let allow = AllowDestructive::confirm(&table, "shop.carts").expect("the confirmation doesn't match");
+/// let truncate = Truncate::new(&table, &allow).restart_identity();
+///
+/// assert_eq!(truncate.get_statement().unwrap(), "TRUNCATE TABLE shop.carts RESTART IDENTITY");
+/// assert!(AllowDestructive::confirm(&table, "carts").is_err());
+/// ```
+#[derive(Debug)]
+pub struct AllowDestructive {
+    table_name: String,
+}
+
+impl AllowDestructive {
+    /// Confirms the destructive statements on `table`. `confirmation` must be the table name as it's referenced,
+    /// i.e. `"schema.table"` for a table with a schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError::InvalidInputError` if `confirmation` isn't exactly the table name.
+    pub fn confirm(table: &Table<'_>, confirmation: &str) -> Result<Self, GeneratorError> {
+        if let Table::SubQueryAsTable(_) = table {
+            return Err(GeneratorError::InvalidTableNameError("a sub query can't be dropped or truncated.".to_string()))
+        }
+        let table_name = table.get_plain_table_name();
+        if table_name != confirmation {
+            return Err(GeneratorError::InvalidInputError(
+                format!("the confirmation '{}' doesn't match the table name '{}'.", confirmation, table_name)))
+        }
+        Ok(Self { table_name })
+    }
+
+    pub(super) fn check(&self, table: &Table<'_>) -> Result<(), GeneratorError> {
+        table.validate_identifiers()?;
+        if self.table_name != table.get_plain_table_name() {
+            return Err(GeneratorError::InconsistentConfigError(
+                format!("'{}' is confirmed but the statement is for '{}'.", self.table_name, table.get_plain_table_name())))
+        }
+        Ok(())
+    }
+}
+
+/// Generates `DROP TABLE` for a table confirmed by `AllowDestructive`.
+pub struct DropTable<'a> {
+    table: &'a Table<'a>,
+    allow: &'a AllowDestructive,
+    if_exists: bool,
+    cascade: bool,
+}
+
+impl<'a> DropTable<'a> {
+    pub fn new(table: &'a Table<'a>, allow: &'a AllowDestructive) -> Self {
+        Self {
+            table,
+            allow,
+            if_exists: false,
+            cascade: false,
+        }
+    }
+
+    pub fn if_exists(mut self) -> Self {
+        self.if_exists = true;
+        self
+    }
+
+    /// Drops the objects depending on the table as well, e.g. the views and the foreign keys of the other tables.
+    pub fn cascade(mut self) -> Self {
+        self.cascade = true;
+        self
+    }
+}
+
+impl DefinitionGenerator for DropTable<'_> {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        self.allow.check(self.table)?;
+
+        let if_exists = if self.if_exists { "IF EXISTS " } else { "" };
+        let cascade = if self.cascade { " CASCADE" } else { "" };
+        Ok(format!("DROP TABLE {}{}{}", if_exists, self.table.get_table_name(), cascade))
+    }
+}
+
+/// Generates `TRUNCATE TABLE` for a table confirmed by `AllowDestructive`.
+pub struct Truncate<'a> {
+    table: &'a Table<'a>,
+    allow: &'a AllowDestructive,
+    restart_identity: bool,
+    cascade: bool,
+}
+
+impl<'a> Truncate<'a> {
+    pub fn new(table: &'a Table<'a>, allow: &'a AllowDestructive) -> Self {
+        Self {
+            table,
+            allow,
+            restart_identity: false,
+            cascade: false,
+        }
+    }
+
+    /// Restarts the sequences owned by the columns of the table.
+    pub fn restart_identity(mut self) -> Self {
+        self.restart_identity = true;
+        self
+    }
+
+    /// Truncates the tables referencing the table by a foreign key as well.
+    pub fn cascade(mut self) -> Self {
+        self.cascade = true;
+        self
+    }
+}
+
+impl DefinitionGenerator for Truncate<'_> {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        self.allow.check(self.table)?;
+
+        let restart_identity = if self.restart_identity { " RESTART IDENTITY" } else { "" };
+        let cascade = if self.cascade { " CASCADE" } else { "" };
+        Ok(format!("TRUNCATE TABLE {}{}{}", self.table.get_table_name(), restart_identity, cascade))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::definitions::DefinitionGenerator;
+    use crate::Table;
+    use super::{AllowDestructive, DropTable, Truncate};
+
+    #[test]
+    fn test_destructive() {
+        let users = Table::create_table(None, "users");
+        let orders = Table::create_table(None, "orders");
+        let allow = AllowDestructive::confirm(&users, "users").unwrap();
+
+        assert_eq!(DropTable::new(&users, &allow).if_exists().cascade().get_statement().unwrap(), "DROP TABLE IF EXISTS users CASCADE");
+        assert_eq!(Truncate::new(&users, &allow).cascade().get_statement().unwrap(), "TRUNCATE TABLE users CASCADE");
+
+        assert!(DropTable::new(&orders, &allow).get_statement().is_err());
+        assert!(AllowDestructive::confirm(&users, "user").is_err());
+    }
+}
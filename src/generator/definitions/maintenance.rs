@@ -0,0 +1,159 @@
+use crate::generator::definitions::destructive::AllowDestructive;
+use crate::generator::definitions::DefinitionGenerator;
+use crate::utils::errors::GeneratorError;
+use crate::Table;
+
+/// Options of the `VACUUM` statement generated by `Vacuum`.
+///
+/// `FULL` rewrites the whole table holding an `ACCESS EXCLUSIVE` lock, which blocks even the reads until it finishes,
+/// so it can only be set with the `AllowDestructive` of the table.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::definitions::destructive::AllowDestructive;
+/// use safety_postgres::generator::definitions::maintenance::{Vacuum, VacuumOptions};
+/// use safety_postgres::generator::definitions::DefinitionGenerator;
+/// use safety_postgres::Table;
+///
+/// let table = Table::create_table(None, "events");
+/// let vacuum = Vacuum::new(&table, VacuumOptions::new().with_analyze(true).with_skip_locked(true));
+/// assert_eq!(vacuum.get_statement().unwrap(), "VACUUM (ANALYZE, SKIP_LOCKED) events");
+///
+/// let allow = AllowDestructive::confirm(&table, "events").expect("the confirmation doesn't match");
+/// let vacuum_full = Vacuum::new(&table, VacuumOptions::new().with_full(&allow));
+/// assert_eq!(vacuum_full.get_statement().unwrap(), "VACUUM (FULL) events");
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct VacuumOptions<'a> {
+    full: Option<&'a AllowDestructive>,
+    analyze: bool,
+    verbose: bool,
+    skip_locked: bool,
+}
+
+impl<'a> VacuumOptions<'a> {
+    /// Creates the options of a plain `VACUUM`, which runs alongside the reads and writes of the table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `FULL` for the table confirmed by `allow`.
+    pub fn with_full(mut self, allow: &'a AllowDestructive) -> Self {
+        self.full = Some(allow);
+        self
+    }
+
+    /// Sets `ANALYZE`, which updates the statistics used by the planner after vacuuming.
+    pub fn with_analyze(mut self, analyze: bool) -> Self {
+        self.analyze = analyze;
+        self
+    }
+
+    /// Sets `VERBOSE`, which reports the progress as notices.
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Sets `SKIP_LOCKED`, which skips the table instead of waiting if it's locked (PostgreSQL 12 or later).
+    pub fn with_skip_locked(mut self, skip_locked: bool) -> Self {
+        self.skip_locked = skip_locked;
+        self
+    }
+
+    fn get_options_text(&self) -> String {
+        let mut options = Vec::new();
+        if self.full.is_some() {
+            options.push("FULL");
+        }
+        if self.analyze {
+            options.push("ANALYZE");
+        }
+        if self.verbose {
+            options.push("VERBOSE");
+        }
+        if self.skip_locked {
+            options.push("SKIP_LOCKED");
+        }
+        options.join(", ")
+    }
+}
+
+/// Generates `VACUUM` for a table.
+///
+/// `VACUUM` can't run in a transaction block, so it must be executed outside a transaction.
+pub struct Vacuum<'a> {
+    table: &'a Table<'a>,
+    options: VacuumOptions<'a>,
+}
+
+impl<'a> Vacuum<'a> {
+    pub fn new(table: &'a Table<'a>, options: VacuumOptions<'a>) -> Self {
+        Self {
+            table,
+            options,
+        }
+    }
+}
+
+impl DefinitionGenerator for Vacuum<'_> {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        if let Table::SubQueryAsTable(_) = self.table {
+            return Err(GeneratorError::InvalidTableNameError("a sub query can't be vacuumed.".to_string()))
+        }
+        match self.options.full {
+            Some(allow) => allow.check(self.table)?,
+            None => self.table.validate_identifiers()?,
+        }
+
+        let options = self.options.get_options_text();
+        if options.is_empty() {
+            return Ok(format!("VACUUM {}", self.table.get_table_name()))
+        }
+        Ok(format!("VACUUM ({}) {}", options, self.table.get_table_name()))
+    }
+}
+
+/// Generates `ANALYZE` for a table, which updates the statistics used by the planner.
+pub struct Analyze<'a> {
+    table: &'a Table<'a>,
+}
+
+impl<'a> Analyze<'a> {
+    pub fn new(table: &'a Table<'a>) -> Self {
+        Self { table }
+    }
+}
+
+impl DefinitionGenerator for Analyze<'_> {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        if let Table::SubQueryAsTable(_) = self.table {
+            return Err(GeneratorError::InvalidTableNameError("a sub query can't be analyzed.".to_string()))
+        }
+        self.table.validate_identifiers()?;
+
+        Ok(format!("ANALYZE {}", self.table.get_table_name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::definitions::destructive::AllowDestructive;
+    use crate::generator::definitions::DefinitionGenerator;
+    use crate::Table;
+    use super::{Analyze, Vacuum, VacuumOptions};
+
+    #[test]
+    fn test_maintenance() {
+        let events = Table::create_table(Some("logs"), "events");
+        let users = Table::create_table(None, "users");
+
+        assert_eq!(Vacuum::new(&events, VacuumOptions::new()).get_statement().unwrap(), "VACUUM logs.events");
+        assert_eq!(Analyze::new(&events).get_statement().unwrap(), "ANALYZE logs.events");
+
+        let allow = AllowDestructive::confirm(&events, "logs.events").unwrap();
+        let options = VacuumOptions::new().with_full(&allow).with_verbose(true);
+        assert_eq!(Vacuum::new(&events, options).get_statement().unwrap(), "VACUUM (FULL, VERBOSE) logs.events");
+        assert!(Vacuum::new(&users, options).get_statement().is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use crate::generator::base::MainGenerator;
+use crate::generator::definitions::DefinitionGenerator;
+use crate::generator::query::QueryGenerator;
+use crate::utils::errors::GeneratorError;
+use crate::utils::literal::inline_parameters;
+use crate::Table;
+
+/// Generates `CREATE MATERIALIZED VIEW ... AS` a query, e.g. to precompute the aggregates of a report.
+///
+/// A view definition can't take the parameters, so the parameters of the query are inlined as literals
+/// (a `Variable::Secret` as `'****'`, so don't put a secret in the definition of a view).
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::definitions::materialized_view::CreateMaterializedView;
+/// use safety_postgres::generator::definitions::DefinitionGenerator;
+/// use safety_postgres::generator::query::query_column::QueryColumns;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::Table;
+///
+/// let orders = Table::create_table(None, "orders");
+/// let view = Table::create_table(Some("report"), "all_orders");
+/// let query = QueryGenerator::new(&orders, QueryColumns::create_all_columns(&orders));
+/// let create_view = CreateMaterializedView::from_query(&view, query).with_no_data();
+///
+/// assert_eq!(
+///     create_view.get_statement().unwrap(),
+///     "CREATE MATERIALIZED VIEW report.all_orders AS SELECT orders.* FROM orders WITH NO DATA");
+/// ```
+pub struct CreateMaterializedView<'a> {
+    view: &'a Table<'a>,
+    query: QueryGenerator<'a>,
+    if_not_exists: bool,
+    with_data: bool,
+}
+
+impl<'a> CreateMaterializedView<'a> {
+    /// Creates the view `view` (the name and the schema of the view as a `Table`) of the result of `query`.
+    pub fn from_query(view: &'a Table<'a>, query: QueryGenerator<'a>) -> Self {
+        Self {
+            view,
+            query,
+            if_not_exists: false,
+            with_data: true,
+        }
+    }
+
+    pub fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+
+    /// Creates the view without running the query. The view can't be read until it's refreshed.
+    pub fn with_no_data(mut self) -> Self {
+        self.with_data = false;
+        self
+    }
+}
+
+impl DefinitionGenerator for CreateMaterializedView<'_> {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        if let Table::SubQueryAsTable(_) = self.view {
+            return Err(GeneratorError::InvalidTableNameError("a sub query can't be the name of a view.".to_string()))
+        }
+        self.view.validate_identifiers()?;
+        for table in self.query.get_referenced_tables() {
+            table.validate_identifiers()?;
+        }
+        let query = inline_parameters(self.query.get_statement().as_str(), &self.query.get_params())?;
+
+        let if_not_exists = if self.if_not_exists { "IF NOT EXISTS " } else { "" };
+        let with_data = if self.with_data { "" } else { " WITH NO DATA" };
+        Ok(format!("CREATE MATERIALIZED VIEW {}{} AS {}{}", if_not_exists, self.view.get_table_name(), query, with_data))
+    }
+}
+
+/// Generates `REFRESH MATERIALIZED VIEW`.
+pub struct RefreshMaterializedView<'a> {
+    view: &'a Table<'a>,
+    concurrently: bool,
+}
+
+impl<'a> RefreshMaterializedView<'a> {
+    /// Refreshes `view`. With `concurrently` the view can be read during the refresh,
+    /// which requires a unique index on the view and a populated view.
+    pub fn new(view: &'a Table<'a>, concurrently: bool) -> Self {
+        Self {
+            view,
+            concurrently,
+        }
+    }
+}
+
+impl DefinitionGenerator for RefreshMaterializedView<'_> {
+    fn get_statement(&self) -> Result<String, GeneratorError> {
+        if let Table::SubQueryAsTable(_) = self.view {
+            return Err(GeneratorError::InvalidTableNameError("a sub query can't be refreshed.".to_string()))
+        }
+        self.view.validate_identifiers()?;
+
+        let concurrently = if self.concurrently { "CONCURRENTLY " } else { "" };
+        Ok(format!("REFRESH MATERIALIZED VIEW {}{}", concurrently, self.view.get_table_name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::base::{BindMethod, ConditionOperator};
+    use crate::generator::base::condition::Condition;
+    use crate::generator::definitions::DefinitionGenerator;
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::generator::query::QueryGenerator;
+    use crate::{Table, Variable};
+    use super::{CreateMaterializedView, RefreshMaterializedView};
+
+    #[test]
+    fn test_materialized_view() {
+        let orders = Table::create_table(None, "orders");
+        let status = orders.get_column("status");
+        let view = Table::create_table(None, "open_orders");
+        let mut query = QueryGenerator::new(&orders, QueryColumns::create_all_columns(&orders));
+        query.add_condition(
+            Condition::new(&status, Variable::Text("open".to_string()).into(), ConditionOperator::Equal),
+            BindMethod::FirstCondition).unwrap();
+
+        let create_view = CreateMaterializedView::from_query(&view, query).if_not_exists();
+        assert_eq!(
+            create_view.get_statement().unwrap(),
+            "CREATE MATERIALIZED VIEW IF NOT EXISTS open_orders AS SELECT orders.* FROM orders WHERE  orders.status = 'open'");
+
+        assert_eq!(RefreshMaterializedView::new(&view, true).get_statement().unwrap(), "REFRESH MATERIALIZED VIEW CONCURRENTLY open_orders");
+    }
+}
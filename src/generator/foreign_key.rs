@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use crate::executor::introspection::ForeignKeyInfo;
+use crate::Table;
+
+fn registry() -> &'static RwLock<HashMap<String, Vec<ForeignKeyInfo>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, Vec<ForeignKeyInfo>>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Caches the foreign keys of `table_name` read by `Introspector::get_foreign_keys`,
+/// replacing the ones cached before.
+pub(crate) fn cache_foreign_keys(table_name: &str, foreign_keys: Vec<ForeignKeyInfo>) {
+    registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(table_name.to_string(), foreign_keys);
+}
+
+pub(crate) fn get_cached_foreign_keys(table_name: &str) -> Vec<ForeignKeyInfo> {
+    registry()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Clears the foreign keys cached by `Introspector::get_foreign_keys`, e.g. after a migration changed them.
+pub fn clear_foreign_key_cache() {
+    registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+}
+
+/// Returns whether `table` is the table referenced by a foreign key.
+/// A table without the schema matches the referenced table in any schema.
+pub(crate) fn is_referenced_table(table: &Table<'_>, foreign_key: &ForeignKeyInfo) -> bool {
+    match table {
+        Table::WithSchema { schema_name, table_name } =>
+            *schema_name == foreign_key.referenced_schema && *table_name == foreign_key.referenced_table,
+        Table::NonSchema { table_name } => *table_name == foreign_key.referenced_table,
+        Table::SubQueryAsTable(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::executor::introspection::ForeignKeyInfo;
+    use crate::generator::base::join_table::JoinType;
+    use crate::generator::base::MainGenerator;
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::generator::query::QueryGenerator;
+    use crate::Table;
+    use super::cache_foreign_keys;
+
+    fn foreign_key(name: &str, column: &str, referenced_table: &str) -> ForeignKeyInfo {
+        ForeignKeyInfo {
+            constraint_name: name.to_string(),
+            columns: vec![column.to_string()],
+            referenced_schema: "fk_test".to_string(),
+            referenced_table: referenced_table.to_string(),
+            referenced_columns: vec!["id".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_auto_join() {
+        cache_foreign_keys("fk_test.orders", vec![foreign_key("orders_user_id_fkey", "user_id", "users")]);
+        let users = Table::create_table(Some("fk_test"), "users");
+        let orders = Table::create_table(Some("fk_test"), "orders");
+        let user_columns = QueryColumns::create_all_columns(&users);
+        let order_columns = QueryColumns::create_all_columns(&orders);
+
+        // The foreign key is on the joined table.
+        let mut generator = QueryGenerator::new(&users, QueryColumns::create_all_columns(&users));
+        generator.auto_join(&orders, &order_columns, JoinType::Inner).unwrap();
+        assert_eq!(
+            generator.get_statement(),
+            "SELECT fk_test.users.*, fk_test.orders.* FROM fk_test.users \
+            JOIN fk_test.orders ON fk_test.users.id = fk_test.orders.user_id");
+
+        // The foreign key is on the main table.
+        let mut generator = QueryGenerator::new(&orders, QueryColumns::create_all_columns(&orders));
+        generator.auto_join(&users, &user_columns, JoinType::Left).unwrap();
+        assert_eq!(
+            generator.get_statement(),
+            "SELECT fk_test.orders.*, fk_test.users.* FROM fk_test.orders \
+            LEFT JOIN fk_test.users ON fk_test.orders.user_id = fk_test.users.id");
+
+        let items = Table::create_table(Some("fk_test"), "items");
+        let item_columns = QueryColumns::create_all_columns(&items);
+        let mut generator = QueryGenerator::new(&orders, QueryColumns::create_all_columns(&orders));
+        assert!(generator.auto_join(&items, &item_columns, JoinType::Inner).is_err());
+
+        cache_foreign_keys("fk_test.orders", vec![
+            foreign_key("orders_buyer_id_fkey", "buyer_id", "users"),
+            foreign_key("orders_seller_id_fkey", "seller_id", "users"),
+        ]);
+        let mut generator = QueryGenerator::new(&orders, QueryColumns::create_all_columns(&orders));
+        assert!(generator.auto_join(&users, &user_columns, JoinType::Inner).is_err());
+    }
+}
@@ -0,0 +1,99 @@
+/// Builds a `ConditionSet` from the comparisons of the columns, e.g. `users.age >= 18`.
+///
+/// The left side is a `Table` variable and a column name, and the right side is any value convertible into
+/// `Variable`. The operators are `==`, `!=`, `>`, `>=`, `<` and `<=`, and the second and later conditions
+/// start with `and` or `or`. Add the set to a generator by `QueryGenerator::add_condition_set`.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::conditions;
+/// use safety_postgres::generator::base::MainGenerator;
+/// use safety_postgres::generator::query::query_column::QueryColumns;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::{Table, Variable};
+///
+/// let users = Table::create_table(None, "users");
+/// let conditions = conditions!{ users.age >= 18, and users.status == Variable::from("active") };
+///
+/// let mut generator = QueryGenerator::new(&users, QueryColumns::create_all_columns(&users));
+/// generator.add_condition_set(&conditions).unwrap();
+///
+/// assert_eq!(generator.get_params().len(), 2);
+/// ```
+#[macro_export]
+macro_rules! conditions {
+    (@operator ==) => { $crate::generator::base::ConditionOperator::Equal };
+    (@operator !=) => { $crate::generator::base::ConditionOperator::NotEqual };
+    (@operator >) => { $crate::generator::base::ConditionOperator::Greater };
+    (@operator >=) => { $crate::generator::base::ConditionOperator::GreaterEq };
+    (@operator <) => { $crate::generator::base::ConditionOperator::Lower };
+    (@operator <=) => { $crate::generator::base::ConditionOperator::LowerEq };
+    (@bind and) => { $crate::generator::base::BindMethod::And };
+    (@bind or) => { $crate::generator::base::BindMethod::Or };
+    ($table:ident . $column:ident $operator:tt $value:expr
+        $(, $bind:ident $next_table:ident . $next_column:ident $next_operator:tt $next_value:expr)* $(,)?) => {{
+        let mut condition_set = $crate::generator::base::condition::ConditionSet::new();
+        condition_set.push(
+            $table.get_column(stringify!($column)),
+            $crate::Variable::from($value).into(),
+            $crate::conditions!(@operator $operator),
+            $crate::generator::base::BindMethod::FirstCondition);
+        $(
+            condition_set.push(
+                $next_table.get_column(stringify!($next_column)),
+                $crate::Variable::from($next_value).into(),
+                $crate::conditions!(@operator $next_operator),
+                $crate::conditions!(@bind $bind));
+        )*
+        condition_set
+    }};
+}
+
+/// Builds a `QueryColumns` of the columns, e.g. `columns![users.id, users.name]`.
+///
+/// Each column is a `Table` variable and a column name.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::columns;
+/// use safety_postgres::generator::base::MainGenerator;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::Table;
+///
+/// let users = Table::create_table(None, "users");
+/// let generator = QueryGenerator::new(&users, columns![users.id, users.name]);
+///
+/// assert_eq!(generator.get_statement(), "SELECT users.id, users.name FROM users");
+/// ```
+#[macro_export]
+macro_rules! columns {
+    ($($table:ident . $column:ident),+ $(,)?) => {
+        $crate::generator::query::query_column::QueryColumns::SpecifyColumns(vec![
+            $($crate::generator::query::query_column::QueryColumn::Owned($table.get_column(stringify!($column)))),+
+        ])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::base::MainGenerator;
+    use crate::generator::query::QueryGenerator;
+    use crate::{Table, Variable};
+
+    #[test]
+    fn test_condition_and_column_macros() {
+        let users = Table::create_table(None, "users");
+        let teams = Table::create_table(None, "teams");
+        let conditions = conditions!{ users.age >= 18, and users.status == Variable::from("active"), or teams.id != 3 };
+        assert_eq!(conditions.len(), 3);
+
+        let mut generator = QueryGenerator::new(&users, columns![users.id, users.name]);
+        assert!(generator.add_condition_set(&conditions).is_err());
+
+        let conditions = conditions!{ users.age >= 18, and users.status == "active" };
+        generator.add_condition_set(&conditions).unwrap();
+        assert_eq!(
+            generator.get_statement(),
+            "SELECT users.id, users.name FROM users WHERE  users.age >= $1 AND users.status = $2");
+    }
+}
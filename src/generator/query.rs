@@ -1,16 +1,22 @@
 use std::collections::HashSet;
 use std::ops::AddAssign;
 use crate::generator::base::{BindMethod, GeneratorPlaceholder, GeneratorPlaceholderWrapper, MainGenerator, Parameters, SortRule, SortRules};
-use crate::generator::base::condition::{Condition, Conditions};
-use crate::generator::base::join_table::{JoinTable, JoinTables};
+use crate::generator::base::condition::{Condition, ConditionSet, Conditions};
+use crate::generator::base::join_table::{JoinColumn, JoinTable, JoinTables, JoinType};
+use crate::generator::foreign_key::{get_cached_foreign_keys, is_referenced_table};
 use crate::generator::query::grouping::{GroupCondition, Groupings, GroupConditions};
+use crate::generator::query::owned::OwnedQuery;
 use crate::generator::query::query_column::QueryColumns;
+use crate::generator::soft_delete::{add_where_predicate, get_soft_delete_column};
 use crate::utils::errors::GeneratorError;
+use crate::utils::identifier::quote_identifier;
 use crate::{Column, Table};
 
 pub mod grouping;
+pub mod owned;
 pub mod query_column;
 
+#[derive(Clone)]
 pub struct QueryGenerator<'a> {
     base_table: &'a Table<'a>,
     main_query_columns: QueryColumns<'a>,
@@ -20,6 +26,7 @@ pub struct QueryGenerator<'a> {
     group_conditions: GroupConditions<'a>,
     sort_rules: SortRules<'a>,
     include_tables: HashSet<String>,
+    include_deleted: bool,
     placeholder_start_num: u16,
 }
 
@@ -39,10 +46,69 @@ impl<'a> QueryGenerator<'a> {
             group_conditions: GroupConditions::new(),
             sort_rules: SortRules::new(),
             include_tables: HashSet::from_iter(vec![main_table]),
+            include_deleted: false,
             placeholder_start_num: 1,
         }
     }
 
+    /// Adds the join table like `add_join_table` and returns the generator, so the calls can be chained.
+    ///
+    /// # Example
+    /// ```rust
+    /// use safety_postgres::generator::base::{BindMethod, ConditionOperator, SortMethod, SortRule};
+    /// use safety_postgres::generator::base::condition::Condition;
+    /// use safety_postgres::generator::base::join_table::{JoinTable, JoinType};
+    /// use safety_postgres::generator::query::query_column::QueryColumns;
+    /// use safety_postgres::generator::query::QueryGenerator;
+    /// use safety_postgres::utils::helpers::Pair;
+    /// use safety_postgres::{Table, Variable};
+    /// # use safety_postgres::utils::errors::GeneratorError;
+    ///
+    /// # fn main() -> Result<(), GeneratorError> {
+    /// let users = Table::create_table(None, "users");
+    /// let teams = Table::create_table(None, "teams");
+    /// let (user_team_id, team_id, user_age) = (users.get_column("team_id"), teams.get_column("id"), users.get_column("age"));
+    /// let team_columns = QueryColumns::create_all_columns(&teams);
+    ///
+    /// let mut join_table = JoinTable::new(&teams, &team_columns, JoinType::Inner);
+    /// join_table.add_join_columns(Pair::new(&user_team_id, &team_id), ConditionOperator::Equal, BindMethod::FirstCondition);
+    ///
+    /// let generator = QueryGenerator::new(&users, QueryColumns::create_all_columns(&users))
+    ///     .join(join_table)?
+    ///     .filter(Condition::new(&user_age, Variable::Int(20).into(), ConditionOperator::GreaterEq), BindMethod::FirstCondition)?
+    ///     .order_by(SortRule::new(&user_age, SortMethod::Desc))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn join(mut self, join_table: JoinTable<'a>) -> Result<Self, GeneratorError> {
+        self.add_join_table(join_table)?;
+        Ok(self)
+    }
+
+    /// Adds the condition like `add_condition` and returns the generator, so the calls can be chained.
+    pub fn filter(mut self, condition: Condition<'a>, bind_method: BindMethod) -> Result<Self, GeneratorError> {
+        self.add_condition(condition, bind_method)?;
+        Ok(self)
+    }
+
+    /// Adds the grouping column like `add_grouping` and returns the generator, so the calls can be chained.
+    pub fn group_by(mut self, grouping_column: &'a Column<'a>) -> Result<Self, GeneratorError> {
+        self.add_grouping(grouping_column)?;
+        Ok(self)
+    }
+
+    /// Adds the aggregation condition like `add_aggregation_condition` and returns the generator, so the calls can be chained.
+    pub fn having(mut self, aggregation_condition: GroupCondition<'a>) -> Result<Self, GeneratorError> {
+        self.add_aggregation_condition(aggregation_condition)?;
+        Ok(self)
+    }
+
+    /// Adds the sort rule like `add_sort_rule` and returns the generator, so the calls can be chained.
+    pub fn order_by(mut self, sort_rule: SortRule<'a>) -> Result<Self, GeneratorError> {
+        self.add_sort_rule(sort_rule)?;
+        Ok(self)
+    }
+
     pub fn add_join_table(&mut self, join_table: JoinTable<'a>) -> Result<(), GeneratorError> {
         let table = join_table.get_table_name();
 
@@ -59,6 +125,83 @@ impl<'a> QueryGenerator<'a> {
         Ok(())
     }
 
+    /// Joins `table` on the foreign key between it and the main table or a joined table,
+    /// so the join columns don't have to be written by hand.
+    ///
+    /// The foreign keys are read from the cache filled by `Introspector::get_foreign_keys`,
+    /// so the foreign keys of `table` and of the tables it's joined to should be read beforehand.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError::InconsistentConfigError` if no foreign key or more than one foreign key
+    /// relates `table` to the main table and the joined tables.
+    ///
+    /// # Example
+    /// ```rust
+    /// use safety_postgres::executor::base::Executor;
+    /// use safety_postgres::executor::query::Query;
+    /// use safety_postgres::generator::base::join_table::JoinType;
+    /// use safety_postgres::generator::query::query_column::QueryColumns;
+    /// use safety_postgres::generator::query::QueryGenerator;
+    /// use safety_postgres::Table;
+    ///
+    /// async fn count_orders(query: &Query) {
+    ///     let users = Table::create_table(Some("public"), "users");
+    ///     let orders = Table::create_table(Some("public"), "orders");
+    ///     query.introspect().get_foreign_keys(&orders).await.expect("introspection failed");
+    ///
+    ///     let order_columns = QueryColumns::create_all_columns(&orders);
+    ///     let mut generator = QueryGenerator::new(&users, QueryColumns::create_all_columns(&users));
+    ///     generator.auto_join(&orders, &order_columns, JoinType::Inner).expect("no foreign key");
+    /// }
+    /// ```
+    pub fn auto_join(&mut self, table: &'a Table<'a>, query_columns: &'a QueryColumns<'a>, join_type: JoinType) -> Result<(), GeneratorError> {
+        let mut joined_tables = vec![self.base_table];
+        joined_tables.extend(self.join_tables.get_tables());
+
+        let mut candidates = Vec::new();
+        for foreign_key in get_cached_foreign_keys(table.get_plain_table_name().as_str()) {
+            for joined_table in joined_tables.iter().filter(|joined_table| is_referenced_table(joined_table, &foreign_key)) {
+                let join_columns = foreign_key.referenced_columns.iter()
+                    .zip(&foreign_key.columns)
+                    .map(|(dist_column, src_column)| (*joined_table, dist_column.clone(), src_column.clone()))
+                    .collect::<Vec<_>>();
+                candidates.push((foreign_key.constraint_name.clone(), join_columns));
+            }
+        }
+        for joined_table in &joined_tables {
+            for foreign_key in get_cached_foreign_keys(joined_table.get_plain_table_name().as_str()) {
+                if !is_referenced_table(table, &foreign_key) {
+                    continue
+                }
+                let join_columns = foreign_key.columns.iter()
+                    .zip(&foreign_key.referenced_columns)
+                    .map(|(dist_column, src_column)| (*joined_table, dist_column.clone(), src_column.clone()))
+                    .collect::<Vec<_>>();
+                candidates.push((foreign_key.constraint_name.clone(), join_columns));
+            }
+        }
+
+        let join_columns = match candidates.len() {
+            0 => return Err(GeneratorError::InconsistentConfigError(
+                format!("no foreign key relates '{}' to the joined tables. \
+                Please read the foreign keys via Introspector::get_foreign_keys first.", table.get_plain_table_name()))),
+            1 => candidates.remove(0).1,
+            _ => return Err(GeneratorError::InconsistentConfigError(
+                format!("'{}' is related to the joined tables by {} foreign keys ({}). Please join it by hand.",
+                        table.get_plain_table_name(),
+                        candidates.len(),
+                        candidates.iter().map(|(name, _)| name.as_str()).collect::<Vec<&str>>().join(", ")))),
+        };
+
+        let mut join_table = JoinTable::new(table, query_columns, join_type);
+        for (index, (dist_table, dist_column, src_column)) in join_columns.into_iter().enumerate() {
+            let bind_method = if index == 0 { BindMethod::FirstCondition } else { BindMethod::And };
+            join_table.add_join_column(JoinColumn::from_names(dist_table, dist_column.as_str(), table, src_column.as_str(), bind_method));
+        }
+        self.add_join_table(join_table)
+    }
+
     pub fn add_condition(&mut self, condition: Condition<'a>, bind_method: BindMethod) -> Result<(), GeneratorError> {
         let table_name = condition.get_table_name();
 
@@ -71,6 +214,25 @@ impl<'a> QueryGenerator<'a> {
         Ok(())
     }
 
+    /// Adds the conditions of the set in order.
+    ///
+    /// If the generator already has conditions, the first condition of the set is bound with `AND`.
+    /// No condition is added if a condition refers to a table which is neither the main table nor a joined table.
+    pub fn add_condition_set(&mut self, condition_set: &'a ConditionSet<'a>) -> Result<(), GeneratorError> {
+        let conditions = condition_set.get_conditions();
+        for (condition, _) in &conditions {
+            self.table_validation(condition.get_table_name().as_str())?;
+        }
+        for (condition, bind_method) in conditions {
+            let bind_method = match bind_method {
+                BindMethod::FirstCondition if self.conditions.len() != 0 => BindMethod::And,
+                _ => bind_method,
+            };
+            self.add_condition(condition, bind_method)?;
+        }
+        Ok(())
+    }
+
     pub fn add_grouping(&mut self, grouping_column: &'a Column<'a>) -> Result<(), GeneratorError> {
         let table_name = grouping_column.get_table_name();
 
@@ -104,6 +266,17 @@ impl<'a> QueryGenerator<'a> {
         Ok(())
     }
 
+    /// Includes the soft-deleted records of the main table (see `soft_delete::register_soft_delete`).
+    pub fn include_deleted(&mut self) {
+        self.include_deleted = true;
+    }
+
+    /// Takes the snapshot of the generator as `OwnedQuery`, which has no lifetime
+    /// so it can be returned, cached and sent across the await points.
+    pub fn to_owned_query(&self) -> OwnedQuery {
+        OwnedQuery::from_generator(self)
+    }
+
     pub(crate) fn get_query_columns(&self) -> String {
         let mut  query_columns = vec![self.main_query_columns.get_query_columns_statement()];
         if self.join_tables.len() != 0 {
@@ -117,6 +290,18 @@ impl<'a> QueryGenerator<'a> {
         self.placeholder_start_num = placeholder_start_num
     }
 
+    /// Returns the `IS NULL` predicate of the soft-delete column if the main table is a soft-delete table.
+    fn get_soft_delete_predicate(&self) -> Option<String> {
+        if self.include_deleted {
+            return None
+        }
+        if let Table::SubQueryAsTable(_) = self.base_table {
+            return None
+        }
+        get_soft_delete_column(self.base_table.get_plain_table_name().as_str())
+            .map(|column| format!("{}.{} IS NULL", self.base_table.get_table_name(), quote_identifier(column.as_str())))
+    }
+
     fn table_validation(&self, table_name: &str) -> Result<(), GeneratorError> {
         if !self.include_tables.contains(table_name) {
             return Err(
@@ -149,10 +334,18 @@ impl MainGenerator for QueryGenerator<'_> {
         if self.join_tables.len() != 0 {
             base_vec.push(join_tables);
         }
+        let soft_delete_predicate = self.get_soft_delete_predicate();
         if self.conditions.len() != 0 {
-            base_vec.push(self.conditions.get_total_statement(parameter_counter));
+            let where_statement = self.conditions.get_total_statement(parameter_counter);
+            match &soft_delete_predicate {
+                Some(predicate) => base_vec.push(add_where_predicate(where_statement.as_str(), predicate)),
+                None => base_vec.push(where_statement),
+            }
             parameter_counter += self.conditions.len() as u16;
         }
+        else if let Some(predicate) = &soft_delete_predicate {
+            base_vec.push(add_where_predicate("", predicate));
+        }
         if self.groupings.len() != 0 {
             base_vec.push(self.groupings.get_grouping_statement());
         }
@@ -179,4 +372,35 @@ impl MainGenerator for QueryGenerator<'_> {
     fn get_all_parameters_num(&self) -> u16 {
         self.placeholder_start_num
     }
+
+    fn get_referenced_tables(&self) -> Vec<Table<'_>> {
+        let mut tables = self.base_table.get_referenced_tables();
+        tables.extend(self.join_tables.get_referenced_tables());
+        tables.extend(self.conditions.get_referenced_tables());
+        tables.extend(self.group_conditions.get_referenced_tables());
+        tables
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::base::{BindMethod, ConditionOperator, MainGenerator, SortMethod, SortRule};
+    use crate::generator::base::condition::Condition;
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::generator::query::QueryGenerator;
+    use crate::{Table, Variable};
+
+    #[test]
+    fn test_chained_generator() {
+        let users = Table::create_table(None, "users");
+        let teams = Table::create_table(None, "teams");
+        let (age, name, team_name) = (users.get_column("age"), users.get_column("name"), teams.get_column("name"));
+
+        let generator = QueryGenerator::new(&users, QueryColumns::create_all_columns(&users))
+            .filter(Condition::new(&age, Variable::Int(20).into(), ConditionOperator::GreaterEq), BindMethod::FirstCondition).unwrap()
+            .order_by(SortRule::new(&name, SortMethod::Asc)).unwrap();
+        assert_eq!(generator.get_statement(), "SELECT users.* FROM users WHERE  users.age >= $1 ORDER BY users.name ASC");
+
+        assert!(generator.order_by(SortRule::new(&team_name, SortMethod::Asc)).is_err());
+    }
 }
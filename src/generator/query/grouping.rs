@@ -1,6 +1,7 @@
 use crate::generator::base::{Aggregation, ConditionOperator, GeneratorPlaceholder, GeneratorPlaceholderWrapper, MainGenerator, Parameters, ReferenceValue};
-use crate::Column;
+use crate::{Column, Table};
 
+#[derive(Clone)]
 pub(crate) struct Groupings<'a> {
     groupings: Vec<&'a Column<'a>>,
 }
@@ -31,6 +32,7 @@ impl <'a> Groupings <'a> {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct GroupConditions<'a> {
     group_conditions: Vec<GroupCondition<'a>>,
 }
@@ -45,6 +47,12 @@ impl <'a> GroupConditions<'a> {
     pub(crate) fn add_group_condition(&mut self, group_condition: GroupCondition<'a>) {
         self.group_conditions.push(group_condition);
     }
+
+    pub(crate) fn get_referenced_tables(&self) -> Vec<Table<'_>> {
+        self.group_conditions.iter()
+            .flat_map(|group_condition| group_condition.ref_value.get_referenced_tables())
+            .collect()
+    }
 }
 
 impl GeneratorPlaceholderWrapper for GroupConditions<'_> {
@@ -76,6 +84,7 @@ impl GeneratorPlaceholderWrapper for GroupConditions<'_> {
     }
 }
 
+#[derive(Clone)]
 pub struct GroupCondition<'a> {
     aggregation: &'a Aggregation<'a>,
     ref_value: ReferenceValue<'a>,
@@ -97,7 +106,7 @@ impl <'a> GroupCondition<'a> {
 impl GeneratorPlaceholder for GroupCondition<'_> {
     fn get_statement(&self, start_placeholder_number: u16) -> String {
         match &self.ref_value {
-            ReferenceValue::Variable(_) => format!("{} {} ${}", self.aggregation, self.condition_operator, start_placeholder_number),
+            ReferenceValue::Variable(_) => self.condition_operator.get_placeholder_statement(self.aggregation, start_placeholder_number),
             ReferenceValue::SubQueryAggregation(query) => query.get_statement()
         }
     }
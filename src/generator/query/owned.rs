@@ -0,0 +1,114 @@
+use crate::generator::base::{MainGenerator, Parameters};
+use crate::{Table, Variable};
+
+/// A query which owns its statement, parameters and referenced tables, so it has no lifetime.
+///
+/// `QueryGenerator` borrows its tables and columns, so it can't be returned from the function building it
+/// or stored. `QueryGenerator::to_owned_query` takes the snapshot of the generator as `OwnedQuery`,
+/// which can be returned, cached and sent across the await points, and runs through the executors as it is.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::query::owned::OwnedQuery;
+/// use safety_postgres::generator::query::query_column::QueryColumns;
+/// use safety_postgres::generator::query::QueryGenerator;
+/// use safety_postgres::Table;
+///
+/// fn build_queries(table_names: &[String]) -> Vec<OwnedQuery> {
+///     table_names.iter()
+///         .map(|table_name| {
+///             let table = Table::create_table(Some("public"), table_name.as_str());
+///             QueryGenerator::new(&table, QueryColumns::create_all_columns(&table)).to_owned_query()
+///         })
+///         .collect()
+/// }
+///
+/// let queries = build_queries(&["users".to_string(), "teams".to_string()]);
+/// assert_eq!(queries.len(), 2);
+/// ```
+#[derive(Clone)]
+pub struct OwnedQuery {
+    statement: String,
+    params: Vec<Variable>,
+    parameters_num: u16,
+    tables: Vec<(Option<String>, String)>,
+}
+
+impl OwnedQuery {
+    /// Creates the query from the statement, its parameters and the schema and table names it reads.
+    pub(crate) fn new(statement: String, params: Vec<Variable>, parameters_num: u16, tables: Vec<(Option<String>, String)>) -> Self {
+        Self {
+            statement,
+            params,
+            parameters_num,
+            tables,
+        }
+    }
+
+    /// Takes the snapshot of the generator.
+    pub(crate) fn from_generator<G: MainGenerator>(generator: &G) -> Self {
+        let tables = generator.get_referenced_tables()
+            .iter()
+            .filter_map(|table| match table {
+                Table::WithSchema { schema_name, table_name } => Some((Some(schema_name.to_string()), table_name.to_string())),
+                Table::NonSchema { table_name } => Some((None, table_name.to_string())),
+                Table::SubQueryAsTable(_) => None,
+            })
+            .collect();
+
+        Self::new(
+            generator.get_statement(),
+            generator.get_params().get_variables().to_vec(),
+            generator.get_all_parameters_num(),
+            tables)
+    }
+}
+
+impl MainGenerator for OwnedQuery {
+    fn get_statement(&self) -> String {
+        self.statement.clone()
+    }
+
+    fn get_params(&self) -> Parameters {
+        Parameters::from(self.params.clone())
+    }
+
+    fn get_all_parameters_num(&self) -> u16 {
+        self.parameters_num
+    }
+
+    fn get_referenced_tables(&self) -> Vec<Table<'_>> {
+        self.tables.iter()
+            .map(|(schema_name, table_name)| Table::create_table(schema_name.as_deref(), table_name.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::base::{BindMethod, ConditionOperator, MainGenerator};
+    use crate::generator::base::condition::Condition;
+    use crate::generator::query::owned::OwnedQuery;
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::generator::query::QueryGenerator;
+    use crate::{Table, Variable};
+
+    fn build_query(table_name: String) -> OwnedQuery {
+        let table = Table::create_table(Some("public"), table_name.as_str());
+        let id = table.get_column("id");
+        let mut generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+        generator.add_condition(
+            Condition::new(&id, Variable::Int(3).into(), ConditionOperator::Equal), BindMethod::FirstCondition).unwrap();
+        generator.to_owned_query()
+    }
+
+    #[test]
+    fn test_owned_query() {
+        let query = build_query("users".to_string());
+
+        assert_eq!(query.get_statement(), "SELECT public.users.* FROM public.users WHERE  public.users.id = $1");
+        assert_eq!(query.get_params().get_display_values(), vec!["3".to_string()]);
+        let tables = query.get_referenced_tables().iter().map(|table| table.get_plain_table_name()).collect::<Vec<String>>();
+        assert_eq!(tables, vec!["public.users".to_string()]);
+    }
+}
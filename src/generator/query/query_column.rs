@@ -2,6 +2,7 @@ use crate::generator::base::{Aggregation, Parameters};
 use crate::utils::errors::GeneratorError;
 use crate::{Column, Table};
 
+#[derive(Clone)]
 pub enum QueryColumns<'a> {
     AllColumns(&'a Table<'a>),
     SpecifyColumns(Vec<QueryColumn<'a>>)
@@ -24,6 +25,15 @@ impl <'a> QueryColumns<'a> {
         Ok(())
     }
 
+    /// Adds a column owned by the `QueryColumns`, e.g. one created by `Table::get_column` in place.
+    pub fn add_column(&mut self, column: Column<'a>) -> Result<(), GeneratorError> {
+        self.validate_self()?;
+        if let QueryColumns::SpecifyColumns(vec) = self {
+            vec.push(QueryColumn::Owned(column));
+        }
+        Ok(())
+    }
+
     pub fn add_aggregation_column(&mut self, aggregation_column: &'a Aggregation<'a>) -> Result<(), GeneratorError> {
         self.validate_self()?;
         if let QueryColumns::SpecifyColumns(vec) = self {
@@ -56,8 +66,10 @@ impl <'a> QueryColumns<'a> {
     }
 }
 
+#[derive(Clone)]
 pub enum QueryColumn<'a> {
     AsIs(&'a Column<'a>),
+    Owned(Column<'a>),
     Aggregation(&'a Aggregation<'a>),
 }
 
@@ -65,6 +77,7 @@ impl QueryColumn<'_> {
     fn get_statement(&self) -> String {
         match self {
             Self::AsIs(column) => format!("{}", column),
+            Self::Owned(column) => format!("{}", column),
             Self::Aggregation(column) => format!("{}", column),
         }
     }
@@ -0,0 +1,212 @@
+use crate::generator::base::{MainGenerator, Parameters};
+use crate::utils::errors::GeneratorError;
+use crate::utils::identifier::validate_identifier;
+use crate::{Table, Variable};
+
+/// A raw statement built by the `sql!` macro, for the rare query the generators can't express.
+///
+/// The statement isn't validated except for its placeholders, and the tables it reads are unknown
+/// until they are declared by `with_tables`, so an executor with a `TablePolicy` refuses it without the declaration.
+/// Prefer the generators wherever they can express the query.
+#[derive(Clone)]
+pub struct RawQuery {
+    statement: &'static str,
+    params: Vec<Variable>,
+    tables: Option<Vec<(Option<String>, String)>>,
+}
+
+impl RawQuery {
+    /// Creates the query without checking the placeholders. Use the `sql!` macro instead, which checks them at compile time.
+    #[doc(hidden)]
+    pub fn __new(statement: &'static str, params: Vec<Variable>) -> Self {
+        Self {
+            statement,
+            params,
+            tables: None,
+        }
+    }
+
+    /// Declares the tables (`"table"` or `"schema.table"`) the statement reads, which are checked by the `TablePolicy`
+    /// of the executors. The declaration isn't verified against the statement, so list every table it touches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError::InvalidTableNameError` if a schema or table name isn't accepted by the `IdentifierPolicy`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use safety_postgres::sql;
+    ///
+    /// let query = sql!("SELECT u.id FROM app.users u JOIN app.teams t ON t.id = u.team_id WHERE t.name = $1", "core")
+    ///     .with_tables(&["app.users", "app.teams"])
+    ///     .expect("invalid table name");
+    /// ```
+    pub fn with_tables(mut self, table_names: &[&str]) -> Result<Self, GeneratorError> {
+        let mut tables = Vec::with_capacity(table_names.len());
+        for table_name in table_names {
+            let (schema_name, name) = match table_name.split_once('.') {
+                Some((schema_name, name)) => (Some(schema_name), name),
+                None => (None, *table_name),
+            };
+            if !validate_identifier(name) || !schema_name.is_none_or(validate_identifier) {
+                return Err(GeneratorError::InvalidTableNameError(
+                    format!("'{}' is not a valid table name.", table_name)))
+            }
+            tables.push((schema_name.map(str::to_string), name.to_string()));
+        }
+        self.tables = Some(tables);
+        Ok(self)
+    }
+}
+
+impl MainGenerator for RawQuery {
+    fn get_statement(&self) -> String {
+        self.statement.to_string()
+    }
+
+    fn get_params(&self) -> Parameters {
+        Parameters::from(self.params.clone())
+    }
+
+    fn get_all_parameters_num(&self) -> u16 {
+        self.params.len() as u16
+    }
+
+    fn get_referenced_tables(&self) -> Vec<Table<'_>> {
+        self.tables.iter()
+            .flatten()
+            .map(|(schema_name, table_name)| Table::create_table(schema_name.as_deref(), table_name.as_str()))
+            .collect()
+    }
+
+    fn has_known_tables(&self) -> bool {
+        self.tables.is_some()
+    }
+}
+
+/// Returns whether the placeholders of `statement` are exactly `$1` to `$count`, each used at least once.
+///
+/// The `$` in the string literals of the statement are counted too, so bind such values as parameters.
+#[doc(hidden)]
+pub const fn __check_placeholders(statement: &str, count: usize) -> bool {
+    let bytes = statement.as_bytes();
+    let mut max_index = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let (index, next) = read_placeholder(bytes, i);
+        if next > i + 1 {
+            if index == 0 {
+                return false
+            }
+            if index > max_index {
+                max_index = index;
+            }
+        }
+        i = next;
+    }
+    if max_index != count {
+        return false
+    }
+
+    let mut expected = 1;
+    while expected <= count {
+        if !uses_placeholder(bytes, expected) {
+            return false
+        }
+        expected += 1;
+    }
+    true
+}
+
+/// Reads the placeholder starting at `start` and returns its index and the position after it.
+/// The position is `start + 1` if there is no placeholder at `start`.
+const fn read_placeholder(bytes: &[u8], start: usize) -> (usize, usize) {
+    if bytes[start] != b'$' {
+        return (0, start + 1)
+    }
+    let mut index = 0;
+    let mut i = start + 1;
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        index = index * 10 + (bytes[i] - b'0') as usize;
+        i += 1;
+    }
+    if i == start + 1 {
+        return (0, start + 1)
+    }
+    (index, i)
+}
+
+const fn uses_placeholder(bytes: &[u8], expected: usize) -> bool {
+    let mut i = 0;
+    while i < bytes.len() {
+        let (index, next) = read_placeholder(bytes, i);
+        if next > i + 1 && index == expected {
+            return true
+        }
+        i = next;
+    }
+    false
+}
+
+/// Builds a `RawQuery` from a statement literal with `$n` placeholders and the values bound to them.
+///
+/// The values are converted into `Variable` by `From`. The statement has to use exactly the placeholders
+/// `$1` to `$n` for `n` values, which is checked at compile time.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::base::MainGenerator;
+/// use safety_postgres::{sql, Variable};
+///
+/// let query = sql!("SELECT id FROM users WHERE created_at > now() - $1::interval AND status = $2", "7 days", Variable::from("active"));
+///
+/// assert_eq!(query.get_params().len(), 2);
+/// ```
+///
+#[cfg_attr(feature = "raw-sql", doc = r#"
+A statement whose placeholders don't match the values doesn't compile:
+```rust,compile_fail
+use safety_postgres::sql;
+
+let query = sql!("SELECT id FROM users WHERE id = $2", 1);
+```"#)]
+#[macro_export]
+macro_rules! sql {
+    (@unit $value:expr) => { () };
+    ($statement:literal $(, $value:expr)* $(,)?) => {{
+        const _: () = assert!(
+            $crate::generator::raw::__check_placeholders($statement, <[()]>::len(&[$($crate::sql!(@unit $value)),*])),
+            "the placeholders of the statement should be $1 to $n for n values");
+        $crate::generator::raw::RawQuery::__new($statement, vec![$($crate::Variable::from($value)),*])
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::executor::policy::TablePolicy;
+    use super::__check_placeholders;
+
+    #[test]
+    fn test_check_placeholders() {
+        assert!(__check_placeholders("SELECT 1", 0));
+        assert!(__check_placeholders("SELECT * FROM users WHERE id = $1 OR parent_id = $1 AND age > $2", 2));
+        assert!(__check_placeholders("SELECT $10, $1, $2, $3, $4, $5, $6, $7, $8, $9", 10));
+
+        assert!(!__check_placeholders("SELECT * FROM users WHERE id = $2", 1));
+        assert!(!__check_placeholders("SELECT * FROM users WHERE id = $1", 2));
+        assert!(!__check_placeholders("SELECT * FROM users WHERE id = $0", 0));
+        assert!(!__check_placeholders("SELECT $1, $3", 3));
+    }
+
+    #[test]
+    fn test_table_policy() {
+        let policy = TablePolicy::new().allow_schema("app");
+        assert!(policy.check(&crate::sql!("SELECT * FROM app.users WHERE id = $1", 1)).is_err());
+
+        let query = crate::sql!("SELECT * FROM app.users WHERE id = $1", 1).with_tables(&["app.users"]).unwrap();
+        assert!(policy.check(&query).is_ok());
+        let query = crate::sql!("SELECT * FROM audit.log").with_tables(&["audit.log"]).unwrap();
+        assert!(policy.check(&query).is_err());
+        assert!(crate::sql!("SELECT 1").with_tables(&["app.users; DROP"]).is_err());
+    }
+}
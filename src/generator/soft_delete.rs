@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+use crate::utils::errors::GeneratorError;
+use crate::utils::identifier::validate_identifier;
+
+fn registry() -> &'static RwLock<HashMap<String, String>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, String>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers `table_name` as a soft-delete table marked as deleted by a timestamp `column`.
+///
+/// For a registered table:
+/// - `PostgresBase::delete` sets `column = now()` instead of deleting the records.
+/// - `QueryGenerator` and the `PostgresBase` queries only return the records whose `column` is NULL,
+///   unless the deleted records are included explicitly (`QueryGenerator::include_deleted` or
+///   `PostgresBase::set_include_deleted`).
+///
+/// `table_name` should be the same as the table is referenced, i.e. `"schema.table"` for a table with a schema.
+/// Registering the same table twice replaces the column.
+///
+/// # Errors
+///
+/// Returns `GeneratorError::InvalidInputError` if `column` isn't accepted by the `IdentifierPolicy`.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::generator::soft_delete::register_soft_delete;
+///
+/// register_soft_delete("users", "deleted_at").expect("invalid column name");
+/// ```
+pub fn register_soft_delete(table_name: &str, column: &str) -> Result<(), GeneratorError> {
+    if !validate_identifier(column) {
+        return Err(GeneratorError::InvalidInputError(
+            format!("'{}' is not a valid soft-delete column name.", column)))
+    }
+    registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(table_name.to_string(), column.to_string());
+    Ok(())
+}
+
+/// Removes `table_name` from the soft-delete tables, so its records are deleted physically again.
+pub fn unregister_soft_delete(table_name: &str) {
+    registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(table_name);
+}
+
+pub(crate) fn get_soft_delete_column(table_name: &str) -> Option<String> {
+    registry()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .cloned()
+}
+
+/// Adds `predicate` to a `WHERE ...` statement (or an empty one) with `AND`,
+/// wrapping the existing conditions in parentheses so that an `OR` doesn't bypass the predicate.
+pub(crate) fn add_where_predicate(where_statement: &str, predicate: &str) -> String {
+    match where_statement.strip_prefix("WHERE") {
+        Some(conditions) if !conditions.trim().is_empty() => format!("WHERE ({}) AND {}", conditions.trim(), predicate),
+        _ => format!("WHERE {}", predicate),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::base::MainGenerator;
+    use crate::generator::query::query_column::QueryColumns;
+    use crate::generator::query::QueryGenerator;
+    use crate::Table;
+    use super::{add_where_predicate, get_soft_delete_column, register_soft_delete, unregister_soft_delete};
+
+    #[test]
+    fn test_soft_delete_registry() {
+        register_soft_delete("soft_delete_test.users", "deleted_at").unwrap();
+        assert_eq!(get_soft_delete_column("soft_delete_test.users"), Some("deleted_at".to_string()));
+        assert!(register_soft_delete("soft_delete_test.users", "deleted_at; --").is_err());
+
+        unregister_soft_delete("soft_delete_test.users");
+        assert_eq!(get_soft_delete_column("soft_delete_test.users"), None);
+
+        assert_eq!(add_where_predicate("", "users.deleted_at IS NULL"), "WHERE users.deleted_at IS NULL");
+        assert_eq!(
+            add_where_predicate("WHERE  id = $1 OR id = $2", "users.deleted_at IS NULL"),
+            "WHERE (id = $1 OR id = $2) AND users.deleted_at IS NULL");
+    }
+
+    #[test]
+    fn test_soft_delete_query_generator() {
+        register_soft_delete("soft_delete_test.records", "removed_at").unwrap();
+        let table = Table::create_table(Some("soft_delete_test"), "records");
+
+        let mut generator = QueryGenerator::new(&table, QueryColumns::create_all_columns(&table));
+        assert_eq!(
+            generator.get_statement(),
+            "SELECT soft_delete_test.records.* FROM soft_delete_test.records WHERE soft_delete_test.records.removed_at IS NULL");
+
+        generator.include_deleted();
+        assert_eq!(generator.get_statement(), "SELECT soft_delete_test.records.* FROM soft_delete_test.records");
+
+        unregister_soft_delete("soft_delete_test.records");
+    }
+}
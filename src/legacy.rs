@@ -15,5 +15,8 @@ pub mod sql_base;
 /// Module `validators` provides functions for validating input data utilities.
 mod validators;
 pub mod json_parser;
+/// Module `csv_parser` serializes the query result into CSV.
+mod csv_parser;
 mod converter;
-mod format;
+/// Module `format` lets the application register the date and time formats accepted by the string parameters.
+pub mod format;
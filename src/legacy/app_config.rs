@@ -1,4 +1,5 @@
-use crate::legacy::validators::validate_alphanumeric_name;
+use crate::legacy::validators::validate_connection_name;
+use crate::utils::logging::warn_event;
 
 /// Represents the configuration for the application.
 ///
@@ -16,7 +17,7 @@ impl AppConfig {
     ///
     /// This function retrieves the necessary configuration values from environment variables:
     /// - `DB_USER`: The database username.
-    /// - `DB_PASSWORD`: The database password.
+    /// - `DB_PASSWORD`: The database password. If it's missing, the password is read from the file at `DB_PASSWORD_FILE`.
     /// - `DB_HOST`: The database hostname.
     /// - `DB_PORT`: The database port number.
     /// - `DB_NAME`: The database name.
@@ -35,7 +36,11 @@ impl AppConfig {
         };
         let db_password = match std::env::var("DB_PASSWORD") {
             Ok(password) => password,
-            Err(_) => return Err("'password' isn't presented by environment variable. Please check your environment.".to_string()),
+            Err(_) => match std::env::var("DB_PASSWORD_FILE").map(std::fs::read_to_string) {
+                Ok(Ok(password)) => password.trim_end_matches(['\r', '\n']).to_string(),
+                Ok(Err(e)) => return Err(format!("'password' can't be read from 'DB_PASSWORD_FILE' due to {}.", e)),
+                Err(_) => return Err("'password' isn't presented by environment variable. Please check your environment.".to_string()),
+            },
         };
         let db_hostname = match std::env::var("DB_HOST") {
             Ok(hostname) => hostname,
@@ -44,7 +49,7 @@ impl AppConfig {
         let db_port = match std::env::var("DB_PORT") {
             Ok(port_number_str) => {
                 port_number_str.parse::<u32>().unwrap_or_else(|e| {
-                    eprintln!("Port number parse error due to {}", e);
+                    warn_event!("Port number parse error due to {}", e);
                     5432
                 })
             },
@@ -52,8 +57,8 @@ impl AppConfig {
         };
         let db_name = match std::env::var("DB_NAME") {
             Ok(dbname) => {
-                if !validate_alphanumeric_name(&dbname, "_") {
-                    eprintln!("{} is invalid name. 'dbname' is filled as 'postgres' automatically.", dbname);
+                if !validate_connection_name(&dbname) {
+                    warn_event!("{} is invalid name. 'dbname' is filled as 'postgres' automatically.", dbname);
                     "postgres".to_string()
                 } else {
                     dbname
@@ -1,6 +1,9 @@
 use crate::legacy::conditions::IsInJoinedTable::Yes;
+use crate::legacy::converter::ParamValue;
 use crate::legacy::errors::{ConditionError, ConditionErrorGenerator};
 use crate::legacy::validators::validate_string;
+use crate::utils::identifier::quote_identifier;
+use crate::utils::logging::warn_event;
 
 /// Provides the available comparison operators for standardizing input for the `Conditions.add_condition()` method.
 ///
@@ -10,6 +13,12 @@ use crate::legacy::validators::validate_string;
 ///  - `Greater`: Represents the greater than condition, where the column and the value are chained by ">"
 ///  - `LowerEq`: Represents the less than or equal to condition, where the column and the value are chained by "<="
 ///  - `GreaterEq`: Represents the greater than or equal to condition, where the column and the value are chained by ">="
+///  - `Like`: Represents the pattern matching condition, where the column and the pattern are chained by "LIKE"
+///  - `ILike`: Represents the case-insensitive pattern matching condition, where the column and the pattern are chained by "ILIKE"
+///  - `NotLike`: Represents the negated pattern matching condition, where the column and the pattern are chained by "NOT LIKE"
+///
+/// The pattern of `Like`, `ILike` and `NotLike` is bound as a parameter, but `%` and `_` in it are still wildcards,
+/// so escape them with `\` when the pattern includes the user input (e.g. a search box).
 #[derive(Clone)]
 pub enum ComparisonOperator {
     Equal,
@@ -17,6 +26,25 @@ pub enum ComparisonOperator {
     Grater,
     LowerEq,
     GraterEq,
+    Like,
+    ILike,
+    NotLike,
+}
+
+impl ComparisonOperator {
+    /// Returns the SQL text of the operator.
+    pub(super) fn get_operator_text(&self) -> &'static str {
+        match self {
+            ComparisonOperator::Equal => "=",
+            ComparisonOperator::Lower => "<",
+            ComparisonOperator::LowerEq => "<=",
+            ComparisonOperator::Grater => ">",
+            ComparisonOperator::GraterEq => ">=",
+            ComparisonOperator::Like => "LIKE",
+            ComparisonOperator::ILike => "ILIKE",
+            ComparisonOperator::NotLike => "NOT LIKE",
+        }
+    }
 }
 
 /// Represents whether the column is from a joined table or not.
@@ -49,19 +77,39 @@ pub enum LogicalOperator {
     Or,
 }
 
+/// Represents the predicate applied to the column of a condition.
+///
+/// - `Comparison`: Compares the column with the values by the comparison operator.
+/// - `In`: Checks the column equals one of the values.
+/// - `IsNull` / `IsNotNull`: Checks the column is NULL (or not) without any value.
+#[derive(Clone)]
+enum Predicate {
+    Comparison(ComparisonOperator),
+    In,
+    IsNull,
+    IsNotNull,
+}
+
 /// Represents a condition to be used in an execution.
 ///
 /// # Fields
 /// - `is_joined_table_condition`: A flag indicating whether the condition belongs to a joined table or the main table.
 /// - `key`: The column name to apply the condition on.
-/// - `operator`: The comparison operator to use for the condition.
-/// - `value`: The value to compare against.
+/// - `predicate`: The predicate to use for the condition.
+/// - `values`: The values to compare against, each bound to one placeholder.
 #[derive(Clone)]
 struct Condition {
     is_joined_table_condition: IsInJoinedTable,
     key: String,
-    operator: ComparisonOperator,
-    value: String,
+    predicate: Predicate,
+    values: Vec<String>,
+}
+
+/// Represents an element of `Conditions`: a condition or a parenthesized group of conditions.
+#[derive(Clone)]
+enum ConditionItem {
+    Single(Condition),
+    Group(Conditions),
 }
 
 /// Represents a set of conditions to be used in an execution.
@@ -114,7 +162,7 @@ struct Condition {
 #[derive(Clone)]
 pub struct Conditions {
     logics: Vec<LogicalOperator>,
-    conditions: Vec<Condition>,
+    conditions: Vec<ConditionItem>,
 }
 
 impl Conditions {
@@ -139,6 +187,9 @@ impl Conditions {
     ///     * GreaterEqual: ">=", "greater_equal", "ge", "greater_eq"
     ///     * Lower: "<", "lower", "lt"
     ///     * LowerEqual: "<=", "lower_equal", "le", "lower_eq"
+    ///     * Like: "like", "~~"
+    ///     * ILike: "ilike", "~~*"
+    ///     * NotLike: "not_like", "!~~"
     /// * `condition_chain_operator` - The operator to use for chaining multiple conditions.
     ///   * Available operator:
     ///     * FirstCondition(there is no previous condition): "", "first", "none"
@@ -173,6 +224,9 @@ impl Conditions {
             ">=" | "greater_equal" | "ge" | "greater_eq" => ComparisonOperator::GraterEq,
             "<" | "lower" | "lt" => ComparisonOperator::Lower,
             "<=" | "lower_equal" | "le" | "lower_eq" => ComparisonOperator::LowerEq,
+            "like" | "~~" => ComparisonOperator::Like,
+            "ilike" | "~~*" => ComparisonOperator::ILike,
+            "not_like" | "!~~" => ComparisonOperator::NotLike,
             _ => return Err(ConditionError::InputInvalidError(format!("'comparison operator' can select symbol('=', '>', '<', '>=', '<=') or some specify string, but got {}", comparison_operator))),
         };
         let condition_chain_op = match condition_chain_operator {
@@ -223,21 +277,127 @@ impl Conditions {
     /// assert_eq!(conditions.get_condition_text(), "name = John OR age <= 40");
     /// ```
     pub fn add_condition(&mut self, column: &str, value: &str, comparison: ComparisonOperator, condition_chain: LogicalOperator, is_joined_table_condition: IsInJoinedTable) -> Result<&mut Self, ConditionError> {
-        validate_string(column, "column", &ConditionErrorGenerator)?;
+        self.push_condition(column, Predicate::Comparison(comparison), vec![value.to_string()], condition_chain, is_joined_table_condition)
+    }
 
-        let mut validated_condition_chain: LogicalOperator = condition_chain.clone();
-        if let LogicalOperator::FirstCondition = condition_chain  {
-            if !self.conditions.is_empty() {
-                return Err(ConditionError::InputInvalidError(
-                    "Already condition exists. 'FirstCondition' can be used only specifying the first condition.".to_string()));
-            }
+    /// Adds a condition checking the column equals one of the values, e.g. `id IN ($1, $2, $3)`.
+    ///
+    /// Each value is bound to its own placeholder, so it replaces a chain of the `Equal` conditions combined by `Or`.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column name to which the condition is applied.
+    /// * `values` - The values for comparison.
+    /// * `condition_chain` - The logical operator used to chain the conditions.
+    /// * `is_joined_table_condition` - Indicates whether the condition is for a joined table or not.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConditionError` if `values` is empty (`IN ()` is a syntax error) or the column is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use safety_postgres::legacy::conditions::{Conditions, IsInJoinedTable, LogicalOperator};
+    ///
+    /// let mut conditions = Conditions::new();
+    /// conditions
+    ///     .add_in_condition("status", &["open", "pending"], LogicalOperator::FirstCondition, IsInJoinedTable::No)
+    ///     .expect("add condition failed");
+    ///
+    /// assert_eq!(conditions.get_condition_text(), "status IN (open, pending)");
+    /// ```
+    pub fn add_in_condition(&mut self, column: &str, values: &[&str], condition_chain: LogicalOperator, is_joined_table_condition: IsInJoinedTable) -> Result<&mut Self, ConditionError> {
+        if values.is_empty() {
+            return Err(ConditionError::InputInvalidError("'values' of the IN condition must have at least one element.".to_string()));
         }
-        else {
-            if self.conditions.is_empty() {
-                eprintln!("The first condition should have 'FirstCondition' as 'condition_chain'. Automatically converted.");
-                validated_condition_chain = LogicalOperator::FirstCondition;
-            }
+        let values = values.iter().map(|value| value.to_string()).collect::<Vec<String>>();
+        self.push_condition(column, Predicate::In, values, condition_chain, is_joined_table_condition)
+    }
+
+    /// Adds a condition checking the column is NULL.
+    ///
+    /// The condition has no value, so no parameter placeholder is generated for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column name to which the condition is applied.
+    /// * `condition_chain` - The logical operator used to chain the conditions.
+    /// * `is_joined_table_condition` - Indicates whether the condition is for a joined table or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use safety_postgres::legacy::conditions::{Conditions, IsInJoinedTable, LogicalOperator};
+    ///
+    /// let mut conditions = Conditions::new();
+    /// conditions
+    ///     .add_condition_from_str("name", "John", "=", "first", IsInJoinedTable::No).expect("add condition failed")
+    ///     .add_is_null_condition("deleted_at", LogicalOperator::And, IsInJoinedTable::No).expect("add condition failed");
+    ///
+    /// assert_eq!(conditions.get_condition_text(), "name = John AND deleted_at IS NULL");
+    /// ```
+    pub fn add_is_null_condition(&mut self, column: &str, condition_chain: LogicalOperator, is_joined_table_condition: IsInJoinedTable) -> Result<&mut Self, ConditionError> {
+        self.push_condition(column, Predicate::IsNull, Vec::new(), condition_chain, is_joined_table_condition)
+    }
+
+    /// Adds a condition checking the column is not NULL.
+    ///
+    /// The condition has no value, so no parameter placeholder is generated for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column name to which the condition is applied.
+    /// * `condition_chain` - The logical operator used to chain the conditions.
+    /// * `is_joined_table_condition` - Indicates whether the condition is for a joined table or not.
+    pub fn add_is_not_null_condition(&mut self, column: &str, condition_chain: LogicalOperator, is_joined_table_condition: IsInJoinedTable) -> Result<&mut Self, ConditionError> {
+        self.push_condition(column, Predicate::IsNotNull, Vec::new(), condition_chain, is_joined_table_condition)
+    }
+
+    /// Adds a group of conditions, which is parenthesized in the statement so it's evaluated before the chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The conditions in the group.
+    /// * `condition_chain` - The logical operator used to chain the group to the previous condition.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConditionError` if `group` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use safety_postgres::legacy::conditions::{Conditions, IsInJoinedTable, LogicalOperator};
+    ///
+    /// let mut status = Conditions::new();
+    /// status
+    ///     .add_condition_from_str("status", "a", "=", "", IsInJoinedTable::No).expect("add condition failed")
+    ///     .add_condition_from_str("status", "b", "=", "or", IsInJoinedTable::No).expect("add condition failed");
+    ///
+    /// let mut conditions = Conditions::new();
+    /// conditions
+    ///     .add_condition_group(status, LogicalOperator::FirstCondition).expect("add condition group failed")
+    ///     .add_condition_from_str("user_id", "3", "=", "and", IsInJoinedTable::No).expect("add condition failed");
+    ///
+    /// assert_eq!(conditions.get_condition_text(), "(status = a OR status = b) AND user_id = 3");
+    /// ```
+    pub fn add_condition_group(&mut self, group: Conditions, condition_chain: LogicalOperator) -> Result<&mut Self, ConditionError> {
+        if group.is_empty() {
+            return Err(ConditionError::InputInvalidError("The condition group must have at least one condition.".to_string()));
         }
+        let validated_condition_chain = self.validate_condition_chain(condition_chain)?;
+
+        self.logics.push(validated_condition_chain);
+        self.conditions.push(ConditionItem::Group(group));
+
+        Ok(self)
+    }
+
+    /// Validates and pushes a condition of the predicate and its values.
+    fn push_condition(&mut self, column: &str, predicate: Predicate, values: Vec<String>, condition_chain: LogicalOperator, is_joined_table_condition: IsInJoinedTable) -> Result<&mut Self, ConditionError> {
+        validate_string(column, "column", &ConditionErrorGenerator)?;
+        let validated_condition_chain = self.validate_condition_chain(condition_chain)?;
 
         match &is_joined_table_condition {
             Yes {schema_name, table_name} => {
@@ -254,16 +414,34 @@ impl Conditions {
         let condition = Condition {
             is_joined_table_condition,
             key: column.to_string(),
-            operator: comparison,
-            value: value.to_string(),
+            predicate,
+            values,
         };
 
         self.logics.push(validated_condition_chain);
-        self.conditions.push(condition);
+        self.conditions.push(ConditionItem::Single(condition));
 
         Ok(self)
     }
 
+    /// Validates the chain operator of a new condition, converting the first chain operator to `FirstCondition`.
+    fn validate_condition_chain(&self, condition_chain: LogicalOperator) -> Result<LogicalOperator, ConditionError> {
+        let mut validated_condition_chain: LogicalOperator = condition_chain.clone();
+        if let LogicalOperator::FirstCondition = condition_chain  {
+            if !self.conditions.is_empty() {
+                return Err(ConditionError::InputInvalidError(
+                    "Already condition exists. 'FirstCondition' can be used only specifying the first condition.".to_string()));
+            }
+        }
+        else {
+            if self.conditions.is_empty() {
+                warn_event!("The first condition should have 'FirstCondition' as 'condition_chain'. Automatically converted.");
+                validated_condition_chain = LogicalOperator::FirstCondition;
+            }
+        }
+        Ok(validated_condition_chain)
+    }
+
     /// Checks if the conditions is empty.
     ///
     /// # Returns
@@ -298,23 +476,19 @@ impl Conditions {
     /// assert_eq!(conditions.get_condition_text(), "name = John OR age <= 40");
     /// ```
     pub(super) fn generate_statement_text(&self, start_index: usize) -> String {
-        let mut statement_texts: Vec<String> = Vec::new();
-
-        for (index, (condition, logic)) in self.conditions.iter().zip(&self.logics).enumerate() {
-            if statement_texts.is_empty() {
-                statement_texts.push("WHERE".to_string());
-            }
-            match logic {
-                LogicalOperator::FirstCondition => {},
-                LogicalOperator::And => statement_texts.push("AND".to_string()),
-                LogicalOperator::Or => statement_texts.push("OR".to_string()),
-            }
-            let condition_text = condition.generate_statement_text();
-            let statement_text = format!("{} ${}", condition_text, index + start_index + 1);
-            statement_texts.push(statement_text);
+        if self.conditions.is_empty() {
+            return String::new();
         }
-
-        statement_texts.join(" ")
+        let mut placeholder_index = start_index;
+        let condition_text = self.build_text(&mut |condition| {
+            let placeholders = (0..condition.values.len())
+                .map(|offset| format!("${}", placeholder_index + offset + 1))
+                .collect::<Vec<String>>();
+            placeholder_index += condition.values.len();
+            condition.generate_statement_text(&placeholders)
+        });
+
+        format!("WHERE {}", condition_text)
     }
 
     /// Returns the condition text generated by the conditions you set.
@@ -324,53 +498,66 @@ impl Conditions {
     ///
     /// The set condition as a `String`.
     pub fn get_condition_text(&self) -> String {
+        self.build_text(&mut |condition| condition.generate_statement_text(&condition.values))
+    }
+
+    /// Retrieves the values of the conditions as flatten vec.
+    pub(super) fn get_flat_values(&self) -> Vec<ParamValue> {
+        self.conditions
+            .iter()
+            .flat_map(|item| match item {
+                ConditionItem::Single(condition) => condition.values.iter().cloned().map(ParamValue::Raw).collect(),
+                ConditionItem::Group(group) => group.get_flat_values(),
+            })
+            .collect::<Vec<ParamValue>>()
+    }
+
+    /// Chains the conditions by the logical operators, rendering each condition by `render` in order
+    /// and parenthesizing the groups.
+    fn build_text(&self, render: &mut dyn FnMut(&Condition) -> String) -> String {
         let mut conditions_txt: Vec<String> = Vec::new();
 
-        for (condition, logic) in self.conditions.iter().zip(&self.logics) {
+        for (item, logic) in self.conditions.iter().zip(&self.logics) {
             match logic {
                 LogicalOperator::FirstCondition => {},
                 LogicalOperator::And => conditions_txt.push("AND".to_string()),
                 LogicalOperator::Or => conditions_txt.push("OR".to_string()),
             }
-            let condition_txt = format!("{} {}", condition.generate_statement_text(), condition.value);
-            conditions_txt.push(condition_txt);
+            match item {
+                ConditionItem::Single(condition) => conditions_txt.push(render(condition)),
+                ConditionItem::Group(group) => conditions_txt.push(format!("({})", group.build_text(render))),
+            }
         }
 
         conditions_txt.join(" ")
     }
-
-    /// Retrieves the values of the conditions as flatten vec.
-    pub(super) fn get_flat_values(&self) -> Vec<String> {
-        self.conditions.iter().map(|condition| condition.value.clone()).collect::<Vec<String>>()
-    }
 }
 
 impl Condition {
-    /// Generates one part of the condition by the set condition.
-    fn generate_statement_text(&self) -> String {
+    /// Generates one part of the condition by the set condition, placing `values` (the placeholders or the values) after the operator.
+    fn generate_statement_text(&self, values: &[String]) -> String {
         let table_name = match &self.is_joined_table_condition {
             Yes{ schema_name, table_name } => {
                 if schema_name.is_empty() & table_name.is_empty() {
-                    self.key.to_string()
+                    quote_identifier(&self.key)
                 }
                 else if schema_name.is_empty() {
-                    format!("{}.{}", table_name, self.key)
+                    format!("{}.{}", quote_identifier(table_name), quote_identifier(&self.key))
                 }
                 else {
-                    format!("{}.{}.{}", schema_name, table_name, self.key)
+                    format!("{}.{}.{}", quote_identifier(schema_name), quote_identifier(table_name), quote_identifier(&self.key))
                 }
             },
-            IsInJoinedTable::No => self.key.to_string(),
+            IsInJoinedTable::No => quote_identifier(&self.key),
         };
-        let operator = match self.operator {
-            ComparisonOperator::Equal => "=",
-            ComparisonOperator::Lower => "<",
-            ComparisonOperator::LowerEq => "<=",
-            ComparisonOperator::Grater => ">",
-            ComparisonOperator::GraterEq => ">="
+        let operator = match &self.predicate {
+            Predicate::Comparison(operator) => operator.get_operator_text(),
+            Predicate::In => return format!("{} IN ({})", table_name, values.join(", ")),
+            Predicate::IsNull => return format!("{} IS NULL", table_name),
+            Predicate::IsNotNull => return format!("{} IS NOT NULL", table_name),
         };
 
-        format!("{} {}", table_name, operator)
+        format!("{} {} {}", table_name, operator, values.join(", "))
     }
 }
 
@@ -448,6 +635,84 @@ mod tests {
         assert_eq!(conditions.get_flat_values(), expected_values);
     }
 
+    /// Tests that the null checks generate no placeholder and the following placeholders are numbered continuously.
+    #[test]
+    fn test_null_condition() {
+        let mut conditions = Conditions::new();
+        conditions
+            .add_is_null_condition("deleted_at", LogicalOperator::FirstCondition, IsInJoinedTable::No).unwrap()
+            .add_condition_from_str("name", "John", "=", "and", IsInJoinedTable::No).unwrap()
+            .add_is_not_null_condition("email", LogicalOperator::And, IsInJoinedTable::Yes {
+                schema_name: "".to_string(),
+                table_name: "profiles".to_string()
+            }).unwrap()
+            .add_condition_from_str("age", "20", ">", "and", IsInJoinedTable::No).unwrap();
+
+        assert_eq!(
+            conditions.generate_statement_text(0),
+            "WHERE deleted_at IS NULL AND name = $1 AND profiles.email IS NOT NULL AND age > $2");
+        assert_eq!(
+            conditions.get_condition_text(),
+            "deleted_at IS NULL AND name = John AND profiles.email IS NOT NULL AND age > 20");
+        assert_eq!(conditions.get_flat_values(), vec!["John", "20"]);
+    }
+
+    /// Tests that the IN condition expands to a placeholder per value.
+    #[test]
+    fn test_in_condition() {
+        let mut conditions = Conditions::new();
+        conditions
+            .add_condition_from_str("age", "20", ">", "", IsInJoinedTable::No).unwrap()
+            .add_in_condition("status", &["open", "pending", "closed"], LogicalOperator::And, IsInJoinedTable::No).unwrap()
+            .add_condition_from_str("name", "John", "=", "or", IsInJoinedTable::No).unwrap();
+
+        assert_eq!(
+            conditions.generate_statement_text(0),
+            "WHERE age > $1 AND status IN ($2, $3, $4) OR name = $5");
+        assert_eq!(conditions.get_flat_values(), vec!["20", "open", "pending", "closed", "John"]);
+
+        let Err(e) = conditions.add_in_condition("status", &[], LogicalOperator::And, IsInJoinedTable::No) else { panic!() };
+        assert_eq!(e, ConditionError::InputInvalidError("'values' of the IN condition must have at least one element.".to_string()));
+    }
+
+    /// Tests that the pattern matching operators bind the pattern as a parameter.
+    #[test]
+    fn test_like_condition() {
+        let mut conditions = Conditions::new();
+        conditions
+            .add_condition_from_str("name", "Jo%", "ilike", "", IsInJoinedTable::No).unwrap()
+            .add_condition_from_str("email", "%@example.com", "!~~", "and", IsInJoinedTable::No).unwrap()
+            .add_condition("code", "A_1", ComparisonOperator::Like, LogicalOperator::Or, IsInJoinedTable::No).unwrap();
+
+        assert_eq!(
+            conditions.generate_statement_text(0),
+            "WHERE name ILIKE $1 AND email NOT LIKE $2 OR code LIKE $3");
+        assert_eq!(conditions.get_flat_values(), vec!["Jo%", "%@example.com", "A_1"]);
+    }
+
+    /// Tests that a group is parenthesized and its placeholders are numbered in the statement order.
+    #[test]
+    fn test_condition_group() {
+        let mut status = Conditions::new();
+        status
+            .add_condition_from_str("status", "a", "=", "", IsInJoinedTable::No).unwrap()
+            .add_in_condition("status", &["b", "c"], LogicalOperator::Or, IsInJoinedTable::No).unwrap();
+
+        let mut conditions = Conditions::new();
+        conditions
+            .add_condition_from_str("age", "20", ">", "", IsInJoinedTable::No).unwrap()
+            .add_condition_group(status, LogicalOperator::And).unwrap()
+            .add_condition_from_str("user_id", "3", "=", "and", IsInJoinedTable::No).unwrap();
+
+        assert_eq!(
+            conditions.generate_statement_text(0),
+            "WHERE age > $1 AND (status = $2 OR status IN ($3, $4)) AND user_id = $5");
+        assert_eq!(conditions.get_flat_values(), vec!["20", "a", "b", "c", "3"]);
+
+        let Err(e) = conditions.add_condition_group(Conditions::new(), LogicalOperator::Or) else { panic!() };
+        assert_eq!(e, ConditionError::InputInvalidError("The condition group must have at least one condition.".to_string()));
+    }
+
     /// Tests providing invalid string as comparison operator results in an appropriate error.
     #[test]
     fn test_invalid_comparison_str_input() {
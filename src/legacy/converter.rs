@@ -1,10 +1,21 @@
 use std::str::FromStr;
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use cidr::{IpCidr, IpInet};
+use eui48::MacAddress;
 use itertools::Itertools;
 use rust_decimal::Decimal;
 use serde_json::{json, Value};
 use tokio_postgres::Row;
+use std::error::Error;
+use std::fmt::{self, Debug, Formatter};
+use tokio_postgres::types::{IsNull, ToSql, Type};
+use bytes::BytesMut;
 use crate::legacy::errors::DataParseError;
+use crate::types::interval::Interval;
+use crate::types::pg_enum::EnumLabel;
+use crate::types::range::Range;
+use crate::converter::row_converter::variable_to_json;
+use crate::Variable;
 use crate::legacy::format::{ambiguous_datetime_formats, support_date_formats, support_datetime_formats, support_time_formats, timezone_datetime_formats, unsupported_date_formats, unsupported_datetime_formats, unsupported_time_formats};
 
 const UNSUPPORTED_DATA_TYPE: [&str; 7] = ["f16", "isize", "fsize", "u16", "u32", "u64", "usize"];
@@ -24,8 +35,10 @@ const UNSUPPORTED_DATA_TYPE: [&str; 7] = ["f16", "isize", "fsize", "u16", "u32",
 /// - `Decimal(Decimal)`: A parameter of type `Decimal`.
 /// - `Date(NaiveDate)`: A parameter of type `NaiveDate`.
 /// - `DateTime(NaiveDateTime)`: A parameter of type `NaiveDateTime`.
+/// - `DateTimeTz(DateTime<FixedOffset>)`: A parameter of type `DateTime<FixedOffset>`.
 /// - `Time(NaiveTime)`: A parameter of type `NaiveTime`.
 /// - `Bool(bool)`: A parameter of type `bool`.
+/// - `Variable(Variable)`: A parameter typed by the caller, bound without any inference.
 pub(super) enum Param {
     Text(String),
     SmallInt(i16),
@@ -36,8 +49,108 @@ pub(super) enum Param {
     Decimal(Decimal),
     Date(NaiveDate),
     DateTime(NaiveDateTime),
+    DateTimeTz(DateTime<FixedOffset>),
     Time(NaiveTime),
     Bool(bool),
+    Variable(Variable),
+}
+
+/// Represents a value bound to a placeholder of the legacy statements.
+///
+/// - `Raw(String)`: A string whose type is inferred by the `InferenceMode`.
+/// - `Typed(Variable)`: A value typed by the caller, bound as it is.
+#[derive(Clone, Debug)]
+pub(super) enum ParamValue {
+    Raw(String),
+    Typed(Variable),
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::Raw(value) => write!(f, "{}", value),
+            ParamValue::Typed(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<String> for ParamValue {
+    fn from(value: String) -> Self {
+        ParamValue::Raw(value)
+    }
+}
+
+/// A raw value equals the same string. A typed value never equals a string.
+impl PartialEq<String> for ParamValue {
+    fn eq(&self, other: &String) -> bool {
+        matches!(self, ParamValue::Raw(value) if value == other)
+    }
+}
+
+impl PartialEq<&str> for ParamValue {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, ParamValue::Raw(value) if value == other)
+    }
+}
+
+impl Param {
+    /// Converts the parameter into the `Variable` bound by the new executors.
+    pub(super) fn into_variable(self) -> Variable {
+        match self {
+            Param::Text(text) => Variable::Text(text),
+            Param::SmallInt(smallint) => Variable::SmallInt(smallint),
+            Param::Int(int) => Variable::Int(int),
+            Param::BigInt(bigint) => Variable::BigInt(bigint),
+            Param::Float(float) => Variable::Float(float),
+            Param::Double(double) => Variable::Double(double),
+            Param::Decimal(decimal) => Variable::Decimal(decimal),
+            Param::Date(date) => Variable::Date(date),
+            Param::DateTime(datetime) => Variable::DateTime(datetime),
+            Param::DateTimeTz(datetime) => Variable::DateTimeTz(datetime),
+            Param::Time(time) => Variable::Time(time),
+            Param::Bool(bool) => Variable::Bool(bool),
+            Param::Variable(variable) => variable,
+        }
+    }
+
+    fn as_to_sql(&self) -> &(dyn ToSql + Sync) {
+        match self {
+            Param::Text(text) => text,
+            Param::SmallInt(smallint) => smallint,
+            Param::Int(int) => int,
+            Param::BigInt(bigint) => bigint,
+            Param::Float(float) => float,
+            Param::Double(double) => double,
+            Param::Decimal(decimal) => decimal,
+            Param::Date(date) => date,
+            Param::DateTime(datetime) => datetime,
+            Param::DateTimeTz(datetime) => datetime,
+            Param::Time(time) => time,
+            Param::Bool(bool) => bool,
+            Param::Variable(variable) => variable,
+        }
+    }
+}
+
+impl Debug for Param {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_to_sql(), f)
+    }
+}
+
+impl ToSql for Param {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_to_sql().to_sql_checked(ty, out)
+    }
+
+    /// The type is checked by the held value in `to_sql_checked`.
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    fn to_sql_checked(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_to_sql().to_sql_checked(ty, out)
+    }
 }
 
 enum ParsedData<T> {
@@ -52,22 +165,22 @@ fn parse_data<T: FromStr>(data: &str) -> ParsedData<T> {
     }
 }
 
-fn parse_datetime_with_zones(data: &str) -> bool {
-    if let Ok(_) = DateTime::parse_from_rfc3339(data) {
-        return true
+fn parse_datetime_with_zones(data: &str) -> Option<DateTime<FixedOffset>> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(data) {
+        return Some(datetime)
     }
-    else if let Ok(_) = DateTime::parse_from_rfc2822(data) {
-        return true
+    else if let Ok(datetime) = DateTime::parse_from_rfc2822(data) {
+        return Some(datetime)
     }
-    else if let Ok(_) = DateTime::parse_from_str(data, "%Y-%m-%d %H:%M:%S %z") {
-        return true
+    else if let Ok(datetime) = DateTime::parse_from_str(data, "%Y-%m-%d %H:%M:%S %z") {
+        return Some(datetime)
     }
 
-    false
+    None
 }
 
 fn parse_naive_date(data: &str) -> Result<NaiveDate, DataParseError> {
-    for support_format in support_date_formats() {
+    for support_format in support_date_formats().iter() {
         if let Ok(date) = NaiveDate::parse_from_str(data, support_format.as_str()) {
             let date_chars: Vec<char> = date.to_string().chars().collect();
             if date_chars[..2] == ['0', '0'] {
@@ -76,7 +189,7 @@ fn parse_naive_date(data: &str) -> Result<NaiveDate, DataParseError> {
             return Ok(date)
         }
     }
-    for unsupported_format in unsupported_date_formats() {
+    for unsupported_format in unsupported_date_formats().iter() {
         if let Ok(unsupported_date) = NaiveDate::parse_from_str(data, unsupported_format.as_str()) {
             return Err(DataParseError::ParseUnsupportedError(
                 format!(
@@ -90,13 +203,13 @@ fn parse_naive_date(data: &str) -> Result<NaiveDate, DataParseError> {
 }
 
 fn parse_naive_time(data: &str) -> Result<NaiveTime, DataParseError> {
-    for support_time_format in support_time_formats() {
+    for support_time_format in support_time_formats().iter() {
         if let Ok(time) = NaiveTime::parse_from_str(data, support_time_format.as_str()) {
             return Ok(time)
         }
     }
 
-    for unsupported_time_format in unsupported_time_formats() {
+    for unsupported_time_format in unsupported_time_formats().iter() {
         if let Ok(_) = NaiveTime::parse_from_str(data, unsupported_time_format.as_str()) {
             return Err(DataParseError::ParseUnsupportedError(
                 format!(
@@ -112,7 +225,7 @@ fn parse_naive_time(data: &str) -> Result<NaiveTime, DataParseError> {
 }
 
 fn parse_naive_datetime(data: &str) -> Result<NaiveDateTime, DataParseError> {
-    for support_format in support_datetime_formats() {
+    for support_format in support_datetime_formats().iter() {
         if let Ok(date) = NaiveDateTime::parse_from_str(data, support_format.as_str()) {
             let date_chars: Vec<char> = date.to_string().chars().collect();
             if date_chars[..2] == ['0', '0'] {
@@ -122,7 +235,7 @@ fn parse_naive_datetime(data: &str) -> Result<NaiveDateTime, DataParseError> {
         }
     }
 
-    for ambiguous_and_timezone_format in unsupported_datetime_formats() {
+    for ambiguous_and_timezone_format in unsupported_datetime_formats().iter() {
         if let Ok(datetime) = NaiveDateTime::parse_from_str(data, ambiguous_and_timezone_format.as_str()) {
             return Err(DataParseError::ParseUnsupportedError(
                 format!(
@@ -133,7 +246,7 @@ fn parse_naive_datetime(data: &str) -> Result<NaiveDateTime, DataParseError> {
         }
     }
 
-    for ambiguous_datetime_format in ambiguous_datetime_formats() {
+    for ambiguous_datetime_format in ambiguous_datetime_formats().iter() {
         if let Ok(datetime) = NaiveDateTime::parse_from_str(data, ambiguous_datetime_format.as_str()) {
             return Err(DataParseError::ParseUnsupportedError(
                 format!(
@@ -145,7 +258,7 @@ fn parse_naive_datetime(data: &str) -> Result<NaiveDateTime, DataParseError> {
         }
     }
 
-    for time_with_timezone_format in timezone_datetime_formats() {
+    for time_with_timezone_format in timezone_datetime_formats().iter() {
         if let Ok(_) = NaiveDateTime::parse_from_str(data, time_with_timezone_format.as_str()) {
             return Err(DataParseError::ParseUnsupportedError(
                 format!(
@@ -208,8 +321,8 @@ pub(super) fn str_to_param(data: &str) -> Result<Param, DataParseError> {
             return Err(DataParseError::ParseIntError(
                 format!("'{}' can not convert to i32(integer) because overflow the range.", invalid_int)))
         }
-        else if parse_datetime_with_zones(data) {
-            return Err(DataParseError::ParseDateTimeError("DateTime with timezone is unsupported. Please use non timezone datetime instead.".to_string()))
+        else if let Some(datetime) = parse_datetime_with_zones(data) {
+            return Ok(Param::DateTimeTz(datetime))
         }
         else if UNSUPPORTED_DATA_TYPE.iter().any(|data_type| data.ends_with(data_type)) {
             let data_chars: Vec<char> = data.chars().collect();
@@ -252,38 +365,106 @@ pub(super) fn str_to_param(data: &str) -> Result<Param, DataParseError> {
     Ok(param)
 }
 
-pub(super) fn row_to_value(row: &Row, column: &String) -> Value {
-    if let Ok(int) = row.try_get::<&str, i32>(column.as_str()) {
+/// Parses a string parameter as the type declared by the prepared statement, without any inference.
+///
+/// Used in the strict inference mode, so that e.g. `"00123"` bound to a `text` column stays text
+/// and `"1e5"` is only accepted as a float for a floating point column.
+pub(super) fn str_to_typed_param(data: &str, ty: &Type) -> Result<Param, DataParseError> {
+    let param = match *ty {
+        Type::INT2 => Param::SmallInt(data.parse::<i16>().map_err(|e| DataParseError::ParseIntError(
+            format!("'{}' can not convert to i16(smallint) due to {}", data, e)))?),
+        Type::INT4 => Param::Int(data.parse::<i32>().map_err(|e| DataParseError::ParseIntError(
+            format!("'{}' can not convert to i32(integer) due to {}", data, e)))?),
+        Type::INT8 => Param::BigInt(data.parse::<i64>().map_err(|e| DataParseError::ParseIntError(
+            format!("'{}' can not convert to i64(bigint) due to {}", data, e)))?),
+        Type::FLOAT4 => Param::Float(data.parse::<f32>().map_err(|e| DataParseError::ParseFloatError(
+            format!("'{}' can not convert to f32(real) due to {}", data, e)))?),
+        Type::FLOAT8 => Param::Double(data.parse::<f64>().map_err(|e| DataParseError::ParseFloatError(
+            format!("'{}' can not convert to f64(double precision) due to {}", data, e)))?),
+        Type::NUMERIC => Param::Decimal(Decimal::from_str(data).map_err(|e| DataParseError::ParseNumericError(
+            format!("'{}' can not convert to numeric due to {}", data, e)))?),
+        Type::BOOL => Param::Bool(data.parse::<bool>().map_err(|e| DataParseError::ParseUnsupportedError(
+            format!("'{}' can not convert to bool due to {}", data, e)))?),
+        Type::DATE => Param::Date(parse_naive_date(data).map_err(|e| typed_datetime_error(e, data, ty))?),
+        Type::TIME => Param::Time(parse_naive_time(data).map_err(|e| typed_datetime_error(e, data, ty))?),
+        Type::TIMESTAMP => Param::DateTime(parse_naive_datetime(data).map_err(|e| typed_datetime_error(e, data, ty))?),
+        Type::TIMESTAMPTZ => match parse_datetime_with_zones(data) {
+            Some(datetime) => Param::DateTimeTz(datetime),
+            None => return Err(DataParseError::ParseDateTimeError(
+                format!("'{}' can not convert to timestamptz.", data))),
+        },
+        _ => Param::Text(data.to_string()),
+    };
+    Ok(param)
+}
+
+fn typed_datetime_error(error: DataParseError, data: &str, ty: &Type) -> DataParseError {
+    match error {
+        DataParseError::ParseDateTimeError(_) => DataParseError::ParseDateTimeError(
+            format!("'{}' can not convert to {}.", data, ty)),
+        _ => error,
+    }
+}
+
+pub(super) fn row_to_value(row: &Row, index: usize) -> Value {
+    if let Ok(Variable::Custom(value)) = row.try_get::<usize, Variable>(index) {
+        json!(value.to_string())
+    }
+    else if let Ok(int) = row.try_get::<usize, i32>(index) {
         json!(int)
     }
-    else if let Ok(bigint) = row.try_get::<&str, i64>(column.as_str()) {
+    else if let Ok(bigint) = row.try_get::<usize, i64>(index) {
         json!(bigint)
     }
-    else if let Ok(smallint) = row.try_get::<&str, i16>(column.as_str()) {
+    else if let Ok(smallint) = row.try_get::<usize, i16>(index) {
         json!(smallint)
     }
-    else if let Ok(decimal) = row.try_get::<&str, Decimal>(column.as_str()) {
+    else if let Ok(decimal) = row.try_get::<usize, Decimal>(index) {
         json!(decimal)
     }
-    else if let Ok(float) = row.try_get::<&str, f32>(column.as_str()) {
+    else if let Ok(float) = row.try_get::<usize, f32>(index) {
         json!(float)
     }
-    else if let Ok(double) = row.try_get::<&str, f64>(column.as_str()) {
+    else if let Ok(double) = row.try_get::<usize, f64>(index) {
         json!(double)
     }
-    else if let Ok(datetime) = row.try_get::<&str, NaiveDateTime>(column.as_str()) {
+    else if let Ok(datetime) = row.try_get::<usize, NaiveDateTime>(index) {
         json!(datetime.to_string())
     }
-    else if let Ok(date) = row.try_get::<&str, NaiveDate>(column.as_str()) {
+    else if let Ok(datetime) = row.try_get::<usize, DateTime<FixedOffset>>(index) {
+        json!(datetime.to_rfc3339())
+    }
+    else if let Ok(date) = row.try_get::<usize, NaiveDate>(index) {
         json!(date.to_string())
     }
-    else if let Ok(time) = row.try_get::<&str, NaiveTime>(column.as_str()) {
+    else if let Ok(time) = row.try_get::<usize, NaiveTime>(index) {
         json!(time.to_string())
     }
-    else if let Ok(bool) = row.try_get::<&str, bool>(column.as_str()) {
+    else if let Ok(bool) = row.try_get::<usize, bool>(index) {
         json!(bool)
     }
+    else if let Ok(interval) = row.try_get::<usize, Interval>(index) {
+        json!(interval.to_string())
+    }
+    else if let Ok(inet) = row.try_get::<usize, IpInet>(index) {
+        json!(inet.to_string())
+    }
+    else if let Ok(cidr) = row.try_get::<usize, IpCidr>(index) {
+        json!(cidr.to_string())
+    }
+    else if let Ok(mac) = row.try_get::<usize, MacAddress>(index) {
+        json!(mac.to_hex_string())
+    }
+    else if let Ok(label) = row.try_get::<usize, EnumLabel>(index) {
+        json!(label.get_label())
+    }
+    else if let Ok(range) = row.try_get::<usize, Range>(index) {
+        json!(range.to_string())
+    }
+    else if let Ok(array) = row.try_get::<usize, Vec<Variable>>(index) {
+        Value::Array(array.iter().map(variable_to_json).collect())
+    }
     else {
-        json!(row.get::<&str, String>(column.as_str()))
+        json!(row.get::<usize, String>(index))
     }
 }
@@ -0,0 +1,59 @@
+use serde_json::Value;
+use tokio_postgres::Row;
+use crate::legacy::converter::row_to_value;
+
+/// Serializes the rows into CSV with a header row of the column names.
+///
+/// The values are converted in the same way as the JSON output, and NULL becomes an empty field.
+/// The lines end with CRLF as RFC 4180.
+pub(super) fn rows_to_csv(query_result: &[Row]) -> String {
+    let Some(first_row) = query_result.first() else {
+        return String::new()
+    };
+    let header = first_row.columns().iter().map(|column| column.name().to_string()).collect::<Vec<String>>();
+    let mut csv = csv_line(&header);
+    for row in query_result {
+        let fields = (0..row.len())
+            .map(|index| value_to_field(&row_to_value(row, index)))
+            .collect::<Vec<String>>();
+        csv += &csv_line(&fields);
+    }
+    csv
+}
+
+fn value_to_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(string) => string.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Joins the fields into a line, quoting the fields which include a comma, a quote or a line break.
+fn csv_line(fields: &[String]) -> String {
+    let fields = fields.iter().map(|field| {
+        if field.contains([',', '"', '\r', '\n']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }).collect::<Vec<String>>();
+    format!("{}\r\n", fields.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::{csv_line, value_to_field};
+
+    #[test]
+    fn test_csv_line() {
+        let fields = vec!["1".to_string(), "Smith, John".to_string(), "say \"hi\"".to_string(), "".to_string()];
+        assert_eq!(csv_line(&fields), "1,\"Smith, John\",\"say \"\"hi\"\"\",\r\n");
+
+        assert_eq!(value_to_field(&json!(null)), "");
+        assert_eq!(value_to_field(&json!("text")), "text");
+        assert_eq!(value_to_field(&json!(1.5)), "1.5");
+        assert_eq!(value_to_field(&json!({"a": 1})), "{\"a\":1}");
+    }
+}
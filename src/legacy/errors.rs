@@ -1,6 +1,8 @@
 use std::fmt;
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
+use tokio_postgres::Error as PGError;
+use crate::utils::errors::{DatabaseError, StatementSnapshot};
 
 
 /// A trait for generating custom error values.
@@ -159,6 +161,64 @@ pub enum PostgresBaseError {
     SQLExecutionError(String),
     TokioPostgresError(String),
     SerializeError(String),
+    WriteError(String),
+    StaleRowError(String),
+    DatabaseError(DatabaseError),
+    ConnectionClosedError(String),
+}
+
+impl PostgresBaseError {
+    /// Returns the error reported by the PostgreSQL server, if this is one.
+    pub fn get_database_error(&self) -> Option<&DatabaseError> {
+        match self {
+            Self::DatabaseError(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the execution failed due to a unique constraint.
+    pub fn is_unique_violation(&self) -> bool {
+        self.get_database_error().is_some_and(DatabaseError::is_unique_violation)
+    }
+
+    /// Returns whether the execution failed due to a foreign key constraint.
+    pub fn is_foreign_key_violation(&self) -> bool {
+        self.get_database_error().is_some_and(DatabaseError::is_foreign_key_violation)
+    }
+
+    /// Returns the name of the violated constraint, if any.
+    pub fn constraint_name(&self) -> Option<&str> {
+        self.get_database_error().and_then(DatabaseError::constraint_name)
+    }
+
+    /// Returns whether the same execution may succeed when it's retried (see `DatabaseError::is_retryable`).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::ConnectionClosedError(_) => true,
+            Self::DatabaseError(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Attaches the snapshot of the failed statement to the server error or the execution error message.
+    pub(crate) fn with_statement_snapshot(self, snapshot: StatementSnapshot) -> Self {
+        match self {
+            Self::DatabaseError(e) => Self::DatabaseError(e.with_statement_snapshot(snapshot)),
+            Self::SQLExecutionError(e) => Self::SQLExecutionError(format!("{} while executing {}", e, snapshot)),
+            Self::ConnectionClosedError(e) => Self::ConnectionClosedError(format!("{} while executing {}", e, snapshot)),
+            other => other,
+        }
+    }
+}
+
+impl From<PGError> for PostgresBaseError {
+    fn from(value: PGError) -> Self {
+        match DatabaseError::from_error(value) {
+            Ok(e) => Self::DatabaseError(e),
+            Err(e) if e.is_closed() => Self::ConnectionClosedError(e.to_string()),
+            Err(e) => Self::SQLExecutionError(e.to_string()),
+        }
+    }
 }
 
 impl fmt::Display for PostgresBaseError {
@@ -172,11 +232,22 @@ impl fmt::Display for PostgresBaseError {
             Self::SQLExecutionError(e) => write!(f, "SQL execution failed due to {}", e),
             Self::TokioPostgresError(e) => write!(f, "Get error from tokio-postgres crate: {}", e),
             Self::SerializeError(e) => write!(f, "Serialize process failed due to {}", e),
+            Self::WriteError(e) => write!(f, "Writing the output failed due to {}", e),
+            Self::StaleRowError(e) => write!(f, "The record was modified or deleted by another execution: {}", e),
+            Self::DatabaseError(e) => write!(f, "SQL execution failed due to {}", e),
+            Self::ConnectionClosedError(e) => write!(f, "Connection was closed due to {}", e),
         }
     }
 }
 
-impl Error for PostgresBaseError {}
+impl Error for PostgresBaseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::DatabaseError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, PartialEq)]
 pub enum DataParseError {
@@ -1,4 +1,7 @@
+use std::sync::{Arc, LazyLock, RwLock};
+use chrono::format::{Item, StrftimeItems};
 use itertools::Itertools;
+use crate::legacy::errors::DataParseError;
 
 const DATE_SEPARATORS: [&str; 2] = ["-", "/"];
 const DATE_FORMATS: [[&str; 3]; 6] = [
@@ -27,6 +30,136 @@ const TIMEZONE_SPEC: [&str; 5] = [
     "%Z", "%z", "%:z", "%::z", "%:::z"
 ];
 
+/// Formats registered by the application in addition to (or instead of) the built-in formats.
+///
+/// `cache` holds the format lists generated from the current registry and is cleared on every update.
+struct FormatRegistry {
+    date_formats: Vec<String>,
+    time_formats: Vec<String>,
+    datetime_formats: Vec<String>,
+    disabled_formats: Vec<String>,
+    cache: Option<Arc<FormatLists>>,
+}
+
+/// All format lists used by the converter, generated once per registry state.
+struct FormatLists {
+    support_date: Arc<Vec<String>>,
+    unsupported_date: Arc<Vec<String>>,
+    support_time: Arc<Vec<String>>,
+    unsupported_time: Arc<Vec<String>>,
+    support_datetime: Arc<Vec<String>>,
+    timezone_datetime: Arc<Vec<String>>,
+    ambiguous_datetime: Arc<Vec<String>>,
+    unsupported_datetime: Arc<Vec<String>>,
+}
+
+static FORMAT_REGISTRY: RwLock<FormatRegistry> = RwLock::new(FormatRegistry {
+    date_formats: Vec::new(),
+    time_formats: Vec::new(),
+    datetime_formats: Vec::new(),
+    disabled_formats: Vec::new(),
+    cache: None,
+});
+
+static BUILT_IN_DATE_FORMATS: LazyLock<Vec<String>> =
+    LazyLock::new(|| date_format_generator(&DATE_FORMATS, &SPECIAL_DATE_FORMATS, &DATE_SEPARATORS));
+static BUILT_IN_UNSUPPORTED_DATE_FORMATS: LazyLock<Vec<String>> =
+    LazyLock::new(|| date_format_generator(&UNSUPPORTED_DATE_FORMATS, &[], &DATE_SEPARATORS));
+
+fn update_registry<F: FnOnce(&mut FormatRegistry)>(update: F) {
+    let mut registry = FORMAT_REGISTRY.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    update(&mut registry);
+    registry.cache = None;
+}
+
+fn validate_format(format: &str) -> Result<(), DataParseError> {
+    if format.is_empty() || StrftimeItems::new(format).any(|item| item == Item::Error) {
+        return Err(DataParseError::ParseUnsupportedError(
+            format!("'{}' is not a valid strftime format.", format)))
+    }
+    Ok(())
+}
+
+/// Registers an additional date format (strftime syntax, e.g. `"%d.%m.%Y"`) accepted by the string parameters.
+///
+/// Registered formats are tried before the built-in formats and are also combined with the time formats
+/// to accept datetime strings.
+///
+/// # Errors
+///
+/// Returns `DataParseError::ParseUnsupportedError` if the format is not a valid strftime format.
+pub fn register_date_format(format: &str) -> Result<(), DataParseError> {
+    validate_format(format)?;
+    update_registry(|registry| {
+        registry.disabled_formats.retain(|disabled| disabled != format);
+        registry.date_formats.push(format.to_string());
+    });
+    Ok(())
+}
+
+/// Registers an additional time format (strftime syntax, e.g. `"%Hh%M"`) accepted by the string parameters.
+///
+/// # Errors
+///
+/// Returns `DataParseError::ParseUnsupportedError` if the format is not a valid strftime format.
+pub fn register_time_format(format: &str) -> Result<(), DataParseError> {
+    validate_format(format)?;
+    update_registry(|registry| {
+        registry.disabled_formats.retain(|disabled| disabled != format);
+        registry.time_formats.push(format.to_string());
+    });
+    Ok(())
+}
+
+/// Registers an additional datetime format which is not a combination of a date and a time format.
+///
+/// # Errors
+///
+/// Returns `DataParseError::ParseUnsupportedError` if the format is not a valid strftime format.
+pub fn register_datetime_format(format: &str) -> Result<(), DataParseError> {
+    validate_format(format)?;
+    update_registry(|registry| {
+        registry.disabled_formats.retain(|disabled| disabled != format);
+        registry.datetime_formats.push(format.to_string());
+    });
+    Ok(())
+}
+
+/// Stops accepting a date or time format, built-in or registered.
+///
+/// For example, the built-in formats accept both `"%m/%d/%Y"` and `"%d/%m/%Y"`, so `"01/02/2023"` is
+/// read as January 2nd. Disabling `"%m/%d/%Y"` makes the same string read as February 1st.
+/// Datetime formats built from a disabled date or time format are disabled as well.
+pub fn disable_format(format: &str) {
+    update_registry(|registry| {
+        registry.date_formats.retain(|registered| registered != format);
+        registry.time_formats.retain(|registered| registered != format);
+        registry.datetime_formats.retain(|registered| registered != format);
+        registry.disabled_formats.push(format.to_string());
+    });
+}
+
+/// Removes all registered and disabled formats, so that only the built-in formats are accepted.
+pub fn reset_formats() {
+    update_registry(|registry| {
+        registry.date_formats.clear();
+        registry.time_formats.clear();
+        registry.datetime_formats.clear();
+        registry.disabled_formats.clear();
+    });
+}
+
+/// Puts the registered formats in front of the built-in formats and removes the disabled ones.
+fn apply_registry(registered: &[String], built_in_formats: Vec<String>, disabled: &[String]) -> Vec<String> {
+    registered
+        .iter()
+        .cloned()
+        .chain(built_in_formats)
+        .filter(|format| !disabled.contains(format))
+        .unique()
+        .collect()
+}
+
 fn date_format_generator(formats: &[[&str; 3]], special_formats: &[&str], separators: &[&str]) -> Vec<String> {
     let mut generated_formats = Vec::new();
     for format_list in formats {
@@ -41,63 +174,119 @@ fn date_format_generator(formats: &[[&str; 3]], special_formats: &[&str], separa
     generated_formats
 }
 
-pub(super) fn support_date_formats() -> Vec<String> {
-    date_format_generator(&DATE_FORMATS, &SPECIAL_DATE_FORMATS, &DATE_SEPARATORS)
+fn combine_date_and_time(date_formats: &[String], time_formats: &[String]) -> Vec<String> {
+    date_formats
+        .iter()
+        .cartesian_product(time_formats.iter())
+        .flat_map(|(date, time)| vec![format!("{} {}", date, time), format!("{}T{}", date, time)])
+        .collect()
 }
 
-pub(super) fn unsupported_date_formats() -> Vec<String> {
-    date_format_generator(&UNSUPPORTED_DATE_FORMATS, &vec![], &DATE_SEPARATORS)
-}
+fn generate_format_lists(registry: &FormatRegistry) -> FormatLists {
+    let disabled = &registry.disabled_formats;
+    let support_date = apply_registry(&registry.date_formats, BUILT_IN_DATE_FORMATS.clone(), disabled);
+    let unsupported_date = BUILT_IN_UNSUPPORTED_DATE_FORMATS.clone();
+    let support_time = apply_registry(
+        &registry.time_formats,
+        TIME_FORMATS.iter().map(|format| format.to_string()).collect(),
+        disabled);
 
-pub(super) fn support_time_formats() -> Vec<String> {
-    TIME_FORMATS.iter().map(|format| format.to_string()).collect()
-}
+    let mut unsupported_time = Vec::new();
+    for time_format in &support_time {
+        for timezone_format in TIMEZONE_SPEC {
+            unsupported_time.push(format!("{}{}", time_format, timezone_format));
+            unsupported_time.push(format!("{} {}", time_format, timezone_format));
+        }
+    }
 
-pub(super) fn unsupported_time_formats() -> Vec<String> {
-    let mut unsupported_formats = Vec::new();
+    let support_datetime = apply_registry(
+        &registry.datetime_formats,
+        combine_date_and_time(&support_date, &support_time),
+        disabled);
+    let timezone_datetime = combine_date_and_time(&support_date, &unsupported_time);
+    let ambiguous_datetime = combine_date_and_time(&unsupported_date, &support_time);
+    let unsupported_datetime = combine_date_and_time(&unsupported_date, &unsupported_time);
 
-    for time_format in support_time_formats() {
-        for timezone_format in TIMEZONE_SPEC {
-            unsupported_formats.push(format!("{}{}", time_format, timezone_format));
-            unsupported_formats.push(format!("{} {}", time_format, timezone_format));
+    FormatLists {
+        support_date: Arc::new(support_date),
+        unsupported_date: Arc::new(unsupported_date),
+        support_time: Arc::new(support_time),
+        unsupported_time: Arc::new(unsupported_time),
+        support_datetime: Arc::new(support_datetime),
+        timezone_datetime: Arc::new(timezone_datetime),
+        ambiguous_datetime: Arc::new(ambiguous_datetime),
+        unsupported_datetime: Arc::new(unsupported_datetime),
+    }
+}
+
+/// Returns the cached format lists, generating them if the registry changed since the last call.
+fn format_lists() -> Arc<FormatLists> {
+    {
+        let registry = FORMAT_REGISTRY.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(cache) = &registry.cache {
+            return cache.clone()
         }
     }
-    unsupported_formats
+    let mut registry = FORMAT_REGISTRY.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(cache) = &registry.cache {
+        return cache.clone()
+    }
+    let lists = Arc::new(generate_format_lists(&registry));
+    registry.cache = Some(lists.clone());
+    lists
 }
 
-pub(super) fn support_datetime_formats() -> Vec<String> {
+pub(super) fn support_date_formats() -> Arc<Vec<String>> {
+    format_lists().support_date.clone()
+}
 
-    let date_formats = support_date_formats();
-    let time_formats = support_time_formats();
+pub(super) fn unsupported_date_formats() -> Arc<Vec<String>> {
+    format_lists().unsupported_date.clone()
+}
 
-    let datetime_formats = date_formats
-        .iter()
-        .cartesian_product(time_formats.iter())
-        .flat_map(|(date, time)| vec![format!("{} {}", date, time), format!("{}T{}", date, time)])
-        .collect();
-    datetime_formats
+pub(super) fn support_time_formats() -> Arc<Vec<String>> {
+    format_lists().support_time.clone()
 }
 
-pub(super) fn timezone_datetime_formats() -> Vec<String> {
-    support_date_formats()
-        .iter()
-        .cartesian_product(unsupported_time_formats())
-        .flat_map(|(date, time_with_zone)| vec![format!("{} {}", date, time_with_zone), format!("{}T{}", date, time_with_zone)])
-        .collect::<Vec<String>>()
+pub(super) fn unsupported_time_formats() -> Arc<Vec<String>> {
+    format_lists().unsupported_time.clone()
 }
 
-pub(super) fn ambiguous_datetime_formats() -> Vec<String> {
-    unsupported_date_formats()
-        .iter()
-        .cartesian_product(support_time_formats())
-        .flat_map(|(ambiguous_date, time)| vec![format!("{} {}", ambiguous_date, time), format!("{}T{}", ambiguous_date, time)])
-        .collect::<Vec<String>>()
+pub(super) fn support_datetime_formats() -> Arc<Vec<String>> {
+    format_lists().support_datetime.clone()
 }
 
-pub(super) fn unsupported_datetime_formats() -> Vec<String> {
-    unsupported_date_formats()
-        .iter()
-        .cartesian_product(unsupported_time_formats())
-        .flat_map(|(ambiguous_date, time_with_timezone)| vec![format!("{} {}", ambiguous_date, time_with_timezone), format!("{}T{}", ambiguous_date, time_with_timezone)])
-        .collect::<Vec<String>>()
+pub(super) fn timezone_datetime_formats() -> Arc<Vec<String>> {
+    format_lists().timezone_datetime.clone()
+}
+
+pub(super) fn ambiguous_datetime_formats() -> Arc<Vec<String>> {
+    format_lists().ambiguous_datetime.clone()
+}
+
+pub(super) fn unsupported_datetime_formats() -> Arc<Vec<String>> {
+    format_lists().unsupported_datetime.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The registry is global to the process, so the test uses the formats which no other test parses
+    /// and doesn't depend on the position of the other registered formats.
+    #[test]
+    fn test_format_registry() {
+        assert!(register_date_format("%Q").is_err());
+
+        register_date_format("%d~%m~%Y").unwrap();
+        let date_formats = support_date_formats();
+        let registered = date_formats.iter().position(|format| format == "%d~%m~%Y").unwrap();
+        let built_in = date_formats.iter().position(|format| format == "%Y-%m-%d").unwrap();
+        assert!(registered < built_in);
+        assert!(support_datetime_formats().contains(&"%d~%m~%Y %H:%M".to_string()));
+
+        disable_format("%d~%m~%Y");
+        assert!(!support_date_formats().contains(&"%d~%m~%Y".to_string()));
+        assert!(!support_datetime_formats().contains(&"%d~%m~%Y %H:%M".to_string()));
+    }
 }
@@ -1,18 +1,15 @@
-use std::str::FromStr;
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
-use tokio_postgres::types::ToSql;
-use crate::legacy::converter::{Param, str_to_param};
+use tokio_postgres::types::{ToSql, Type};
+use crate::legacy::converter::{Param, ParamValue, str_to_param, str_to_typed_param};
 use crate::legacy::errors::DataParseError;
 
-/// Generates boxed parameters from a vector of strings.
+/// Generates parameters from a vector of strings.
 ///
 /// The function takes a slice of strings `str_params` and attempts to parse each string into
 /// different types. If parsing is successful, the parsed value is stored in a `Param` enum variant
-/// and added to the `params` vector.
+/// and added to the returned vector. The typed values are bound as they are.
 ///
-/// After parsing all the strings, the function creates a new vector `box_param` and populates it
-/// by pushing boxed values of the parsed parameters. Each value is boxed as a trait object
-/// implementing the `ToSql` and `Sync` traits.
+/// `Param` implements `ToSql` by dispatching to the held value, so the parameters are bound by reference
+/// via `params_ref_generator` without boxing each value.
 ///
 /// # Arguments
 ///
@@ -20,54 +17,74 @@ use crate::legacy::errors::DataParseError;
 ///
 /// # Returns
 ///
-/// A vector of boxed trait objects (`Box<dyn ToSql + Sync>`) containing the parsed parameters.
-pub(super) fn box_param_generator(str_params: &[String]) -> Result<Vec<Box<dyn ToSql + Sync>>, DataParseError> {
-    let mut params: Vec<Param> = Vec::new();
-    for str_param in str_params {
-        params.push(str_to_param(str_param)?);
-    }
+/// A vector of `Param` containing the parsed parameters.
+pub(super) fn param_generator(str_params: &[ParamValue]) -> Result<Vec<Param>, DataParseError> {
+    str_params
+        .iter()
+        .map(|str_param| match str_param {
+            ParamValue::Raw(str_param) => str_to_param(str_param),
+            ParamValue::Typed(variable) => Ok(Param::Variable(variable.clone())),
+        })
+        .collect()
+}
 
-    let mut box_param: Vec<Box<dyn ToSql + Sync>> = Vec::new();
-
-    for param in params {
-        match param {
-            Param::Int(int) => box_param.push(Box::new(int) as Box<dyn ToSql + Sync>),
-            Param::SmallInt(smallint) => box_param.push(Box::new(smallint) as Box<dyn  ToSql + Sync>),
-            Param::BigInt(bigint) => box_param.push(Box::new(bigint) as Box<dyn ToSql + Sync>),
-            Param::Float(float) => box_param.push(Box::new(float) as Box<dyn ToSql + Sync>),
-            Param::Double(double) => box_param.push(Box::new(double) as Box<dyn ToSql + Sync>),
-            Param::Decimal(decimal) => box_param.push(Box::new(decimal) as Box<dyn ToSql + Sync>),
-            Param::DateTime(datetime) => box_param.push(Box::new(datetime) as Box<dyn ToSql + Sync>),
-            Param::Date(date) => box_param.push(Box::new(date) as Box<dyn ToSql + Sync>),
-            Param::Time(time) => box_param.push(Box::new(time) as Box<dyn ToSql + Sync>),
-            Param::Bool(bool) => box_param.push(Box::new(bool) as Box<dyn ToSql + Sync>),
-            Param::Text(text) => box_param.push(Box::new(text) as Box<dyn ToSql + Sync>),
-        }
+/// Generates parameters from a vector of strings using the parameter types of the prepared statement.
+///
+/// Unlike `param_generator`, no type is inferred from the string itself, so each string has to be
+/// a valid value of the declared type. The typed values are bound as they are.
+///
+/// # Arguments
+///
+/// * `str_params` - A reference to a slice of strings to be parsed into parameters.
+/// * `types` - The parameter types declared by the prepared statement.
+///
+/// # Returns
+///
+/// A vector of `Param` containing the parsed parameters.
+pub(super) fn typed_param_generator(str_params: &[ParamValue], types: &[Type]) -> Result<Vec<Param>, DataParseError> {
+    if str_params.len() != types.len() {
+        return Err(DataParseError::ParseUnsupportedError(
+            format!("The statement expects {} parameter(s) but {} value(s) are input.", types.len(), str_params.len())))
     }
-    Ok(box_param)
+
+    str_params
+        .iter()
+        .zip(types)
+        .map(|(str_param, ty)| match str_param {
+            ParamValue::Raw(str_param) => str_to_typed_param(str_param, ty),
+            ParamValue::Typed(variable) => Ok(Param::Variable(variable.clone())),
+        })
+        .collect()
 }
 
-/// Generates a new reference to the parameters from a vector of boxed parameters.
+/// Generates references to the parameters to pass them to the executor.
 ///
 /// # Arguments
 ///
-/// * `box_params` - A reference to a vector of boxed parameters implementing `ToSql` and `Sync`.
+/// * `params` - A reference to a slice of parameters.
 ///
 /// # Returns
 ///
-/// A new vector containing references to the boxed parameters.
-pub(super) fn params_ref_generator<'a>(box_params: &'a[Box<dyn ToSql + Sync>]) -> Vec<&'a(dyn ToSql + Sync)> {
-    box_params.iter().map(AsRef::as_ref).collect()
+/// A new vector containing references to the parameters.
+pub(super) fn params_ref_generator(params: &[Param]) -> Vec<&(dyn ToSql + Sync)> {
+    params.iter().map(|param| param as &(dyn ToSql + Sync)).collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+    use crate::Variable;
     use super::*;
 
-    /// Tests the box_param_generator function with various input cases of different data types.
-    /// It checks if the correct boxed values are returned.
+    fn to_param_values(str_params: &[String]) -> Vec<ParamValue> {
+        str_params.iter().map(|str_param| ParamValue::Raw(str_param.clone())).collect()
+    }
+
+    /// Tests the param_generator function with various input cases of different data types.
+    /// It checks if the parameters hold the correct values.
     #[test]
-    fn test_box_param_generator() {
+    fn test_param_generator() {
         let str_params = vec![
             "42".to_string(),
             "hello".to_string(),
@@ -78,38 +95,87 @@ mod tests {
             "false".to_string()
         ];
 
-        let box_params = box_param_generator(&str_params).unwrap();
-        assert_eq!(box_params.len(), str_params.len());
+        let params = param_generator(&to_param_values(&str_params)).unwrap();
+        assert_eq!(params.len(), str_params.len());
         assert_eq!(
-            format!("{:?}", box_params[0]),
+            format!("{:?}", params[0]),
             format!("{:?}", Box::new(42) as Box<dyn ToSql + Sync>)
         );
         assert_eq!(
-            format!("{:?}", box_params[1]),
+            format!("{:?}", params[1]),
             format!("{:?}", Box::new("hello") as Box<dyn ToSql + Sync>)
         );
         assert_eq!(
-            format!("{:?}", box_params[2]),
+            format!("{:?}", params[2]),
             format!("{:?}", Box::new(3.14) as Box<dyn ToSql + Sync>)
         );
         assert_eq!(
-            format!("{:?}", box_params[3]),
+            format!("{:?}", params[3]),
             format!("{:?}", Box::new(NaiveDate::from_str("2023-11-29").unwrap()) as Box<dyn ToSql + Sync>)
         );
         assert_eq!(
-            format!("{:?}", box_params[4]),
+            format!("{:?}", params[4]),
             format!("{:?}", Box::new(NaiveDateTime::from_str("2023-11-29T21:00:09").unwrap()) as Box<dyn ToSql + Sync>)
         );
         assert_eq!(
-            format!("{:?}", box_params[5]),
+            format!("{:?}", params[5]),
             format!("{:?}", Box::new(NaiveTime::from_str("21:00:09").unwrap()) as Box<dyn ToSql + Sync>)
         );
         assert_eq!(
-            format!("{:?}", box_params[6]),
+            format!("{:?}", params[6]),
             format!("{:?}", Box::new(false) as Box<dyn ToSql + Sync>));
     }
 
-    /// Tests the params_ref_generator function by using the result of the box_param_generator as input.
+    /// Tests that datetime strings with a timezone offset are bound as `timestamptz` values.
+    #[test]
+    fn test_param_generator_with_timezone() {
+        let str_params = vec!["2023-11-29T21:00:09+09:00".to_string()];
+
+        let params = param_generator(&to_param_values(&str_params)).unwrap();
+        assert_eq!(
+            format!("{:?}", params[0]),
+            format!("{:?}", Box::new(DateTime::parse_from_rfc3339("2023-11-29T21:00:09+09:00").unwrap()) as Box<dyn ToSql + Sync>)
+        );
+    }
+
+    /// Tests that the strict generator binds the strings as the declared types without inference.
+    #[test]
+    fn test_typed_param_generator() {
+        let str_params = vec!["00123".to_string(), "1e5".to_string(), "1e5".to_string()];
+        let types = vec![Type::VARCHAR, Type::TEXT, Type::FLOAT8];
+
+        let params = typed_param_generator(&to_param_values(&str_params), &types).unwrap();
+        assert_eq!(
+            format!("{:?}", params[0]),
+            format!("{:?}", Box::new("00123") as Box<dyn ToSql + Sync>)
+        );
+        assert_eq!(
+            format!("{:?}", params[1]),
+            format!("{:?}", Box::new("1e5") as Box<dyn ToSql + Sync>)
+        );
+        assert_eq!(
+            format!("{:?}", params[2]),
+            format!("{:?}", Box::new(100000.0f64) as Box<dyn ToSql + Sync>)
+        );
+
+        assert!(typed_param_generator(&[ParamValue::Raw("abc".to_string())], &[Type::INT4]).is_err());
+        assert!(typed_param_generator(&[ParamValue::Raw("1".to_string())], &[]).is_err());
+    }
+
+    /// Tests that the typed values are bound without the inference in both modes.
+    #[test]
+    fn test_typed_value_params() {
+        let params = vec![ParamValue::Typed(Variable::Text("42".to_string())), ParamValue::Raw("42".to_string())];
+
+        let lenient = param_generator(&params).unwrap();
+        assert_eq!(format!("{:?}", lenient[0]), format!("{:?}", Variable::Text("42".to_string())));
+        assert_eq!(format!("{:?}", lenient[1]), format!("{:?}", Box::new(42) as Box<dyn ToSql + Sync>));
+
+        let strict = typed_param_generator(&params, &[Type::TEXT, Type::INT4]).unwrap();
+        assert_eq!(format!("{:?}", strict[0]), format!("{:?}", Variable::Text("42".to_string())));
+    }
+
+    /// Tests the params_ref_generator function by using the result of the param_generator as input.
     /// It checks if the correct references are returned.
     #[test]
     fn test_params_ref_generator() {
@@ -123,8 +189,8 @@ mod tests {
             "false".to_string()
         ];
 
-        let box_params = box_param_generator(&str_params).unwrap();
-        let params_ref = params_ref_generator(&box_params);
+        let params = param_generator(&to_param_values(&str_params)).unwrap();
+        let params_ref = params_ref_generator(&params);
         assert_eq!(params_ref.len(), str_params.len());
     }
 }
@@ -1,5 +1,7 @@
+use crate::legacy::conditions::ComparisonOperator;
 use crate::legacy::errors::{JoinTableError, JoinTableErrorGenerator};
-use crate::legacy::validators::{validate_alphanumeric_name, validate_string};
+use crate::legacy::validators::{validate_name, validate_string};
+use crate::utils::identifier::quote_identifier;
 
 /// Represents a join table in a database.
 #[derive(Clone)]
@@ -8,6 +10,31 @@ struct JoinTable {
     table_name: String,
     join_columns: Vec<String>,
     destination_columns: Vec<String>,
+    operators: Vec<ComparisonOperator>,
+    join_type: JoinType,
+}
+
+/// Represents the type of the join.
+///
+/// Use `Left` (or `Right`/`Full`) for an optional relation, because `Inner` drops the rows
+/// of the main table which have no matching row in the joined table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JoinType {
+    Inner,
+    Left,
+    Right,
+    Full,
+}
+
+impl JoinType {
+    fn get_join_text(&self) -> &'static str {
+        match self {
+            JoinType::Inner => "INNER JOIN",
+            JoinType::Left => "LEFT JOIN",
+            JoinType::Right => "RIGHT JOIN",
+            JoinType::Full => "FULL JOIN",
+        }
+    }
 }
 
 /// Represents a collection of join tables in a database.
@@ -69,9 +96,85 @@ impl JoinTables {
     /// assert_eq!(joined_text, "INNER JOIN public.users ON main_table_name.user_id = public.users.id");
     /// ```
     pub fn add_join_table(&mut self, schema: &str, table_name: &str, join_columns: &[&str], destination_columns: &[&str]) -> Result<&mut Self, JoinTableError> {
+        self.add_join_table_with_type(schema, table_name, join_columns, destination_columns, JoinType::Inner)
+    }
+
+    /// Adds a join table joined with the specified join type to the instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - The schema name for the new join table (input "" if there is no schema_name).
+    /// * `table_name` - The table name for the new join table.
+    /// * `join_columns` - The names of the columns in the joined table.
+    /// * `destination_columns` - The names of the columns in the main(base) table.
+    /// * `join_type` - The type of the join.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `JoinTableError` if there is an error adding the join table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use safety_postgres::legacy::join_tables::{JoinTables, JoinType};
+    ///
+    /// let mut join_tables = JoinTables::new();
+    ///
+    /// join_tables.add_join_table_with_type("", "profiles", &["user_id"], &["id"], JoinType::Left).expect("adding join table failed");
+    /// let joined_text = join_tables.get_joined_text();
+    ///
+    /// assert_eq!(joined_text, "LEFT JOIN profiles ON main_table_name.id = profiles.user_id");
+    /// ```
+    pub fn add_join_table_with_type(&mut self, schema: &str, table_name: &str, join_columns: &[&str], destination_columns: &[&str], join_type: JoinType) -> Result<&mut Self, JoinTableError> {
+        let operators = vec![ComparisonOperator::Equal; join_columns.len()];
+        self.add_join_table_with_operators(schema, table_name, join_columns, destination_columns, &operators, join_type)
+    }
+
+    /// Adds a join table whose column pairs are compared by the specified operators to the instance.
+    ///
+    /// Each pair is joined as `<main table>.<destination column> <operator> <joined table>.<join column>`,
+    /// so a range join such as `events.started_at <= logs.logged_at` can be expressed.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - The schema name for the new join table (input "" if there is no schema_name).
+    /// * `table_name` - The table name for the new join table.
+    /// * `join_columns` - The names of the columns in the joined table.
+    /// * `destination_columns` - The names of the columns in the main(base) table.
+    /// * `operators` - The comparison operators of each column pair.
+    /// * `join_type` - The type of the join.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `JoinTableError` if there is an error adding the join table,
+    /// e.g. the number of the operators doesn't match the number of the column pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use safety_postgres::legacy::conditions::ComparisonOperator;
+    /// use safety_postgres::legacy::join_tables::{JoinTables, JoinType};
+    ///
+    /// let mut join_tables = JoinTables::new();
+    ///
+    /// join_tables.add_join_table_with_operators(
+    ///     "",
+    ///     "logs",
+    ///     &["device_id", "logged_at"],
+    ///     &["device_id", "started_at"],
+    ///     &[ComparisonOperator::Equal, ComparisonOperator::LowerEq],
+    ///     JoinType::Inner).expect("adding join table failed");
+    /// let joined_text = join_tables.get_joined_text();
+    ///
+    /// assert_eq!(joined_text, "INNER JOIN logs ON main_table_name.device_id = logs.device_id AND main_table_name.started_at <= logs.logged_at");
+    /// ```
+    pub fn add_join_table_with_operators(&mut self, schema: &str, table_name: &str, join_columns: &[&str], destination_columns: &[&str], operators: &[ComparisonOperator], join_type: JoinType) -> Result<&mut Self, JoinTableError> {
         validate_string(table_name, "table_name", &JoinTableErrorGenerator)?;
         validate_string(schema, "schema", &JoinTableErrorGenerator)?;
         Self::validate_column_collection_pare(join_columns, destination_columns)?;
+        if operators.len() != join_columns.len() {
+            return Err(JoinTableError::InputInconsistentError("'operators' should have the same number of elements as 'join_columns' and 'destination_columns'.".to_string()));
+        }
 
         fn convert_vec(input: &[&str]) -> Vec<String> {
             input.iter().map(|str| str.to_string()).collect()
@@ -82,6 +185,8 @@ impl JoinTables {
             table_name: table_name.to_string(),
             join_columns: convert_vec(join_columns),
             destination_columns: convert_vec(destination_columns),
+            operators: operators.to_vec(),
+            join_type,
         };
 
         self.tables.push(join_table);
@@ -132,6 +237,13 @@ impl JoinTables {
         self.generate_statement_text("main_table_name")
     }
 
+    /// Returns the schema (empty if there is no schema) and the table names of the joined tables.
+    pub(super) fn get_table_names(&self) -> Vec<(&str, &str)> {
+        self.tables.iter()
+            .map(|table| (table.schema.as_str(), table.table_name.as_str()))
+            .collect()
+    }
+
     /// Checks if the tables collection is empty.
     ///
     /// # Returns
@@ -162,10 +274,10 @@ impl JoinTables {
     /// * `Ok(())` - If the column collections pass the validation.
     /// * `Err(JoinTableError)` - If there are any validation errors. The error type provides a detailed message.
     fn validate_column_collection_pare(join_columns: &[&str], destination_columns: &[&str]) -> Result<(), JoinTableError> {
-        if !join_columns.iter().all(|column| validate_alphanumeric_name(column, "_")) {
+        if !join_columns.iter().all(|column| validate_name(column)) {
             return Err(JoinTableError::InputInvalidError("'join_columns' includes invalid name. Please check your input.".to_string()));
         }
-        if !destination_columns.iter().all(|column| validate_alphanumeric_name(column, "_")) {
+        if !destination_columns.iter().all(|column| validate_name(column)) {
             return Err(JoinTableError::InputInvalidError("'destination_columns' includes invalid name. Please check your input.".to_string()));
         }
 
@@ -186,16 +298,16 @@ impl JoinTable {
     ///
     /// # Returns
     ///
-    /// The generated inner join SQL statement as a `String`.
+    /// The generated join SQL statement as a `String`.
     fn generate_statement_text(&self, main_table: String) -> String {
         let table_with_schema = if self.schema.is_empty() {
-            self.table_name.clone()
+            quote_identifier(&self.table_name)
         } else {
-            format!("{}.{}", self.schema, self.table_name)
+            format!("{}.{}", quote_identifier(&self.schema), quote_identifier(&self.table_name))
         };
-        let mut statement = format!("INNER JOIN {} ON", table_with_schema);
-        for (index, (join_column, destination_column)) in self.join_columns.iter().zip(&self.destination_columns).enumerate() {
-            statement += format!(" {}.{} = {}.{}", main_table, destination_column, table_with_schema, join_column).as_str();
+        let mut statement = format!("{} {} ON", self.join_type.get_join_text(), table_with_schema);
+        for (index, ((join_column, destination_column), operator)) in self.join_columns.iter().zip(&self.destination_columns).zip(&self.operators).enumerate() {
+            statement += format!(" {}.{} {} {}.{}", main_table, quote_identifier(destination_column), operator.get_operator_text(), table_with_schema, quote_identifier(join_column)).as_str();
             if index + 1 < self.join_columns.len() {
                 statement += " AND";
             }
@@ -231,6 +343,30 @@ mod tests {
         assert!(stmt.contains("INNER JOIN users ON main.user_id = users.id INNER JOIN schema.teams ON main.team_id = schema.teams.id"));
     }
 
+    /// Ensures that the join type is reflected in the statement.
+    #[test]
+    fn test_generate_statement_text_with_join_type() {
+        let mut join_tables = JoinTables::new();
+        join_tables.add_join_table_with_type("", "profiles", &["user_id"], &["id"], JoinType::Left).unwrap();
+        join_tables.add_join_table_with_type("", "teams", &["id"], &["team_id"], JoinType::Full).unwrap();
+
+        let stmt = join_tables.generate_statement_text("main");
+        assert_eq!(stmt, "LEFT JOIN profiles ON main.id = profiles.user_id FULL JOIN teams ON main.team_id = teams.id");
+    }
+
+    /// Ensures that the operators of the column pairs are reflected in the statement.
+    #[test]
+    fn test_generate_statement_text_with_operators() {
+        let mut join_tables = JoinTables::new();
+        join_tables.add_join_table_with_operators("", "logs", &["device_id", "logged_at"], &["device_id", "started_at"], &[ComparisonOperator::Equal, ComparisonOperator::LowerEq], JoinType::Inner).unwrap();
+
+        let stmt = join_tables.generate_statement_text("main");
+        assert_eq!(stmt, "INNER JOIN logs ON main.device_id = logs.device_id AND main.started_at <= logs.logged_at");
+
+        let Err(e) = join_tables.add_join_table_with_operators("", "logs", &["id"], &["log_id"], &[], JoinType::Inner) else { panic!() };
+        assert_eq!(e, JoinTableError::InputInconsistentError("'operators' should have the same number of elements as 'join_columns' and 'destination_columns'.".to_string()));
+    }
+
     /// Checks whether the tables collection is empty.
     #[test]
     fn test_is_tables_empty() {
@@ -247,6 +383,8 @@ mod tests {
             table_name: "users".to_string(),
             join_columns: vec!["id".to_string()],
             destination_columns: vec!["user_id".to_string()],
+            operators: vec![ComparisonOperator::Equal],
+            join_type: JoinType::Inner,
         };
 
         let stmt = join_table.generate_statement_text("main".to_string());
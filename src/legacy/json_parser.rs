@@ -1,6 +1,7 @@
+use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 use serde_json::{Map, Value, Error as JSONError};
-use tokio_postgres::Row;
+use tokio_postgres::{Column, Row};
 use crate::legacy::converter::row_to_value;
 
 #[derive(Serialize, Deserialize)]
@@ -8,19 +9,173 @@ struct GetData {
     data: Vec<Map<String, Value>>
 }
 
-pub(super) fn row_to_json(query_result: &Vec<Row>) -> Result<String, JSONError> {
+#[derive(Serialize, Deserialize)]
+struct GetSchemaData {
+    columns: Vec<ColumnSchema>,
+    data: Vec<Map<String, Value>>
+}
+
+#[derive(Serialize, Deserialize)]
+struct ColumnSchema {
+    name: String,
+    #[serde(rename = "type")]
+    type_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table: Option<String>,
+}
+
+/// Represents how the column names and columns of a query result are emitted as JSON.
+///
+/// The default options emit every column with the column name as the key.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::legacy::json_parser::JsonOptions;
+///
+/// let mut json_options = JsonOptions::new();
+/// json_options
+///     .set_camel_case(true)
+///     .add_rename("id", "userId")
+///     .add_exclude("password_hash");
+///
+/// assert_eq!(json_options.get_key("id"), Some("userId".to_string()));
+/// assert_eq!(json_options.get_key("created_at"), Some("createdAt".to_string()));
+/// assert_eq!(json_options.get_key("password_hash"), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JsonOptions {
+    camel_case: bool,
+    renames: HashMap<String, String>,
+    includes: Vec<String>,
+    excludes: Vec<String>,
+    nest_tables: bool,
+}
+
+impl JsonOptions {
+    /// Creates a new instance of the `JsonOptions` struct with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the snake_case column names are converted to camelCase keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `camel_case` - `true` converts the keys to camelCase.
+    ///
+    /// # Returns
+    ///
+    /// The modified `self` object.
+    pub fn set_camel_case(&mut self, camel_case: bool) -> &mut Self {
+        self.camel_case = camel_case;
+        self
+    }
+
+    /// Renames the key of a column. A renamed key takes precedence over the camelCase conversion.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column name in the query result.
+    /// * `key` - The key used in the JSON output.
+    ///
+    /// # Returns
+    ///
+    /// The modified `self` object.
+    pub fn add_rename(&mut self, column: &str, key: &str) -> &mut Self {
+        self.renames.insert(column.to_string(), key.to_string());
+        self
+    }
+
+    /// Adds a column to the include list. Once the list has a column, only the listed columns are emitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column name in the query result.
+    ///
+    /// # Returns
+    ///
+    /// The modified `self` object.
+    pub fn add_include(&mut self, column: &str) -> &mut Self {
+        self.includes.push(column.to_string());
+        self
+    }
+
+    /// Adds a column to the exclude list. The exclude list takes precedence over the include list.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column name in the query result.
+    ///
+    /// # Returns
+    ///
+    /// The modified `self` object.
+    pub fn add_exclude(&mut self, column: &str) -> &mut Self {
+        self.excludes.push(column.to_string());
+        self
+    }
+
+    /// Sets whether the columns are grouped into nested objects by their source table
+    /// (e.g. `{"users": {"id": 1}, "records": {"id": 3}}`) instead of a flat object.
+    ///
+    /// The columns which don't come from a table (e.g. expressions) stay at the top level.
+    ///
+    /// # Arguments
+    ///
+    /// * `nest_tables` - `true` groups the columns by their source table.
+    ///
+    /// # Returns
+    ///
+    /// The modified `self` object.
+    pub fn set_nest_tables(&mut self, nest_tables: bool) -> &mut Self {
+        self.nest_tables = nest_tables;
+        self
+    }
+
+    /// Returns whether the columns are grouped by their source table.
+    pub fn is_nest_tables(&self) -> bool {
+        self.nest_tables
+    }
+
+    /// Returns the JSON key for the column, or `None` if the column is not emitted.
+    pub fn get_key(&self, column: &str) -> Option<String> {
+        if self.excludes.iter().any(|exclude| exclude == column) {
+            return None
+        }
+        if !self.includes.is_empty() && !self.includes.iter().any(|include| include == column) {
+            return None
+        }
+        if let Some(key) = self.renames.get(column) {
+            return Some(key.to_string())
+        }
+        if self.camel_case {
+            Some(to_camel_case(column))
+        } else {
+            Some(column.to_string())
+        }
+    }
+}
+
+fn to_camel_case(column: &str) -> String {
+    let mut camel_case = String::new();
+    let mut upper_next = false;
+    for char in column.chars() {
+        if char == '_' {
+            upper_next = !camel_case.is_empty();
+        } else if upper_next {
+            camel_case.extend(char.to_uppercase());
+            upper_next = false;
+        } else {
+            camel_case.push(char);
+        }
+    }
+    camel_case
+}
+
+pub(super) fn row_to_json(query_result: &Vec<Row>, json_options: &JsonOptions, table_names: &HashMap<u32, String>) -> Result<String, JSONError> {
     let mut data: Vec<Map<String, Value>> = Vec::new();
-    let columns: Vec<String> =
-        query_result[0].columns().iter().map(
-            |column| column.name().to_string()
-        ).collect();
 
     for row in query_result {
-        let mut row_data: Map<String, Value> = Map::new();
-        for column in &columns {
-            row_data.insert(column.to_string(), row_to_value(row, column));
-        }
-        data.push(row_data);
+        data.push(row_to_map(row, json_options, table_names));
     }
 
     let get_data = GetData {
@@ -28,3 +183,94 @@ pub(super) fn row_to_json(query_result: &Vec<Row>) -> Result<String, JSONError>
     };
     serde_json::to_string(&get_data)
 }
+
+/// Serializes the rows with the column schema of the statement, so the columns are known even if there is no row.
+pub(super) fn row_to_schema_json(columns: &[Column], query_result: &[Row], json_options: &JsonOptions, table_names: &HashMap<u32, String>) -> Result<String, JSONError> {
+    let columns = columns.iter().filter_map(|column| {
+        let name = json_options.get_key(column.name())?;
+        let table = match column.table_oid() {
+            Some(table_oid) if json_options.nest_tables => table_names.get(&table_oid).cloned(),
+            _ => None,
+        };
+        Some(ColumnSchema {
+            name,
+            type_name: column.type_().name().to_string(),
+            table,
+        })
+    }).collect();
+    let data = query_result.iter().map(|row| row_to_map(row, json_options, table_names)).collect();
+
+    let get_schema_data = GetSchemaData {
+        columns,
+        data,
+    };
+    serde_json::to_string(&get_schema_data)
+}
+
+/// Serializes a row into a single-line JSON object for NDJSON output.
+pub(super) fn row_to_json_line(row: &Row, json_options: &JsonOptions, table_names: &HashMap<u32, String>) -> Result<String, JSONError> {
+    serde_json::to_string(&row_to_map(row, json_options, table_names))
+}
+
+/// Converts a row to a JSON object.
+///
+/// `table_names` maps the table OIDs to the table names and is only used when the columns are nested by table.
+fn row_to_map(row: &Row, json_options: &JsonOptions, table_names: &HashMap<u32, String>) -> Map<String, Value> {
+    let mut row_data: Map<String, Value> = Map::new();
+    for (index, column) in row.columns().iter().enumerate() {
+        let key = match json_options.get_key(column.name()) {
+            Some(key) => key,
+            None => continue,
+        };
+        let value = row_to_value(row, index);
+
+        let table_name = match column.table_oid() {
+            Some(table_oid) if json_options.nest_tables => table_names.get(&table_oid),
+            _ => None,
+        };
+        match table_name {
+            Some(table_name) => {
+                let table_data = row_data
+                    .entry(table_name.to_string())
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if let Value::Object(table_data) = table_data {
+                    table_data.insert(key, value);
+                }
+            }
+            None => {
+                row_data.insert(key, value);
+            }
+        }
+    }
+    row_data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_camel_case, JsonOptions};
+
+    #[test]
+    fn test_json_options_key() {
+        assert_eq!(to_camel_case("user_name"), "userName");
+        assert_eq!(to_camel_case("_created__at_"), "createdAt");
+        assert_eq!(to_camel_case("id"), "id");
+
+        let default_options = JsonOptions::new();
+        assert_eq!(default_options.get_key("user_name"), Some("user_name".to_string()));
+
+        let mut json_options = JsonOptions::new();
+        json_options
+            .set_camel_case(true)
+            .add_rename("user_name", "name")
+            .add_include("user_name")
+            .add_include("created_at")
+            .add_include("password")
+            .add_exclude("password");
+        assert_eq!(json_options.get_key("user_name"), Some("name".to_string()));
+        assert_eq!(json_options.get_key("created_at"), Some("createdAt".to_string()));
+        assert_eq!(json_options.get_key("password"), None);
+        assert_eq!(json_options.get_key("updated_at"), None);
+        assert!(!json_options.is_nest_tables());
+        assert!(json_options.set_nest_tables(true).is_nest_tables());
+    }
+}
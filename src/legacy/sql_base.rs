@@ -1,5 +1,8 @@
+use crate::legacy::converter::ParamValue;
 use crate::legacy::errors::*;
 use crate::legacy::validators::validate_string;
+use crate::utils::identifier::quote_identifier;
+use crate::Variable;
 
 /// Represents the different types of SQL statements.
 #[derive(Clone)]
@@ -34,15 +37,43 @@ trait SqlBuilder {
 #[derive(Clone)]
 pub struct QueryColumns {
     all_columns: bool,
+    distinct: bool,
     columns: Vec<QueryColumn>,
 }
 
 /// Represents a single query column.
+///
+/// `aggregate` holds the aggregate function when the column is selected as an aggregate,
+/// and `alias` is the name of the result column (empty for no alias).
 #[derive(Clone)]
 struct QueryColumn {
     schema_name: String,
     table_name: String,
     column: String,
+    aggregate: Option<Agg>,
+    alias: String,
+}
+
+/// Represents the aggregate functions available in `QueryColumns::add_aggregate`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Agg {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl Agg {
+    fn get_function_text(&self) -> &'static str {
+        match self {
+            Agg::Count => "COUNT",
+            Agg::Sum => "SUM",
+            Agg::Avg => "AVG",
+            Agg::Min => "MIN",
+            Agg::Max => "MAX",
+        }
+    }
 }
 
 impl QueryColumns {
@@ -55,10 +86,32 @@ impl QueryColumns {
     pub fn new(all_columns: bool) -> Self {
         Self {
             all_columns,
+            distinct: false,
             columns: Vec::new(),
         }
     }
 
+    /// Sets whether the duplicated rows are removed from the result by `SELECT DISTINCT`.
+    ///
+    /// # Arguments
+    ///
+    /// * `distinct` - `true` to select the distinct rows only.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use safety_postgres::legacy::sql_base::QueryColumns;
+    ///
+    /// let mut query_columns = QueryColumns::new(false);
+    /// query_columns.set_distinct(true).add_column("", "", "country").unwrap();
+    ///
+    /// assert_eq!(query_columns.get_query_text(), "SELECT DISTINCT country FROM main_table_name");
+    /// ```
+    pub fn set_distinct(&mut self, distinct: bool) -> &mut Self {
+        self.distinct = distinct;
+        self
+    }
+
     /// Adds a query selected column to the query.
     ///
     /// # Arguments
@@ -87,6 +140,39 @@ impl QueryColumns {
     /// query_columns.add_column("", "", "id").unwrap().add_column("", "", "username").unwrap();
     /// ```
     pub fn add_column(&mut self, schema_name: &str, table_name: &str, column: &str) -> Result<&mut Self, QueryColumnError> {
+        self.add_column_with_alias(schema_name, table_name, column, "")
+    }
+
+    /// Adds a query selected column renamed by the alias to the query.
+    ///
+    /// The alias is the column name in the result, so it's also the key of the JSON output.
+    /// Use it to distinguish the same column names of the joined tables, e.g. `id` of `users` and `records`.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_name` - The name of the schema (input "" if there is no schema name or in the same table).
+    /// * `table_name` - The name of the table (input "" if there is no table name or in the same table).
+    /// * `column` - The name of the column.
+    /// * `alias` - The name of the result column (input "" for no alias).
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if:
+    /// * The `all_columns` flag is set to true.
+    /// * The `schema_name`, `table_name`, `column` or `alias` is an invalid string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use safety_postgres::legacy::sql_base::QueryColumns;
+    ///
+    /// let mut query_columns = QueryColumns::new(false);
+    /// query_columns.add_column_with_alias("", "users", "id", "user_id").unwrap()
+    ///     .add_column_with_alias("", "records", "id", "record_id").unwrap();
+    ///
+    /// assert_eq!(query_columns.get_query_text(), "SELECT users.id AS user_id, records.id AS record_id FROM main_table_name");
+    /// ```
+    pub fn add_column_with_alias(&mut self, schema_name: &str, table_name: &str, column: &str, alias: &str) -> Result<&mut Self, QueryColumnError> {
         if self.all_columns {
             return Err(QueryColumnError::InputInconsistentError("'all_columns' flag is true so all columns will queried so you can't set column. Please check your input.".to_string()));
         }
@@ -94,11 +180,74 @@ impl QueryColumns {
         validate_string(schema_name, "schema_name", &QueryColumnErrorGenerator)?;
         validate_string(table_name, "table_name", &QueryColumnErrorGenerator)?;
         validate_string(column, "column_name", &QueryColumnErrorGenerator)?;
+        validate_string(alias, "alias", &QueryColumnErrorGenerator)?;
+
+        let query_column = QueryColumn {
+            schema_name: schema_name.to_string(),
+            table_name: table_name.to_string(),
+            column: column.to_string(),
+            aggregate: None,
+            alias: alias.to_string(),
+        };
+
+        self.columns.push(query_column);
+        Ok(self)
+    }
+
+    /// Adds an aggregated column to the query.
+    ///
+    /// The other selected columns need to be in the `GroupBy` passed to `PostgresBase::query_grouped`.
+    ///
+    /// # Arguments
+    ///
+    /// * `aggregate` - The aggregate function.
+    /// * `schema_name` - The name of the schema (input "" if there is no schema name or in the same table).
+    /// * `table_name` - The name of the table (input "" if there is no table name or in the same table).
+    /// * `column` - The name of the column (input "*" to count the rows with `Agg::Count`).
+    /// * `alias` - The name of the result column.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if:
+    /// * The `all_columns` flag is set to true.
+    /// * The `column` is "*" with the aggregate other than `Agg::Count`.
+    /// * The `schema_name`, `table_name`, `column` or `alias` is an invalid string, or the `alias` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use safety_postgres::legacy::sql_base::{Agg, QueryColumns};
+    ///
+    /// let mut query_columns = QueryColumns::new(false);
+    /// query_columns.add_column("", "", "user_id").unwrap()
+    ///     .add_aggregate(Agg::Count, "", "", "*", "post_count").unwrap();
+    ///
+    /// assert_eq!(query_columns.get_query_text(), "SELECT user_id, COUNT(*) AS post_count FROM main_table_name");
+    /// ```
+    pub fn add_aggregate(&mut self, aggregate: Agg, schema_name: &str, table_name: &str, column: &str, alias: &str) -> Result<&mut Self, QueryColumnError> {
+        if self.all_columns {
+            return Err(QueryColumnError::InputInconsistentError("'all_columns' flag is true so all columns will queried so you can't set column. Please check your input.".to_string()));
+        }
+        if column == "*" {
+            if aggregate != Agg::Count || !schema_name.is_empty() || !table_name.is_empty() {
+                return Err(QueryColumnError::InputInconsistentError("'*' is available only with 'Agg::Count' and without the schema and table names.".to_string()));
+            }
+        } else {
+            validate_string(schema_name, "schema_name", &QueryColumnErrorGenerator)?;
+            validate_string(table_name, "table_name", &QueryColumnErrorGenerator)?;
+            validate_string(column, "column_name", &QueryColumnErrorGenerator)?;
+        }
+        if alias.is_empty() {
+            return Err(QueryColumnError::InputInvalidError("'alias' is required for the aggregated column.".to_string()));
+        }
+        validate_string(alias, "alias", &QueryColumnErrorGenerator)?;
 
         let query_column = QueryColumn {
             schema_name: schema_name.to_string(),
             table_name: table_name.to_string(),
             column: column.to_string(),
+            aggregate: Some(aggregate),
+            alias: alias.to_string(),
         };
 
         self.columns.push(query_column);
@@ -123,6 +272,53 @@ impl QueryColumns {
     pub fn get_query_text(&self) -> String {
         self.build_sql("main_table_name")
     }
+
+    /// Retrieves the RETURNING clause returning the columns.
+    pub(super) fn get_returning_text(&self) -> String {
+        format!("RETURNING {}", self.get_select_list())
+    }
+
+    fn get_select_list(&self) -> String {
+        if self.all_columns {
+            return "*".to_string()
+        }
+        self.columns
+            .iter()
+            .map(|query_column| query_column.get_select_text())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+impl QueryColumn {
+    /// Returns the column qualified with the schema and the table names if they are set.
+    fn get_column_text(&self) -> String {
+        let mut column_text: Vec<String> = Vec::new();
+        if !self.schema_name.is_empty() {
+            column_text.push(quote_identifier(&self.schema_name));
+        }
+        if !self.table_name.is_empty() {
+            column_text.push(quote_identifier(&self.table_name));
+        }
+        column_text.push(quote_identifier(&self.column));
+        column_text.join(".")
+    }
+
+    /// Returns the column text in the SELECT list, wrapped by the aggregate function and renamed by the alias if they are set.
+    fn get_select_text(&self) -> String {
+        let select_text = match &self.aggregate {
+            Some(aggregate) => {
+                let column_text = if self.column == "*" { "*".to_string() } else { self.get_column_text() };
+                format!("{}({})", aggregate.get_function_text(), column_text)
+            },
+            None => self.get_column_text(),
+        };
+        if self.alias.is_empty() {
+            select_text
+        } else {
+            format!("{} AS {}", select_text, quote_identifier(&self.alias))
+        }
+    }
 }
 
 impl SqlBuilder for QueryColumns {
@@ -138,29 +334,165 @@ impl SqlBuilder for QueryColumns {
     fn build_sql(&self, table_name: &str) -> String {
         let mut sql_vec: Vec<String> = Vec::new();
         sql_vec.push("SELECT".to_string());
-        if self.all_columns {
-            sql_vec.push("*".to_string());
-        }
-        else {
-            let mut columns: Vec<String> = Vec::new();
-            for query_column in &self.columns {
-                let mut column_condition: Vec<String> = Vec::new();
-                if !query_column.schema_name.is_empty() {
-                    column_condition.push(query_column.schema_name.clone());
-                }
-                if !query_column.table_name.is_empty() {
-                    column_condition.push(query_column.table_name.clone());
-                }
-                column_condition.push(query_column.column.clone());
-                columns.push(column_condition.join("."));
-            }
-            sql_vec.push(columns.join(", "));
+        if self.distinct {
+            sql_vec.push("DISTINCT".to_string());
         }
+        sql_vec.push(self.get_select_list());
         sql_vec.push(format!("FROM {}", table_name));
         sql_vec.join(" ")
     }
 }
 
+/// Represents the sort direction of an `OrderBy` column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// Represents the ORDER BY clause of a query.
+///
+/// # Example
+///
+/// ```rust
+/// use safety_postgres::legacy::sql_base::{OrderBy, SortOrder};
+///
+/// let mut order_by = OrderBy::new();
+/// order_by.add_order("", "", "created_at", SortOrder::Desc).unwrap().add_order("", "", "id", SortOrder::Asc).unwrap();
+///
+/// assert_eq!(order_by.get_order_text(), "ORDER BY created_at DESC, id ASC");
+/// ```
+#[derive(Clone, Default)]
+pub struct OrderBy {
+    columns: Vec<(QueryColumn, SortOrder)>,
+}
+
+impl OrderBy {
+    /// Creates a new instance of `OrderBy` without any sort column.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a sort column. The rows are sorted by the columns in the order they are added.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_name` - The name of the schema (input "" if there is no schema name or in the same table).
+    /// * `table_name` - The name of the table (input "" if there is no table name or in the same table).
+    /// * `column` - The name of the column.
+    /// * `sort_order` - The sort direction of the column.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `QueryColumnError` if the `schema_name`, `table_name`, or `column` is an invalid string.
+    pub fn add_order(&mut self, schema_name: &str, table_name: &str, column: &str, sort_order: SortOrder) -> Result<&mut Self, QueryColumnError> {
+        validate_string(schema_name, "schema_name", &QueryColumnErrorGenerator)?;
+        validate_string(table_name, "table_name", &QueryColumnErrorGenerator)?;
+        validate_string(column, "column_name", &QueryColumnErrorGenerator)?;
+
+        let query_column = QueryColumn {
+            schema_name: schema_name.to_string(),
+            table_name: table_name.to_string(),
+            column: column.to_string(),
+            aggregate: None,
+            alias: String::new(),
+        };
+
+        self.columns.push((query_column, sort_order));
+        Ok(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Retrieves the ORDER BY clause, or an empty string if no sort column is added.
+    pub fn get_order_text(&self) -> String {
+        if self.columns.is_empty() {
+            return String::new();
+        }
+        let columns = self.columns
+            .iter()
+            .map(|(query_column, sort_order)| {
+                let direction = match sort_order {
+                    SortOrder::Asc => "ASC",
+                    SortOrder::Desc => "DESC",
+                };
+                format!("{} {}", query_column.get_column_text(), direction)
+            })
+            .collect::<Vec<String>>();
+        format!("ORDER BY {}", columns.join(", "))
+    }
+}
+
+/// Represents the GROUP BY clause of a query.
+///
+/// # Example
+///
+/// ```rust
+/// use safety_postgres::legacy::sql_base::GroupBy;
+///
+/// let mut group_by = GroupBy::new();
+/// group_by.add_column("", "", "user_id").unwrap();
+///
+/// assert_eq!(group_by.get_group_text(), "GROUP BY user_id");
+/// ```
+#[derive(Clone, Default)]
+pub struct GroupBy {
+    columns: Vec<QueryColumn>,
+}
+
+impl GroupBy {
+    /// Creates a new instance of `GroupBy` without any group column.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a group column.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_name` - The name of the schema (input "" if there is no schema name or in the same table).
+    /// * `table_name` - The name of the table (input "" if there is no table name or in the same table).
+    /// * `column` - The name of the column.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `QueryColumnError` if the `schema_name`, `table_name`, or `column` is an invalid string.
+    pub fn add_column(&mut self, schema_name: &str, table_name: &str, column: &str) -> Result<&mut Self, QueryColumnError> {
+        validate_string(schema_name, "schema_name", &QueryColumnErrorGenerator)?;
+        validate_string(table_name, "table_name", &QueryColumnErrorGenerator)?;
+        validate_string(column, "column_name", &QueryColumnErrorGenerator)?;
+
+        let query_column = QueryColumn {
+            schema_name: schema_name.to_string(),
+            table_name: table_name.to_string(),
+            column: column.to_string(),
+            aggregate: None,
+            alias: String::new(),
+        };
+
+        self.columns.push(query_column);
+        Ok(self)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Retrieves the GROUP BY clause, or an empty string if no group column is added.
+    pub fn get_group_text(&self) -> String {
+        if self.columns.is_empty() {
+            return String::new();
+        }
+        let columns = self.columns
+            .iter()
+            .map(|query_column| query_column.get_column_text())
+            .collect::<Vec<String>>();
+        format!("GROUP BY {}", columns.join(", "))
+    }
+}
+
 /// Represents a collection of update sets.
 ///
 /// Update sets are used to define the values to be updated in a database table.
@@ -191,7 +523,7 @@ pub struct UpdateSets {
 #[derive(Clone)]
 struct UpdateSet {
     column: String,
-    value: String,
+    value: ParamValue,
 }
 
 impl UpdateSets {
@@ -230,7 +562,44 @@ impl UpdateSets {
 
         let update_set = UpdateSet {
             column: column.to_string(),
-            value: value.to_string(),
+            value: ParamValue::Raw(value.to_string()),
+        };
+        self.update_sets.push(update_set);
+
+        Ok(self)
+    }
+
+    /// Adds a set of column-value pair whose value is typed by the caller.
+    ///
+    /// Unlike `add_set`, the value is bound as it is without the type inference of the string
+    /// (e.g. the `"i64"` and `"dec"` suffixes), so `"00123"` stays text and a `Variable::Decimal` stays numeric.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The name of the column to be updated.
+    /// * `value` - The new value for the column.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `UpdateSetError` if the `column` is not a valid string.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use safety_postgres::legacy::sql_base::UpdateSets;
+    /// use safety_postgres::Variable;
+    ///
+    /// let mut update_sets = UpdateSets::new();
+    /// update_sets
+    ///     .add_typed_set("zip_code", Variable::Text("00123".to_string())).expect("adding update set failed")
+    ///     .add_typed_set("visits", 10_i64).expect("adding update set failed");
+    /// ```
+    pub fn add_typed_set(&mut self, column: &str, value: impl Into<Variable>) -> Result<&mut Self, UpdateSetError> {
+        validate_string(column, "column", &UpdateSetErrorGenerator)?;
+
+        let update_set = UpdateSet {
+            column: column.to_string(),
+            value: ParamValue::Typed(value.into()),
         };
         self.update_sets.push(update_set);
 
@@ -242,7 +611,7 @@ impl UpdateSets {
     /// # Returns
     ///
     /// A vector of strings containing the values.
-    pub(super) fn get_flat_values(&self) -> Vec<String> {
+    pub(super) fn get_flat_values(&self) -> Vec<ParamValue> {
         let mut flat_values = Vec::new();
         for update_set in &self.update_sets {
             flat_values.push(update_set.value.clone());
@@ -255,7 +624,7 @@ impl UpdateSets {
         let mut update_text = self.build_sql("main_table_name");
         let values = self.get_flat_values();
         for (index, value) in values.iter().enumerate() {
-            update_text = update_text.replace(&format!("${}", index + 1), value);
+            update_text = update_text.replace(&format!("${}", index + 1), value.to_string().as_str());
         }
 
         update_text
@@ -289,7 +658,7 @@ impl SqlBuilder for UpdateSets {
 
         let mut set_vec: Vec<String> = Vec::new();
         for (index, update_set) in self.update_sets.iter().enumerate() {
-            set_vec.push(format!("{} = ${}", update_set.column, index + 1));
+            set_vec.push(format!("{} = ${}", quote_identifier(&update_set.column), index + 1));
         }
         sql_vec.push(format!("SET {}", set_vec.join(", ")));
 
@@ -297,6 +666,61 @@ impl SqlBuilder for UpdateSets {
     }
 }
 
+/// Represents the column used for the optimistic locking of `PostgresBase::update_with_lock`.
+///
+/// The update only succeeds if the column still has the value the caller read, and the column is
+/// bumped by the update so that a concurrent update with the same value fails.
+///
+/// # Example
+///
+/// ```rust
+/// use safety_postgres::legacy::sql_base::OptimisticLock;
+///
+/// let version_lock = OptimisticLock::version("version").expect("invalid column");
+/// let timestamp_lock = OptimisticLock::updated_at("updated_at").expect("invalid column");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum OptimisticLock {
+    Version(String),
+    UpdatedAt(String),
+}
+
+impl OptimisticLock {
+    /// Creates a lock on an integer version column which is incremented by each update.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `UpdateSetError` if the `column` is not a valid string.
+    pub fn version(column: &str) -> Result<Self, UpdateSetError> {
+        validate_string(column, "column", &UpdateSetErrorGenerator)?;
+        Ok(Self::Version(column.to_string()))
+    }
+
+    /// Creates a lock on a timestamp column which is set to `now()` by each update.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `UpdateSetError` if the `column` is not a valid string.
+    pub fn updated_at(column: &str) -> Result<Self, UpdateSetError> {
+        validate_string(column, "column", &UpdateSetErrorGenerator)?;
+        Ok(Self::UpdatedAt(column.to_string()))
+    }
+
+    pub(super) fn get_column(&self) -> &str {
+        match self {
+            Self::Version(column) | Self::UpdatedAt(column) => column.as_str(),
+        }
+    }
+
+    /// Returns the SET clause bumping the lock column.
+    pub(super) fn get_bump_text(&self) -> String {
+        match self {
+            Self::Version(column) => format!("{} = {} + 1", quote_identifier(column), quote_identifier(column)),
+            Self::UpdatedAt(column) => format!("{} = now()", quote_identifier(column)),
+        }
+    }
+}
+
 /// Represents a collection of insert records.
 ///
 /// # Fields
@@ -332,7 +756,7 @@ pub struct InsertRecords {
 /// Represents the values of one record to be inserted into a table.
 #[derive(Clone)]
 struct InsertRecord {
-    values: Vec<String>,
+    values: Vec<ParamValue>,
 }
 
 impl InsertRecords {
@@ -376,15 +800,45 @@ impl InsertRecords {
     /// insert_records.add_record(&record).unwrap();
     /// ```
     pub fn add_record(&mut self, record: &[&str]) -> Result<&mut Self, InsertValueError> {
+        let values = record.iter().map(|value| ParamValue::Raw(value.to_string())).collect();
+        self.push_record(values)
+    }
+
+    /// Adds a record whose values are typed by the caller, in the order of the columns.
+    ///
+    /// Unlike `add_record`, the values are bound as they are without the type inference of the strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InsertValueError` if the number of the values doesn't match the columns or a column is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_decimal::Decimal;
+    /// use safety_postgres::legacy::sql_base::InsertRecords;
+    /// use safety_postgres::Variable;
+    ///
+    /// let mut insert_records = InsertRecords::new(&["zip_code", "price"]);
+    /// insert_records
+    ///     .add_typed_record(vec![Variable::Text("00123".to_string()), Variable::from(Decimal::new(1999, 2))])
+    ///     .expect("adding insert record failed");
+    /// ```
+    pub fn add_typed_record(&mut self, record: Vec<Variable>) -> Result<&mut Self, InsertValueError> {
+        let values = record.into_iter().map(ParamValue::Typed).collect();
+        self.push_record(values)
+    }
+
+    fn push_record(&mut self, record: Vec<ParamValue>) -> Result<&mut Self, InsertValueError> {
         if self.insert_records.is_empty() {
-            self.keys.iter().map(|key| validate_string(key.as_str(), "columns", &InsertValueErrorGenerator)).collect::<Result<_, InsertValueError>>()?;
+            self.keys.iter().map(|key| validate_string(key.as_str(), "columns", &InsertValueErrorGenerator)).collect::<Result<(), InsertValueError>>()?;
         }
         if record.len() != self.keys.len() {
             return Err(InsertValueError::InputInconsistentError("'values' should match with the 'columns' number. Please input data.".to_string()));
         }
 
         let insert_record = InsertRecord {
-            values: record.iter().map(|value| value.to_string()).collect(),
+            values: record,
         };
 
         self.insert_records.push(insert_record);
@@ -417,7 +871,7 @@ impl InsertRecords {
         let values = self.get_flat_values();
 
         for (index, value) in values.iter().enumerate() {
-            insert_text = insert_text.replace(&format!("${}", index + 1), value);
+            insert_text = insert_text.replace(&format!("${}", index + 1), value.to_string().as_str());
         }
 
         insert_text
@@ -427,14 +881,35 @@ impl InsertRecords {
     ///
     /// # Returns
     ///
-    /// - `Vec<String>` - A vector containing all the values from the insert records.
-    pub(super) fn get_flat_values(&self) -> Vec<String> {
+    /// - `Vec<ParamValue>` - A vector containing all the values from the insert records.
+    pub(super) fn get_flat_values(&self) -> Vec<ParamValue> {
         let mut flat_values = Vec::new();
         for record in &self.insert_records {
             flat_values.extend(record.values.clone());
         }
         flat_values
     }
+
+    /// Returns the number of the records.
+    pub fn get_num_records(&self) -> usize {
+        self.insert_records.len()
+    }
+
+    /// Returns the number of the columns of each record.
+    pub fn get_num_columns(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Splits the records into batches of at most `batch_size` records with the same columns.
+    pub(super) fn split_batches(&self, batch_size: usize) -> Vec<InsertRecords> {
+        self.insert_records
+            .chunks(batch_size.max(1))
+            .map(|records| InsertRecords {
+                keys: self.keys.clone(),
+                insert_records: records.to_vec(),
+            })
+            .collect()
+    }
 }
 
 impl SqlBuilder for InsertRecords {
@@ -460,7 +935,7 @@ impl SqlBuilder for InsertRecords {
                 _ => values_placeholder_vec.push(format!("${}", placeholder_index))
             }
         }
-        sql_vec.push(format!("({}) VALUES {}", self.keys.join(", "), values_placeholder_vec.join(", ")));
+        sql_vec.push(format!("({}) VALUES {}", self.keys.iter().map(|key| quote_identifier(key)).collect::<Vec<String>>().join(", "), values_placeholder_vec.join(", ")));
         sql_vec.join(" ")
     }
 
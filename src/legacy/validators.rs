@@ -1,17 +1,32 @@
 use crate::legacy::errors::ErrorGenerator;
+use crate::utils::identifier::{get_identifier_policy, validate_identifier, IdentifierPolicy};
 
-/// Validates if a string contains only alphanumeric characters or characters from a provided allow list.
+/// Validates if a name is accepted by the current `IdentifierPolicy`.
+/// An empty string is valid because it means the name isn't specified in this module.
 ///
 /// # Arguments
 ///
 /// * `s` - The string to be validated.
-/// * `allow_chars` - A string containing characters that are allowed in addition to alphanumeric characters.
 ///
 /// # Returns
 ///
 /// Returns `true` if the string is valid, otherwise `false`.
-pub(super) fn validate_alphanumeric_name(s: &str, allow_chars: &str) -> bool {
-    s.chars().all(|char| char.is_alphanumeric() || allow_chars.contains(char))
+pub(super) fn validate_name(s: &str) -> bool {
+    s.is_empty() || validate_identifier(s)
+}
+
+/// Validates if a name used in the connection settings (e.g. dbname) consists of ASCII alphabets,
+/// numbers and `_` only regardless of the `IdentifierPolicy`, because it's embedded in the connection string.
+///
+/// # Arguments
+///
+/// * `s` - The string to be validated.
+///
+/// # Returns
+///
+/// Returns `true` if the string is valid, otherwise `false`.
+pub(super) fn validate_connection_name(s: &str) -> bool {
+    s.is_empty() || IdentifierPolicy::StrictAscii.is_valid(s)
 }
 
 /// Validates a string based on a specific criteria.
@@ -28,8 +43,8 @@ pub(super) fn validate_alphanumeric_name(s: &str, allow_chars: &str) -> bool {
 ///
 /// Returns `Ok(())` if the string is valid, otherwise returns an error.
 pub(super) fn validate_string<E, G>(str: &str, param_name: &str, error_generator: &G) -> Result<(), E> where G: ErrorGenerator<E> {
-    if !validate_alphanumeric_name(str, "_") {
-        let error_message = format!("'{}' has invalid characters. '{}' allows alphabets, numbers and under bar only.", str, param_name);
+    if !validate_name(str) {
+        let error_message = format!("'{}' has invalid characters. '{}' allows {}.", str, param_name, get_identifier_policy().get_rule());
         return Err(error_generator.generate_error(error_message));
     } else {
         Ok(())
@@ -42,17 +57,18 @@ mod tests {
     use crate::legacy::errors::{JoinTableError, JoinTableErrorGenerator};
     use super::*;
 
-    /// Tests `validate_alphanumeric_name` function.
+    /// Tests `validate_connection_name` function.
     /// It checks if the function correctly validates whether given string consists of only alphanumeric characters and allowed characters,
     /// and correctly returns `false` for invalid strings and `true` for valid strings.
     #[test]
     fn test_valid_alphanumeric_name() {
         let symbols_invalid = "`~!@#$%^&*()+=-{}|:\"<>?[]\\;',./";
         for symbol_char in symbols_invalid.chars() {
-            assert_eq!(validate_alphanumeric_name(format!("Abc{}1098", symbol_char).as_str(), "_"), false);
+            assert_eq!(validate_connection_name(format!("Abc{}1098", symbol_char).as_str()), false);
         }
 
-        assert_eq!(validate_alphanumeric_name("abD_234", "_"), true);
+        assert_eq!(validate_connection_name("abD_234"), true);
+        assert!(!validate_connection_name("名前"));
     }
 
     /// Tests that `validate_string` function correctly validates given string and parameter name,
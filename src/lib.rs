@@ -1,15 +1,34 @@
-use std::fmt::{Display, Formatter};
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::fmt::{Debug, Display, Formatter};
+use std::net::IpAddr;
+use std::sync::Arc;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use cidr::{IpCidr, IpInet};
+use eui48::MacAddress;
 use rust_decimal::Decimal;
 use crate::generator::base::{MainGenerator, Parameters};
 use crate::generator::query::QueryGenerator;
-
+use crate::types::custom::CustomType;
+use crate::types::interval::Interval;
+use crate::types::numeric::{fit_numeric, NumericMode};
+use crate::types::pg_enum::{EnumLabel, PgEnum};
+use crate::types::range::Range;
+use crate::utils::errors::GeneratorError;
+use crate::utils::identifier::{get_identifier_policy, quote_identifier, validate_identifier};
+
+mod error;
 pub mod legacy;
 pub mod connector;
 pub mod utils;
 pub mod generator;
-mod converter;
-mod executor;
+pub mod types;
+pub mod converter;
+pub mod executor;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+
+pub use error::Error;
 
 /// Represents a variable that can hold different types of values.
 ///
@@ -19,6 +38,8 @@ mod executor;
 /// # Variants
 ///
 /// - `Text(String)`: Represents a variable that holds a text value.
+/// - `Secret(String)`: Represents a variable that holds a sensitive text value (e.g. a password or an API token).
+///   It is bound as text but is rendered as `****` by `Display` and `Debug`.
 /// - `SmallInt(i16)`: Represents a variable that holds a small integer value (-32,768 to 32,767).
 /// - `Int(i32)`: Represents a variable that holds an integer value (-2,147,483,648 to 2,147,483,647).
 /// - `BigInt(i64)`: Represents a variable that holds a big integer value (-9,223,372,036,854,775,808 to 9,223,372,036,854,775,807).
@@ -27,11 +48,22 @@ mod executor;
 /// - `Decimal(Decimal)`: Represents a variable that holds a decimal value.
 /// - `Date(NaiveDate)`: Represents a variable that holds a date value.
 /// - `DateTime(NaiveDateTime)`: Represents a variable that holds a date and time value.
+/// - `DateTimeTz(DateTime<FixedOffset>)`: Represents a variable that holds a date and time value with timezone offset.
 /// - `Time(NaiveTime)`: Represents a variable that holds a time value.
 /// - `Bool(bool)`: Represents a variable that holds a boolean value.
+/// - `Interval(Interval)`: Represents a variable that holds an interval value.
+/// - `Inet(IpInet)`: Represents a variable that holds a host address with optional netmask (`inet`).
+/// - `Cidr(IpCidr)`: Represents a variable that holds a network address (`cidr`).
+/// - `MacAddr(MacAddress)`: Represents a variable that holds a MAC address (`macaddr`).
+/// - `Enum(EnumLabel)`: Represents a variable that holds a label of a PostgreSQL enum type.
+/// - `Range(Box<Range>)`: Represents a variable that holds a range value with bound inclusivity.
+/// - `Array(Vec<Variable>)`: Represents a variable that holds an array whose elements all have the same type.
+/// - `Null`: Represents a SQL NULL. It can be bound to a column of any type.
+/// - `Custom(Arc<dyn CustomType>)`: Represents a variable that holds a value of an application defined type.
 #[derive(Clone)]
 pub enum Variable {
     Text(String),
+    Secret(String),
     SmallInt(i16),
     Int(i32),
     BigInt(i64),
@@ -40,8 +72,80 @@ pub enum Variable {
     Decimal(Decimal),
     Date(NaiveDate),
     DateTime(NaiveDateTime),
+    DateTimeTz(DateTime<FixedOffset>),
     Time(NaiveTime),
     Bool(bool),
+    Interval(Interval),
+    Inet(IpInet),
+    Cidr(IpCidr),
+    MacAddr(MacAddress),
+    Enum(EnumLabel),
+    Range(Box<Range>),
+    Array(Vec<Variable>),
+    Null,
+    Custom(Arc<dyn CustomType>),
+}
+
+impl Variable {
+    /// Creates an array variable after validating that all elements have the same type.
+    ///
+    /// PostgreSQL arrays are homogeneous, so mixing e.g. `Int` and `Text` elements is rejected here
+    /// instead of failing at execution time. `Null` elements are allowed in an array of any type.
+    ///
+    /// # Example
+    /// ```rust
+    /// use safety_postgres::Variable;
+    ///
+    /// let ids = Variable::create_array(vec![Variable::Int(1), Variable::Int(2)]).unwrap();
+    /// assert_eq!(ids.to_string(), "{1, 2}");
+    ///
+    /// assert!(Variable::create_array(vec![Variable::Int(1), Variable::from("a".to_string())]).is_err());
+    /// ```
+    pub fn create_array(values: Vec<Variable>) -> Result<Variable, GeneratorError> {
+        let mut non_null_values = values.iter().filter(|value| !matches!(value, Variable::Null));
+        if let Some(first) = non_null_values.next() {
+            let first_type = std::mem::discriminant(first);
+            if non_null_values.any(|value| std::mem::discriminant(value) != first_type) {
+                return Err(GeneratorError::InvalidInputError(
+                    "Array elements should have the same type but input has mixed types.".to_string()))
+            }
+        }
+        Ok(Variable::Array(values))
+    }
+
+    /// Creates an enum variable from a Rust enum mapped by `PgEnum`.
+    pub fn from_enum<E: PgEnum>(value: &E) -> Variable {
+        Variable::Enum(EnumLabel::from_enum(value))
+    }
+
+    /// Fits a decimal variable into a `NUMERIC(precision, scale)` column before binding.
+    ///
+    /// PostgreSQL silently rounds the fraction of a value bound to a `NUMERIC` column, so use
+    /// `NumericMode::Strict` for values like money where the truncation should be an error.
+    ///
+    /// # Example
+    /// ```rust
+    /// use rust_decimal::Decimal;
+    /// use safety_postgres::types::numeric::NumericMode;
+    /// use safety_postgres::Variable;
+    ///
+    /// let price = Variable::from(Decimal::new(12345, 3));
+    /// assert_eq!(price.to_numeric(10, 2, NumericMode::Round).unwrap().to_string(), "12.35");
+    /// assert!(price.to_numeric(10, 2, NumericMode::Strict).is_err());
+    /// assert!(price.to_numeric(3, 2, NumericMode::Round).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError::InvalidInputError` if the variable isn't `Decimal`, the value overflows
+    /// the precision, or the value would be truncated in `NumericMode::Strict`.
+    pub fn to_numeric(&self, precision: u32, scale: u32, mode: NumericMode) -> Result<Variable, GeneratorError> {
+        match self {
+            Variable::Decimal(value) => Ok(Variable::Decimal(fit_numeric(*value, precision, scale, mode)?)),
+            _ => Err(GeneratorError::InvalidInputError(
+                format!("'{}' is not a decimal variable.", self))),
+        }
+    }
 }
 
 impl From<String> for Variable {
@@ -50,6 +154,12 @@ impl From<String> for Variable {
     }
 }
 
+impl From<&str> for Variable {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
 impl From<i16> for Variable {
     fn from(value: i16) -> Self {
         Self::SmallInt(value)
@@ -98,6 +208,18 @@ impl From<NaiveDateTime> for Variable {
     }
 }
 
+impl From<DateTime<FixedOffset>> for Variable {
+    fn from(value: DateTime<FixedOffset>) -> Self {
+        Self::DateTimeTz(value)
+    }
+}
+
+impl From<DateTime<Utc>> for Variable {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self::DateTimeTz(value.fixed_offset())
+    }
+}
+
 impl From<NaiveTime> for Variable {
     fn from(value: NaiveTime) -> Self {
         Self::Time(value)
@@ -110,10 +232,66 @@ impl From<bool> for Variable {
     }
 }
 
+impl From<Interval> for Variable {
+    fn from(value: Interval) -> Self {
+        Self::Interval(value)
+    }
+}
+
+impl From<IpAddr> for Variable {
+    fn from(value: IpAddr) -> Self {
+        Self::Inet(IpInet::new_host(value))
+    }
+}
+
+impl From<IpInet> for Variable {
+    fn from(value: IpInet) -> Self {
+        Self::Inet(value)
+    }
+}
+
+impl From<IpCidr> for Variable {
+    fn from(value: IpCidr) -> Self {
+        Self::Cidr(value)
+    }
+}
+
+impl From<MacAddress> for Variable {
+    fn from(value: MacAddress) -> Self {
+        Self::MacAddr(value)
+    }
+}
+
+impl From<Range> for Variable {
+    fn from(value: Range) -> Self {
+        Self::Range(Box::new(value))
+    }
+}
+
+/// The elements are checked by `Variable::create_array`, so the conversion fails if they have mixed types.
+impl<T: Into<Variable>> TryFrom<Vec<T>> for Variable {
+    type Error = GeneratorError;
+
+    fn try_from(value: Vec<T>) -> Result<Self, Self::Error> {
+        Self::create_array(value.into_iter().map(|element| element.into()).collect())
+    }
+}
+
+/// `None` is converted into `Variable::Null`.
+impl<T: Into<Variable>> From<Option<T>> for Variable {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => Self::Null,
+        }
+    }
+}
+
 impl Display for Variable {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Variable::Text(value) => write!(f, "{}", value),
+            Variable::Secret(_) => write!(f, "****"),
             Variable::SmallInt(value) => write!(f, "{}", value),
             Variable::Int(value) => write!(f, "{}", value),
             Variable::BigInt(value) => write!(f, "{}", value),
@@ -122,8 +300,55 @@ impl Display for Variable {
             Variable::Decimal(value) => write!(f, "{}", value),
             Variable::Date(value) => write!(f, "{}", value),
             Variable::DateTime(value) => write!(f, "{}", value),
+            Variable::DateTimeTz(value) => write!(f, "{}", value.to_rfc3339()),
             Variable::Time(value) => write!(f, "{}", value),
             Variable::Bool(value) => write!(f, "{}", value),
+            Variable::Interval(value) => write!(f, "{}", value),
+            Variable::Inet(value) => write!(f, "{}", value),
+            Variable::Cidr(value) => write!(f, "{}", value),
+            Variable::MacAddr(value) => write!(f, "{}", value.to_hex_string()),
+            Variable::Enum(value) => write!(f, "{}", value),
+            Variable::Range(value) => write!(f, "{}", value),
+            Variable::Array(values) => {
+                let elements = values
+                    .iter()
+                    .map(|value| format!("{}", value))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(f, "{{{}}}", elements)
+            },
+            Variable::Null => write!(f, "NULL"),
+            Variable::Custom(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// `Debug` is implemented by hand so that `Variable::Secret` is redacted in the debug output as well.
+impl Debug for Variable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variable::Text(value) => f.debug_tuple("Text").field(value).finish(),
+            Variable::Secret(_) => f.debug_tuple("Secret").field(&format_args!("****")).finish(),
+            Variable::SmallInt(value) => f.debug_tuple("SmallInt").field(value).finish(),
+            Variable::Int(value) => f.debug_tuple("Int").field(value).finish(),
+            Variable::BigInt(value) => f.debug_tuple("BigInt").field(value).finish(),
+            Variable::Float(value) => f.debug_tuple("Float").field(value).finish(),
+            Variable::Double(value) => f.debug_tuple("Double").field(value).finish(),
+            Variable::Decimal(value) => f.debug_tuple("Decimal").field(value).finish(),
+            Variable::Date(value) => f.debug_tuple("Date").field(value).finish(),
+            Variable::DateTime(value) => f.debug_tuple("DateTime").field(value).finish(),
+            Variable::DateTimeTz(value) => f.debug_tuple("DateTimeTz").field(value).finish(),
+            Variable::Time(value) => f.debug_tuple("Time").field(value).finish(),
+            Variable::Bool(value) => f.debug_tuple("Bool").field(value).finish(),
+            Variable::Interval(value) => f.debug_tuple("Interval").field(value).finish(),
+            Variable::Inet(value) => f.debug_tuple("Inet").field(value).finish(),
+            Variable::Cidr(value) => f.debug_tuple("Cidr").field(value).finish(),
+            Variable::MacAddr(value) => f.debug_tuple("MacAddr").field(value).finish(),
+            Variable::Enum(value) => f.debug_tuple("Enum").field(value).finish(),
+            Variable::Range(value) => f.debug_tuple("Range").field(value).finish(),
+            Variable::Array(value) => f.debug_tuple("Array").field(value).finish(),
+            Variable::Null => write!(f, "Null"),
+            Variable::Custom(value) => f.debug_tuple("Custom").field(value).finish(),
         }
     }
 }
@@ -161,6 +386,10 @@ impl <'a> Column<'a> {
         self.table.get_table_name()
     }
 
+    pub(crate) fn get_column_name(&self) -> &str {
+        self.column_name
+    }
+
     pub(crate) fn get_parameter_num(&self) -> u16 {
         self.table.get_parameter_num()
     }
@@ -175,7 +404,7 @@ impl <'a> Column<'a> {
 
 impl Display for Column<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}.{}", self.table.get_table_name(), self.column_name)
+        write!(f, "{}.{}", self.table.get_table_name(), quote_identifier(self.column_name))
     }
 }
 
@@ -210,16 +439,53 @@ impl <'a> Table<'a> {
         }
     }
 
+    /// Returns the table name used in the SQL, quoting the names which need it.
     pub(crate) fn get_table_name(&self) -> String {
+        match self {
+            Table::WithSchema {
+                schema_name,
+                table_name } => format!("{}.{}", quote_identifier(schema_name), quote_identifier(table_name)),
+            Table::NonSchema { table_name } => quote_identifier(table_name),
+            Table::SubQueryAsTable(_) => "sub_query".to_string()
+        }
+    }
+
+    /// Returns the table name as it's given, without quoting (e.g. for the registry lookups).
+    pub(crate) fn get_plain_table_name(&self) -> String {
         match self {
             Table::WithSchema {
                 schema_name,
                 table_name } => format!("{}.{}", schema_name, table_name),
-            Table::NonSchema { table_name } => format!("{}", table_name),
+            Table::NonSchema { table_name } => table_name.to_string(),
             Table::SubQueryAsTable(_) => "sub_query".to_string()
         }
     }
 
+    /// Validates the schema and table names with the current `IdentifierPolicy`.
+    /// A sub query is always valid here because its tables are validated on their own.
+    pub(crate) fn validate_identifiers(&self) -> Result<(), GeneratorError> {
+        let names = match self {
+            Self::WithSchema { schema_name, table_name } => vec![*schema_name, *table_name],
+            Self::NonSchema { table_name } => vec![*table_name],
+            Self::SubQueryAsTable(_) => Vec::new(),
+        };
+        for name in names {
+            if !validate_identifier(name) {
+                return Err(GeneratorError::InvalidTableNameError(
+                    format!("'{}' has invalid characters. The identifier policy allows {}.", name, get_identifier_policy().get_rule())))
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the actual tables of this table, resolving a sub query to the tables it reads.
+    pub(crate) fn get_referenced_tables(&self) -> Vec<Table<'_>> {
+        match self {
+            Self::NonSchema { .. } | Self::WithSchema { .. } => vec![self.clone()],
+            Self::SubQueryAsTable(query) => query.get_referenced_tables(),
+        }
+    }
+
     pub(crate) fn get_parameter_num(&self) -> u16 {
         match self {
             Self::NonSchema { .. } | Self::WithSchema { .. } => 0,
@@ -238,10 +504,7 @@ impl <'a> Table<'a> {
 impl Display for Table<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Table::WithSchema {
-                schema_name,
-                table_name } => write!(f, "{}.{}", schema_name, table_name),
-            Table::NonSchema { table_name } => write!(f, "{}", table_name),
+            Table::WithSchema { .. } | Table::NonSchema { .. } => write!(f, "{}", self.get_table_name()),
             Table::SubQueryAsTable(query_generator) => write!(f, "({}) AS sub_query", query_generator.get_statement()),
         }
     }
@@ -271,7 +534,7 @@ impl <'a> Schema<'a> {
 
 impl Display for Schema<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.schema_name)
+        write!(f, "{}", quote_identifier(self.schema_name))
     }
 }
 
@@ -0,0 +1,80 @@
+//! Helpers for the integration tests running against a disposable PostgreSQL container.
+//!
+//! Requires the `test-support` feature and a running Docker daemon.
+
+use testcontainers::clients::Cli;
+use testcontainers::core::WaitFor;
+use testcontainers::{Container, GenericImage};
+use crate::connector::connection_config::ConnectionConfig;
+use crate::connector::Connector;
+use crate::generator::base::Parameters;
+use crate::utils::errors::ExecutorError;
+
+const IMAGE_NAME: &str = "postgres";
+const IMAGE_TAG: &str = "16.2-alpine3.19";
+const DB_USER: &str = "testuser";
+const DB_PASSWORD: &str = "testpassword";
+const DB_HOST: &str = "localhost";
+const DB_NAME: &str = "test";
+
+/// A PostgreSQL container started for a test. The container is removed when this is dropped.
+///
+/// # Example
+/// ```rust,no_run
+/// use safety_postgres::test_support::TestDatabase;
+/// use testcontainers::clients::Cli;
+///
+/// #[tokio::test]
+/// async fn test_users() {
+///     let docker = Cli::default();
+///     let database = TestDatabase::start(&docker);
+///     database.seed("CREATE TABLE users (id serial PRIMARY KEY, name text);").await.unwrap();
+///
+///     let connector = database.connect().await.unwrap();
+/// }
+/// ```
+pub struct TestDatabase<'d> {
+    container: Container<'d, GenericImage>,
+}
+
+impl<'d> TestDatabase<'d> {
+    /// Starts the container on `docker` and waits until it accepts the connections.
+    pub fn start(docker: &'d Cli) -> Self {
+        let image = GenericImage::new(IMAGE_NAME, IMAGE_TAG)
+            .with_wait_for(WaitFor::message_on_stderr("ready to accept connections"))
+            .with_env_var("POSTGRES_USER", DB_USER)
+            .with_env_var("POSTGRES_PASSWORD", DB_PASSWORD)
+            .with_env_var("POSTGRES_DB", DB_NAME);
+
+        Self {
+            container: docker.run(image),
+        }
+    }
+
+    /// Returns the config connecting to the database of the container.
+    pub fn get_config(&self) -> ConnectionConfig {
+        ConnectionConfig::set_config(DB_USER, DB_PASSWORD, DB_HOST, self.container.get_host_port_ipv4(5432), DB_NAME)
+    }
+
+    /// Connects to the database of the container.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the connection fails.
+    pub async fn connect(&self) -> Result<Connector, ExecutorError> {
+        Ok(Connector::connect(self.get_config()).await?)
+    }
+
+    /// Executes `sql` (e.g. the content of a seed file) which can contain multiple statements.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ExecutorError` if the connection or the execution fails.
+    pub async fn seed(&self, sql: &str) -> Result<(), ExecutorError> {
+        let connector = self.connect().await?;
+        match connector.get_client()?.batch_execute(sql).await {
+            Ok(()) => Ok(()),
+            Err(e) => Err(ExecutorError::from(e).with_statement_snapshot(Parameters::new().get_statement_snapshot(sql))),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod interval;
+pub mod range;
+pub mod pg_enum;
+pub mod custom;
+pub mod numeric;
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::sync::{Arc, OnceLock, RwLock};
+use tokio_postgres::types::{IsNull, Type};
+use bytes::BytesMut;
+
+/// Converter for a PostgreSQL type which is not covered by the built-in `Variable` variants,
+/// e.g. domain or composite types.
+///
+/// A value implementing this trait is bound with `Variable::Custom`, and a reader registered with
+/// `register_custom_type` makes columns of the type readable as `Variable::Custom`.
+///
+/// # Example
+/// ```rust
+/// use std::error::Error;
+/// use std::fmt::{Display, Formatter};
+/// use std::sync::Arc;
+/// use tokio_postgres::types::{IsNull, Type};
+/// use bytes::BytesMut;
+/// use safety_postgres::types::custom::{register_custom_type, CustomType};
+/// use safety_postgres::Variable;
+///
+/// #[derive(Debug)]
+/// struct Email(String);
+///
+/// impl Display for Email {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "{}", self.0)
+///     }
+/// }
+///
+/// impl CustomType for Email {
+///     fn type_name(&self) -> &str {
+///         "email"
+///     }
+///
+///     fn to_sql(&self, _ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+///         out.extend_from_slice(self.0.as_bytes());
+///         Ok(IsNull::No)
+///     }
+/// }
+///
+/// register_custom_type("email", |_ty, raw| {
+///     Ok(Arc::new(Email(String::from_utf8(raw.to_vec())?)))
+/// });
+///
+/// let variable = Variable::Custom(Arc::new(Email("user@example.com".to_string())));
+/// assert_eq!(variable.to_string(), "user@example.com");
+/// ```
+pub trait CustomType: Debug + Display + Send + Sync {
+    /// Returns the name of the PostgreSQL type this value is bound to.
+    fn type_name(&self) -> &str;
+
+    /// Writes the value in the binary format of the PostgreSQL type.
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>>;
+}
+
+/// Function reading the binary format of a registered PostgreSQL type.
+pub type CustomTypeReader = fn(&Type, &[u8]) -> Result<Arc<dyn CustomType>, Box<dyn Error + Sync + Send>>;
+
+fn registry() -> &'static RwLock<HashMap<String, CustomTypeReader>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, CustomTypeReader>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers a reader for the PostgreSQL type named `type_name`.
+///
+/// Registered readers take precedence over the built-in conversions, so registering a name
+/// which is already supported (e.g. `text`) replaces the built-in handling for that type.
+/// Registering the same name twice replaces the previous reader.
+pub fn register_custom_type(type_name: &str, reader: CustomTypeReader) {
+    registry()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(type_name.to_string(), reader);
+}
+
+pub(crate) fn get_custom_type_reader(type_name: &str) -> Option<CustomTypeReader> {
+    registry()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(type_name)
+        .copied()
+}
@@ -0,0 +1,85 @@
+use std::fmt::{Display, Formatter};
+use chrono::Duration;
+use crate::utils::errors::GeneratorError;
+
+/// Represents a PostgreSQL `INTERVAL` value.
+///
+/// PostgreSQL stores intervals as separate months, days and microseconds parts because
+/// the length of a month or a day is not fixed, so the parts are kept as is instead of
+/// being folded into a single `chrono::Duration`.
+///
+/// # Example
+/// ```rust
+/// use chrono::Duration;
+/// use safety_postgres::types::interval::Interval;
+///
+/// let interval = Interval::try_from(Duration::minutes(90)).unwrap();
+/// assert_eq!(interval.to_string(), "P0M0DT5400S");
+///
+/// let monthly = Interval::new(1, 0, 0);
+/// assert_eq!(monthly.to_duration(), None);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    microseconds: i64,
+}
+
+impl Interval {
+    pub fn new(months: i32, days: i32, microseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    pub fn get_months(&self) -> i32 {
+        self.months
+    }
+
+    pub fn get_days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn get_microseconds(&self) -> i64 {
+        self.microseconds
+    }
+
+    /// Converts the interval to `chrono::Duration`.
+    ///
+    /// Returns `None` when the interval has a months part because a month has no fixed length.
+    pub fn to_duration(&self) -> Option<Duration> {
+        if self.months != 0 {
+            return None
+        }
+        Some(Duration::days(self.days as i64) + Duration::microseconds(self.microseconds))
+    }
+}
+
+impl TryFrom<Duration> for Interval {
+    type Error = GeneratorError;
+
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        match value.num_microseconds() {
+            Some(microseconds) => Ok(Self::new(0, 0, microseconds)),
+            None => Err(GeneratorError::InvalidInputError(
+                format!("'{}' overflows the microseconds range of the interval.", value))),
+        }
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let seconds = self.microseconds / 1_000_000;
+        let fraction = (self.microseconds % 1_000_000).abs();
+        if fraction == 0 {
+            write!(f, "P{}M{}DT{}S", self.months, self.days, seconds)
+        }
+        else {
+            let sign = if self.microseconds < 0 && seconds == 0 { "-" } else { "" };
+            write!(f, "P{}M{}DT{}{}.{:06}S", self.months, self.days, sign, seconds, fraction)
+        }
+    }
+}
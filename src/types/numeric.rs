@@ -0,0 +1,48 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use crate::utils::errors::GeneratorError;
+
+/// How a decimal is fitted into a `NUMERIC(precision, scale)` column.
+///
+/// # Variants
+///
+/// - `Round`: Rounds the fraction to the column's scale (half away from zero, same as PostgreSQL).
+/// - `Strict`: Returns an error if the value has more fractional digits than the column's scale.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum NumericMode {
+    Round,
+    Strict,
+}
+
+/// Fits `value` into `NUMERIC(precision, scale)`.
+///
+/// The returned decimal always has exactly `scale` fractional digits.
+/// Both modes return an error when the integer part doesn't fit into `precision - scale` digits
+/// because PostgreSQL rejects such a value with a numeric field overflow.
+pub(crate) fn fit_numeric(value: Decimal, precision: u32, scale: u32, mode: NumericMode) -> Result<Decimal, GeneratorError> {
+    if precision == 0 || scale > precision {
+        return Err(GeneratorError::InvalidInputError(
+            format!("NUMERIC({}, {}) is invalid. The precision should be positive and not less than the scale.", precision, scale)))
+    }
+    if scale > Decimal::MAX_SCALE {
+        return Err(GeneratorError::InvalidInputError(
+            format!("The scale '{}' exceeds the maximum scale '{}' of the decimal.", scale, Decimal::MAX_SCALE)))
+    }
+
+    let mut rounded = value.round_dp_with_strategy(scale, RoundingStrategy::MidpointAwayFromZero);
+    if mode == NumericMode::Strict && rounded != value {
+        return Err(GeneratorError::InvalidInputError(
+            format!("'{}' would be truncated to '{}' by NUMERIC({}, {}).", value, rounded, precision, scale)))
+    }
+
+    let integer_digits = precision - scale;
+    if integer_digits <= Decimal::MAX_SCALE {
+        let limit = Decimal::from_i128_with_scale(10i128.pow(integer_digits), 0);
+        if rounded.abs() >= limit {
+            return Err(GeneratorError::InvalidInputError(
+                format!("'{}' overflows NUMERIC({}, {}).", value, precision, scale)))
+        }
+    }
+
+    rounded.rescale(scale);
+    Ok(rounded)
+}
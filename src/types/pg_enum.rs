@@ -0,0 +1,115 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::utils::errors::GeneratorError;
+
+/// Maps a Rust enum to a PostgreSQL enum type.
+///
+/// The mapping is text based by default: the label is the `Display` output and is parsed back with `FromStr`.
+/// Override `to_label`/`from_label` when the Postgres labels differ from the Rust representation,
+/// and `type_name` to make binding fail on a column with another enum type.
+///
+/// # Example
+/// ```rust
+/// use std::fmt::{Display, Formatter};
+/// use std::str::FromStr;
+/// use safety_postgres::types::pg_enum::PgEnum;
+/// use safety_postgres::Variable;
+///
+/// enum Status { Active, Closed }
+///
+/// impl Display for Status {
+///     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+///         match self {
+///             Status::Active => write!(f, "active"),
+///             Status::Closed => write!(f, "closed"),
+///         }
+///     }
+/// }
+///
+/// impl FromStr for Status {
+///     type Err = String;
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         match s {
+///             "active" => Ok(Status::Active),
+///             "closed" => Ok(Status::Closed),
+///             _ => Err(s.to_string()),
+///         }
+///     }
+/// }
+///
+/// impl PgEnum for Status {
+///     fn type_name() -> Option<&'static str> { Some("status") }
+/// }
+///
+/// let variable = Variable::from_enum(&Status::Active);
+/// assert_eq!(variable.to_string(), "active");
+/// ```
+pub trait PgEnum: Display + FromStr {
+    /// Returns the name of the PostgreSQL enum type. `None` accepts any enum or text type.
+    fn type_name() -> Option<&'static str> {
+        None
+    }
+
+    fn to_label(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        label.parse::<Self>().ok()
+    }
+}
+
+/// Represents a label of a PostgreSQL enum value.
+///
+/// This is the form that `Variable::Enum` binds and that enum columns are extracted as.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumLabel {
+    type_name: Option<String>,
+    label: String,
+}
+
+impl EnumLabel {
+    pub fn new(type_name: Option<&str>, label: &str) -> Self {
+        Self {
+            type_name: type_name.map(|name| name.to_string()),
+            label: label.to_string(),
+        }
+    }
+
+    pub fn from_enum<E: PgEnum>(value: &E) -> Self {
+        Self::new(E::type_name(), value.to_label().as_str())
+    }
+
+    pub fn get_type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+
+    pub fn get_label(&self) -> &str {
+        self.label.as_str()
+    }
+
+    /// Converts the label into the Rust enum.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError::InvalidInputError` if the type name or the label doesn't match the enum.
+    pub fn to_enum<E: PgEnum>(&self) -> Result<E, GeneratorError> {
+        if let (Some(expected), Some(actual)) = (E::type_name(), self.get_type_name()) {
+            if expected != actual {
+                return Err(GeneratorError::InvalidInputError(
+                    format!("'{}' enum type is expected but got '{}'.", expected, actual)))
+            }
+        }
+        match E::from_label(self.label.as_str()) {
+            Some(value) => Ok(value),
+            None => Err(GeneratorError::InvalidInputError(
+                format!("'{}' is not a label of the enum.", self.label))),
+        }
+    }
+}
+
+impl Display for EnumLabel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
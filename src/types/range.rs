@@ -0,0 +1,96 @@
+use std::fmt::{Display, Formatter};
+use crate::utils::errors::GeneratorError;
+use crate::Variable;
+
+/// Represents one bound of a PostgreSQL range value.
+///
+/// # Variants
+///
+/// - `Inclusive(Variable)`: The bound value is contained in the range (`[` or `]`).
+/// - `Exclusive(Variable)`: The bound value is not contained in the range (`(` or `)`).
+/// - `Unbounded`: The range has no limit at this side.
+#[derive(Clone, Debug)]
+pub enum RangeBound {
+    Inclusive(Variable),
+    Exclusive(Variable),
+    Unbounded,
+}
+
+impl RangeBound {
+    pub(crate) fn get_value(&self) -> Option<&Variable> {
+        match self {
+            Self::Inclusive(value) | Self::Exclusive(value) => Some(value),
+            Self::Unbounded => None,
+        }
+    }
+}
+
+/// Represents a PostgreSQL range value like `int4range`, `int8range`, `numrange`, `tsrange`,
+/// `tstzrange` or `daterange`.
+///
+/// # Example
+/// ```rust
+/// use chrono::NaiveDate;
+/// use safety_postgres::types::range::{Range, RangeBound};
+/// use safety_postgres::Variable;
+///
+/// let booking = Range::new(
+///     RangeBound::Inclusive(Variable::from(NaiveDate::from_ymd_opt(2024, 4, 1).unwrap())),
+///     RangeBound::Exclusive(Variable::from(NaiveDate::from_ymd_opt(2024, 4, 5).unwrap())),
+/// ).unwrap();
+///
+/// assert_eq!(booking.to_string(), "[2024-04-01,2024-04-05)");
+/// ```
+#[derive(Clone, Debug)]
+pub enum Range {
+    Empty,
+    NonEmpty {
+        lower: RangeBound,
+        upper: RangeBound,
+    },
+}
+
+impl Range {
+    /// Creates a non-empty range after validating that both bounds have the same type.
+    pub fn new(lower: RangeBound, upper: RangeBound) -> Result<Self, GeneratorError> {
+        if let (Some(lower_value), Some(upper_value)) = (lower.get_value(), upper.get_value()) {
+            if std::mem::discriminant(lower_value) != std::mem::discriminant(upper_value) {
+                return Err(GeneratorError::InvalidInputError(
+                    format!("Range bounds should have the same type but got '{}' and '{}'.", lower_value, upper_value)))
+            }
+        }
+        Ok(Self::NonEmpty { lower, upper })
+    }
+
+    pub fn empty() -> Self {
+        Self::Empty
+    }
+
+    pub fn get_bounds(&self) -> Option<(&RangeBound, &RangeBound)> {
+        match self {
+            Self::Empty => None,
+            Self::NonEmpty { lower, upper } => Some((lower, upper)),
+        }
+    }
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty"),
+            Self::NonEmpty { lower, upper } => {
+                match lower {
+                    RangeBound::Inclusive(value) => write!(f, "[{}", value)?,
+                    RangeBound::Exclusive(value) => write!(f, "({}", value)?,
+                    RangeBound::Unbounded => write!(f, "(")?,
+                }
+                write!(f, ",")?;
+                match upper {
+                    RangeBound::Inclusive(value) => write!(f, "{}]", value),
+                    RangeBound::Exclusive(value) => write!(f, "{})", value),
+                    RangeBound::Unbounded => write!(f, ")"),
+                }
+            }
+        }
+    }
+}
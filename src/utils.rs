@@ -1,4 +1,7 @@
 pub mod errors;
 mod json_parser;
 mod sql_parser;
-pub mod helpers;
\ No newline at end of file
+pub mod helpers;
+pub mod identifier;
+pub(crate) mod literal;
+pub(crate) mod logging;
\ No newline at end of file
@@ -1,5 +1,8 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use tokio_postgres::error::{DbError, SqlState};
+use tokio_postgres::Error as PGError;
 
 pub trait ErrorGenerator<E: Error> {
     fn generate_error(&self, msg: String) -> E;
@@ -10,6 +13,8 @@ pub enum ConnectionConfigError {
     TypeError(String),
     UndefinedValueError(String),
     ConnectionFailedError(String),
+    InvalidFileError(String),
+    InvalidParameterError(String),
 }
 
 impl Display for ConnectionConfigError {
@@ -17,7 +22,9 @@ impl Display for ConnectionConfigError {
         match self {
             Self::TypeError(e) => write!(f, "TypeError occurred due to {}", e),
             Self::UndefinedValueError(e) => write!(f, "Undefined value referred due to {}", e),
-            Self::ConnectionFailedError(e) => write!(f, "Connection to PostgreSQL failed due to {}", e)
+            Self::ConnectionFailedError(e) => write!(f, "Connection to PostgreSQL failed due to {}", e),
+            Self::InvalidFileError(e) => write!(f, "Config file is invalid due to {}", e),
+            Self::InvalidParameterError(e) => write!(f, "Session parameter is invalid due to {}", e),
         }
     }
 }
@@ -42,3 +49,281 @@ impl Display for GeneratorError {
 }
 
 impl Error for GeneratorError {}
+
+#[derive(Debug, PartialEq)]
+pub enum ExecutorError {
+    ConnectionNotFoundError(String),
+    SQLExecutionError(String),
+    ConvertRowError(String),
+    ExportError(String),
+    UnsafeExecutionError(String),
+    DatabaseError(DatabaseError),
+    ConnectionClosedError(String),
+}
+
+impl ExecutorError {
+    /// Returns the error reported by the PostgreSQL server, if this is one.
+    pub fn get_database_error(&self) -> Option<&DatabaseError> {
+        match self {
+            Self::DatabaseError(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the execution failed due to a unique constraint.
+    pub fn is_unique_violation(&self) -> bool {
+        self.get_database_error().is_some_and(DatabaseError::is_unique_violation)
+    }
+
+    /// Returns whether the execution failed due to a foreign key constraint.
+    pub fn is_foreign_key_violation(&self) -> bool {
+        self.get_database_error().is_some_and(DatabaseError::is_foreign_key_violation)
+    }
+
+    /// Returns the name of the violated constraint, if any.
+    pub fn constraint_name(&self) -> Option<&str> {
+        self.get_database_error().and_then(DatabaseError::constraint_name)
+    }
+
+    /// Returns whether the same execution may succeed when it's retried (see `DatabaseError::is_retryable`).
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::ConnectionClosedError(_) => true,
+            Self::DatabaseError(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Attaches the snapshot of the failed statement to the server error or the execution error message.
+    pub(crate) fn with_statement_snapshot(self, snapshot: StatementSnapshot) -> Self {
+        match self {
+            Self::DatabaseError(e) => Self::DatabaseError(e.with_statement_snapshot(snapshot)),
+            Self::SQLExecutionError(e) => Self::SQLExecutionError(format!("{} while executing {}", e, snapshot)),
+            Self::ConnectionClosedError(e) => Self::ConnectionClosedError(format!("{} while executing {}", e, snapshot)),
+            other => other,
+        }
+    }
+}
+
+impl From<PGError> for ExecutorError {
+    fn from(value: PGError) -> Self {
+        match DatabaseError::from_error(value) {
+            Ok(e) => Self::DatabaseError(e),
+            Err(e) if e.is_closed() => Self::ConnectionClosedError(e.to_string()),
+            Err(e) => Self::SQLExecutionError(e.to_string()),
+        }
+    }
+}
+
+impl Display for ExecutorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConnectionNotFoundError(e) => write!(f, "Connection is not found due to {}", e),
+            Self::SQLExecutionError(e) => write!(f, "SQL execution failed due to {}", e),
+            Self::ConvertRowError(e) => write!(f, "Converting the row failed due to {}", e),
+            Self::ExportError(e) => write!(f, "Exporting the result failed due to {}", e),
+            Self::UnsafeExecutionError(e) => write!(f, "Unsafe SQL execution is detected from {}.", e),
+            Self::DatabaseError(e) => write!(f, "SQL execution failed due to {}", e),
+            Self::ConnectionClosedError(e) => write!(f, "Connection was closed due to {}", e),
+        }
+    }
+}
+
+impl Error for ExecutorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::DatabaseError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Error reported by the PostgreSQL server, keeping the SQLSTATE and the details of the original error
+/// so the callers can branch on the kind of the error instead of parsing the message.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::utils::errors::DatabaseError;
+///
+/// fn describe(error: &DatabaseError) -> String {
+///     if error.is_unique_violation() {
+///         format!("duplicated on {}", error.constraint_name().unwrap_or("unknown constraint"))
+///     } else {
+///         format!("SQLSTATE {}", error.code().code())
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DatabaseError {
+    error: Arc<PGError>,
+    statement_snapshot: Option<StatementSnapshot>,
+}
+
+impl DatabaseError {
+    /// Wraps `error` if it was reported by the server, otherwise returns it back.
+    pub(crate) fn from_error(error: PGError) -> Result<Self, PGError> {
+        if error.as_db_error().is_none() {
+            return Err(error)
+        }
+        Ok(Self {
+            error: Arc::new(error),
+            statement_snapshot: None,
+        })
+    }
+
+    pub(crate) fn with_statement_snapshot(mut self, statement_snapshot: StatementSnapshot) -> Self {
+        self.statement_snapshot = Some(statement_snapshot);
+        self
+    }
+
+    /// Returns the snapshot of the failed statement, if it's known.
+    pub fn get_statement_snapshot(&self) -> Option<&StatementSnapshot> {
+        self.statement_snapshot.as_ref()
+    }
+
+    fn get_db_error(&self) -> &DbError {
+        self.error.as_db_error().expect("DatabaseError is created only from an error reported by the server")
+    }
+
+    /// Returns the SQLSTATE of the error.
+    pub fn code(&self) -> &SqlState {
+        self.get_db_error().code()
+    }
+
+    /// Returns the primary message of the error.
+    pub fn message(&self) -> &str {
+        self.get_db_error().message()
+    }
+
+    /// Returns the detail message of the error, if any.
+    pub fn detail(&self) -> Option<&str> {
+        self.get_db_error().detail()
+    }
+
+    /// Returns the name of the violated constraint, if any.
+    pub fn constraint_name(&self) -> Option<&str> {
+        self.get_db_error().constraint()
+    }
+
+    /// Returns the name of the table the error relates to, if any.
+    pub fn table_name(&self) -> Option<&str> {
+        self.get_db_error().table()
+    }
+
+    /// Returns the name of the column the error relates to, if any.
+    pub fn column_name(&self) -> Option<&str> {
+        self.get_db_error().column()
+    }
+
+    pub fn is_unique_violation(&self) -> bool {
+        *self.code() == SqlState::UNIQUE_VIOLATION
+    }
+
+    pub fn is_foreign_key_violation(&self) -> bool {
+        *self.code() == SqlState::FOREIGN_KEY_VIOLATION
+    }
+
+    pub fn is_not_null_violation(&self) -> bool {
+        *self.code() == SqlState::NOT_NULL_VIOLATION
+    }
+
+    pub fn is_check_violation(&self) -> bool {
+        *self.code() == SqlState::CHECK_VIOLATION
+    }
+
+    /// Returns whether the execution can be retried: a connection exception (SQLSTATE class `08`),
+    /// a serialization failure, a deadlock or a shutdown of the server.
+    pub fn is_retryable(&self) -> bool {
+        let code = self.code();
+        code.code().starts_with("08")
+            || *code == SqlState::T_R_SERIALIZATION_FAILURE
+            || *code == SqlState::T_R_DEADLOCK_DETECTED
+            || *code == SqlState::ADMIN_SHUTDOWN
+            || *code == SqlState::CRASH_SHUTDOWN
+            || *code == SqlState::CANNOT_CONNECT_NOW
+    }
+}
+
+impl PartialEq for DatabaseError {
+    fn eq(&self, other: &Self) -> bool {
+        self.code() == other.code()
+            && self.message() == other.message()
+            && self.constraint_name() == other.constraint_name()
+    }
+}
+
+impl Display for DatabaseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (SQLSTATE {})", self.message(), self.code().code())?;
+        if let Some(statement_snapshot) = &self.statement_snapshot {
+            write!(f, " while executing {}", statement_snapshot)?;
+        }
+        Ok(())
+    }
+}
+
+/// The statement text and the redacted parameters of a failed execution, attached to the error
+/// so the failure can be investigated without logging all the queries.
+///
+/// Each parameter is written as its type and its value truncated to 32 characters,
+/// e.g. `Int(3)` or `Text(a very long text which is trun…)`. `Variable::Secret` values are masked.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatementSnapshot {
+    statement: String,
+    parameters: Vec<String>,
+}
+
+impl StatementSnapshot {
+    const MAX_VALUE_LENGTH: usize = 32;
+
+    /// Creates a snapshot from the statement and the pairs of the parameter type and value.
+    pub(crate) fn new(statement: &str, parameters: Vec<(String, String)>) -> Self {
+        let parameters = parameters
+            .into_iter()
+            .map(|(type_name, value)| format!("{}({})", type_name, Self::truncate(value.as_str())))
+            .collect();
+        Self {
+            statement: statement.to_string(),
+            parameters,
+        }
+    }
+
+    fn truncate(value: &str) -> String {
+        if value.chars().count() <= Self::MAX_VALUE_LENGTH {
+            return value.to_string()
+        }
+        let truncated: String = value.chars().take(Self::MAX_VALUE_LENGTH).collect();
+        format!("{}…", truncated)
+    }
+
+    /// Returns the statement text with the `$n` placeholders.
+    pub fn get_statement(&self) -> &str {
+        self.statement.as_str()
+    }
+
+    /// Returns the redacted parameters bound to the placeholders in order.
+    pub fn get_parameters(&self) -> &[String] {
+        &self.parameters
+    }
+}
+
+impl Display for StatementSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.statement)?;
+        if !self.parameters.is_empty() {
+            let parameters = self.parameters
+                .iter()
+                .enumerate()
+                .map(|(index, parameter)| format!("${} = {}", index + 1, parameter))
+                .collect::<Vec<String>>();
+            write!(f, " with [{}]", parameters.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for DatabaseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::{OnceLock, RwLock};
+use regex::Regex;
+use crate::utils::errors::GeneratorError;
+
+/// The keywords PostgreSQL reserves (including the ones which can be a function or type name),
+/// which can't be used as an identifier without quoting.
+const RESERVED_KEYWORDS: &[&str] = &[
+    "all", "analyse", "analyze", "and", "any", "array", "as", "asc", "asymmetric", "authorization",
+    "binary", "both", "case", "cast", "check", "collate", "collation", "column", "concurrently",
+    "constraint", "create", "cross", "current_catalog", "current_date", "current_role", "current_schema",
+    "current_time", "current_timestamp", "current_user", "default", "deferrable", "desc", "distinct",
+    "do", "else", "end", "except", "false", "fetch", "for", "foreign", "freeze", "from", "full", "grant",
+    "group", "having", "ilike", "in", "initially", "inner", "intersect", "into", "is", "isnull", "join",
+    "lateral", "leading", "left", "like", "limit", "localtime", "localtimestamp", "natural", "not",
+    "notnull", "null", "offset", "on", "only", "or", "order", "outer", "overlaps", "placing", "primary",
+    "references", "returning", "right", "select", "session_user", "similar", "some", "symmetric",
+    "system_user", "table", "tablesample", "then", "to", "trailing", "true", "union", "unique", "user",
+    "using", "variadic", "verbose", "when", "where", "window", "with",
+];
+
+/// Returns whether `name` is a keyword reserved by PostgreSQL (case-insensitive).
+pub fn is_reserved_keyword(name: &str) -> bool {
+    RESERVED_KEYWORDS.contains(&name.to_lowercase().as_str())
+}
+
+/// Quotes `name` as a PostgreSQL identifier if it can't be written as is.
+///
+/// A lowercase snake_case name which is not a reserved keyword is returned unchanged, so the generated SQL
+/// stays the same for the ordinary names. Any other name (e.g. `UserAccounts`, `order` or `my-table`)
+/// is wrapped in double quotes with the inner double quotes doubled, so it can't break out of the identifier.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::utils::identifier::quote_identifier;
+///
+/// assert_eq!(quote_identifier("user_accounts"), "user_accounts");
+/// assert_eq!(quote_identifier("UserAccounts"), "\"UserAccounts\"");
+/// assert_eq!(quote_identifier("order"), "\"order\"");
+/// assert_eq!(quote_identifier("a\"b"), "\"a\"\"b\"");
+/// ```
+pub fn quote_identifier(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_plain = match chars.next() {
+        Some(first) => (first.is_ascii_lowercase() || first == '_')
+            && chars.all(|char| char.is_ascii_lowercase() || char.is_ascii_digit() || char == '_' || char == '$'),
+        None => false,
+    };
+    if is_plain && !is_reserved_keyword(name) {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+/// Quotes each part of a dot separated name like `schema.table` with `quote_identifier`.
+pub(crate) fn quote_qualified_identifier(name: &str) -> String {
+    name.split('.').map(quote_identifier).collect::<Vec<String>>().join(".")
+}
+
+/// Rule deciding which names are accepted as a schema, table or column name.
+///
+/// The policy is set once for the process with `set_identifier_policy` and is used by both the `legacy`
+/// module and the generator/executor, so a name is accepted or rejected the same way everywhere.
+/// An accepted name is still quoted by `quote_identifier` when it needs quoting.
+///
+/// A name containing `.` is rejected by every policy, since the qualified names (`schema.table`)
+/// are split on `.` and the name would silently target another schema.
+///
+/// # Variants
+///
+/// - `StrictAscii`: ASCII alphabets, numbers and `_` only (default).
+/// - `QuotedAnything`: Any non-empty name without a NUL character or `.`, e.g. `Straße` or `order items`.
+/// - `Custom`: Names matching the whole of the regex, created by `IdentifierPolicy::custom`.
+#[derive(Clone, Debug, Default)]
+pub enum IdentifierPolicy {
+    #[default]
+    StrictAscii,
+    QuotedAnything,
+    Custom(Regex),
+}
+
+impl IdentifierPolicy {
+    /// Creates a policy accepting the names which match `pattern` as a whole.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GeneratorError::InvalidInputError` if `pattern` isn't a valid regex.
+    ///
+    /// # Example
+    /// ```rust
+    /// use safety_postgres::utils::identifier::IdentifierPolicy;
+    ///
+    /// let policy = IdentifierPolicy::custom("[a-z][a-z0-9_]{0,62}").unwrap();
+    /// assert!(policy.is_valid("users"));
+    /// assert!(!policy.is_valid("Users"));
+    /// ```
+    pub fn custom(pattern: &str) -> Result<Self, GeneratorError> {
+        match Regex::new(format!("^(?:{})$", pattern).as_str()) {
+            Ok(regex) => Ok(Self::Custom(regex)),
+            Err(e) => Err(GeneratorError::InvalidInputError(
+                format!("'{}' is not a valid identifier pattern: {}", pattern, e))),
+        }
+    }
+
+    /// Returns whether `name` is accepted by this policy.
+    pub fn is_valid(&self, name: &str) -> bool {
+        if name.contains('.') {
+            return false
+        }
+        match self {
+            Self::StrictAscii => !name.is_empty() && name.chars().all(|char| char.is_ascii_alphanumeric() || char == '_'),
+            Self::QuotedAnything => !name.is_empty() && !name.contains('\0'),
+            Self::Custom(regex) => regex.is_match(name),
+        }
+    }
+
+    /// Returns the description of the accepted names for the error messages.
+    pub(crate) fn get_rule(&self) -> String {
+        match self {
+            Self::StrictAscii => "alphabets, numbers and under bar only".to_string(),
+            Self::QuotedAnything => "any characters except NUL and '.'".to_string(),
+            Self::Custom(regex) => format!("names matching '{}' only", regex.as_str()),
+        }
+    }
+}
+
+fn policy() -> &'static RwLock<IdentifierPolicy> {
+    static POLICY: OnceLock<RwLock<IdentifierPolicy>> = OnceLock::new();
+    POLICY.get_or_init(|| RwLock::new(IdentifierPolicy::default()))
+}
+
+/// Sets the `IdentifierPolicy` used by all the name validations of this crate.
+///
+/// # Example
+/// ```rust
+/// use safety_postgres::utils::identifier::{get_identifier_policy, set_identifier_policy, IdentifierPolicy};
+///
+/// set_identifier_policy(IdentifierPolicy::QuotedAnything);
+/// assert!(get_identifier_policy().is_valid("Straße"));
+/// # set_identifier_policy(IdentifierPolicy::StrictAscii);
+/// ```
+pub fn set_identifier_policy(identifier_policy: IdentifierPolicy) {
+    *policy().write().unwrap_or_else(|poisoned| poisoned.into_inner()) = identifier_policy;
+}
+
+/// Returns the current `IdentifierPolicy`.
+pub fn get_identifier_policy() -> IdentifierPolicy {
+    policy().read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+/// Returns whether `name` is accepted by the current `IdentifierPolicy`.
+pub fn validate_identifier(name: &str) -> bool {
+    policy().read().unwrap_or_else(|poisoned| poisoned.into_inner()).is_valid(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_reserved_keyword, quote_identifier, quote_qualified_identifier, IdentifierPolicy};
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("users"), "users");
+        assert_eq!(quote_identifier("_col1$"), "_col1$");
+        assert_eq!(quote_identifier("1st"), "\"1st\"");
+        assert_eq!(quote_identifier("Order"), "\"Order\"");
+        assert_eq!(quote_identifier("user"), "\"user\"");
+        assert_eq!(quote_identifier("名前"), "\"名前\"");
+        assert_eq!(quote_identifier(""), "\"\"");
+        assert!(is_reserved_keyword("SELECT"));
+        assert!(!is_reserved_keyword("username"));
+
+        assert_eq!(quote_qualified_identifier("public.UserAccounts"), "public.\"UserAccounts\"");
+        assert_eq!(quote_qualified_identifier("users"), "users");
+    }
+
+    #[test]
+    fn test_identifier_policy() {
+        let strict = IdentifierPolicy::StrictAscii;
+        assert!(strict.is_valid("User_Accounts1"));
+        assert!(!strict.is_valid("Straße"));
+        assert!(!strict.is_valid("user-accounts"));
+        assert!(!strict.is_valid(""));
+
+        let quoted = IdentifierPolicy::QuotedAnything;
+        assert!(quoted.is_valid("Straße"));
+        assert!(quoted.is_valid("order items"));
+        assert!(!quoted.is_valid("a\0b"));
+        assert!(!quoted.is_valid("my.table"));
+
+        let custom = IdentifierPolicy::custom("[a-z]+|tmp_[0-9]+").unwrap();
+        assert!(custom.is_valid("users"));
+        assert!(custom.is_valid("tmp_12"));
+        assert!(!custom.is_valid("users_tmp_12"));
+        assert!(!IdentifierPolicy::custom(".+").unwrap().is_valid("my.table"));
+        assert!(IdentifierPolicy::custom("[a-z").is_err());
+    }
+}
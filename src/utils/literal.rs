@@ -0,0 +1,152 @@
+use crate::generator::base::Parameters;
+use crate::utils::errors::GeneratorError;
+use crate::Variable;
+
+/// Returns `text` as an SQL string literal, doubling the single quotes.
+///
+/// A text containing backslashes is rendered as an escape string (`E'...'`) with the backslashes doubled,
+/// like `quote_literal` of PostgreSQL, so the literal is read the same way whether `standard_conforming_strings` is on or off.
+pub(crate) fn quote_literal(text: &str) -> String {
+    if text.contains('\\') {
+        format!("E'{}'", text.replace('\\', "\\\\").replace('\'', "''"))
+    } else {
+        format!("'{}'", text.replace('\'', "''"))
+    }
+}
+
+/// Renders `variable` as an SQL literal, for the statements which can't take the parameters (e.g. DDL).
+///
+/// The literals are typed with a cast where the type can't be inferred from the text (e.g. `'2024-01-01'::date`).
+/// A `Variable::Secret` is rendered as `'****'`, so the secrets never appear in a rendered statement.
+///
+/// # Errors
+///
+/// Returns `GeneratorError::InvalidInputError` for a custom type, which has no known text representation.
+pub(crate) fn to_literal(variable: &Variable) -> Result<String, GeneratorError> {
+    let literal = match variable {
+        Variable::Text(value) => quote_literal(value),
+        Variable::Secret(_) => "'****'".to_string(),
+        Variable::SmallInt(value) => value.to_string(),
+        Variable::Int(value) => value.to_string(),
+        Variable::BigInt(value) => value.to_string(),
+        Variable::Float(value) => float_literal(*value as f64, "float4"),
+        Variable::Double(value) => float_literal(*value, "float8"),
+        Variable::Decimal(value) => value.to_string(),
+        Variable::Date(value) => format!("{}::date", quote_literal(value.to_string().as_str())),
+        Variable::DateTime(value) => format!("{}::timestamp", quote_literal(value.to_string().as_str())),
+        Variable::DateTimeTz(value) => format!("{}::timestamptz", quote_literal(value.to_rfc3339().as_str())),
+        Variable::Time(value) => format!("{}::time", quote_literal(value.to_string().as_str())),
+        Variable::Bool(value) => value.to_string().to_uppercase(),
+        Variable::Interval(value) => format!("{}::interval", quote_literal(value.to_string().as_str())),
+        Variable::Inet(value) => format!("{}::inet", quote_literal(value.to_string().as_str())),
+        Variable::Cidr(value) => format!("{}::cidr", quote_literal(value.to_string().as_str())),
+        Variable::MacAddr(value) => format!("{}::macaddr", quote_literal(value.to_hex_string().as_str())),
+        Variable::Enum(value) => quote_literal(value.to_string().as_str()),
+        Variable::Range(value) => quote_literal(value.to_string().as_str()),
+        Variable::Array(values) if values.is_empty() => "'{}'".to_string(),
+        Variable::Array(values) => {
+            let elements = values
+                .iter()
+                .map(to_literal)
+                .collect::<Result<Vec<String>, GeneratorError>>()?;
+            format!("ARRAY[{}]", elements.join(", "))
+        },
+        Variable::Null => "NULL".to_string(),
+        Variable::Custom(value) => return Err(GeneratorError::InvalidInputError(
+            format!("a value of the custom type '{}' can't be rendered as a literal.", value.type_name()))),
+    };
+    Ok(literal)
+}
+
+/// Replaces the placeholders (`$1`, `$2`, ...) of `statement` with the literals of `parameters`.
+///
+/// The placeholders in the quoted identifiers and the string literals are left as they are.
+///
+/// # Errors
+///
+/// Returns `GeneratorError::InconsistentConfigError` if a placeholder has no parameter,
+/// or `GeneratorError::InvalidInputError` if a parameter can't be rendered as a literal.
+pub(crate) fn inline_parameters(statement: &str, parameters: &Parameters) -> Result<String, GeneratorError> {
+    let variables = parameters.get_variables();
+    let mut inlined = String::with_capacity(statement.len());
+    let mut quote: Option<char> = None;
+    let mut chars = statement.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(open), _) if open == c => quote = None,
+            (None, '$') if chars.peek().is_some_and(char::is_ascii_digit) => {
+                let mut number = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    number.push(digit);
+                }
+                let variable = number
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|number| number.checked_sub(1))
+                    .and_then(|index| variables.get(index));
+                match variable {
+                    Some(variable) => inlined.push_str(to_literal(variable)?.as_str()),
+                    None => return Err(GeneratorError::InconsistentConfigError(
+                        format!("the placeholder '${}' has no parameter.", number))),
+                }
+                continue
+            },
+            _ => {},
+        }
+        inlined.push(c);
+    }
+    Ok(inlined)
+}
+
+fn float_literal(value: f64, type_name: &str) -> String {
+    if value.is_finite() {
+        return value.to_string()
+    }
+    let text = if value.is_nan() { "NaN" } else if value > 0.0 { "Infinity" } else { "-Infinity" };
+    format!("'{}'::{}", text, type_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use crate::Variable;
+    use crate::generator::base::Parameters;
+    use super::{inline_parameters, quote_literal, to_literal};
+
+    #[test]
+    fn test_to_literal() {
+        assert_eq!(quote_literal("it's"), "'it''s'");
+        assert_eq!(quote_literal(r"C:\dir\' OR 1=1"), r"E'C:\\dir\\'' OR 1=1'");
+        assert_eq!(to_literal(&Variable::Text("O'Reilly".to_string())).unwrap(), "'O''Reilly'");
+        assert_eq!(to_literal(&Variable::Int(-3)).unwrap(), "-3");
+        assert_eq!(to_literal(&Variable::Double(f64::NAN)).unwrap(), "'NaN'::float8");
+        assert_eq!(to_literal(&Variable::Bool(true)).unwrap(), "TRUE");
+        assert_eq!(
+            to_literal(&Variable::Date(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())).unwrap(),
+            "'2024-01-31'::date");
+        assert_eq!(
+            to_literal(&Variable::Array(vec![Variable::Int(1), Variable::Null])).unwrap(),
+            "ARRAY[1, NULL]");
+        assert_eq!(to_literal(&Variable::Null).unwrap(), "NULL");
+        assert_eq!(to_literal(&Variable::Secret("password".to_string())).unwrap(), "'****'");
+
+        let parameters = Parameters::from(vec![Variable::Int(1), Variable::Text("a$1".to_string())]);
+        assert_eq!(
+            inline_parameters("SELECT \"$1\", '$2' FROM t WHERE a = $1 AND b = $2", &parameters).unwrap(),
+            "SELECT \"$1\", '$2' FROM t WHERE a = 1 AND b = 'a$1'");
+        assert!(inline_parameters("SELECT * FROM t WHERE a = $3", &parameters).is_err());
+    }
+
+    #[test]
+    fn test_inline_secret_parameters() {
+        let parameters = Parameters::from(vec![
+            Variable::Text("admin".to_string()),
+            Variable::Secret("password".to_string()),
+            Variable::Array(vec![Variable::Secret("token".to_string())])]);
+        assert_eq!(
+            inline_parameters("SELECT * FROM users WHERE name = $1 AND password = $2 AND token = ANY($3)", &parameters).unwrap(),
+            "SELECT * FROM users WHERE name = 'admin' AND password = '****' AND token = ANY(ARRAY['****'])");
+    }
+}
@@ -0,0 +1,56 @@
+// The events of this crate are emitted with `tracing` only when the `tracing` feature is enabled,
+// otherwise they are discarded, so the crate never writes to stdout/stderr by itself.
+
+/// Emits a `WARN` event with the `format!` style message.
+macro_rules! warn_event {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+/// Emits a `DEBUG` event of an executed statement with the `statement`, `rows` and `elapsed_ms` fields.
+macro_rules! statement_event {
+    ($statement:expr, $rows:expr, $elapsed:expr) => {{
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            statement = %$statement,
+            rows = $rows,
+            elapsed_ms = $elapsed.as_millis() as u64,
+            "statement executed");
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (&$statement, &$rows, &$elapsed);
+        }
+    }};
+}
+
+/// Emits a `WARN` event of a statement slower than the threshold with the `statement`, `elapsed_ms`,
+/// `threshold_ms` and `parameters` (redacted summary) fields and an EXPLAIN hint.
+macro_rules! slow_statement_event {
+    ($statement:expr, $elapsed:expr, $threshold:expr, $parameters:expr) => {{
+        #[cfg(feature = "tracing")]
+        {
+            let statement = $statement;
+            tracing::warn!(
+                statement = %statement,
+                elapsed_ms = $elapsed.as_millis() as u64,
+                threshold_ms = $threshold.as_millis() as u64,
+                parameters = %$parameters,
+                "slow statement; run `EXPLAIN (ANALYZE, BUFFERS) {}` to inspect the plan",
+                statement);
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            let _ = (&$statement, &$elapsed, &$threshold, &$parameters);
+        }
+    }};
+}
+
+pub(crate) use slow_statement_event;
+pub(crate) use statement_event;
+pub(crate) use warn_event;
@@ -1,14 +1,18 @@
 #[cfg(test)]
 mod tests_curd {
     use std::str::FromStr;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use chrono::NaiveDate;
     use testcontainers::clients::Cli;
     use testcontainers::core::WaitFor;
     use testcontainers::{Container, GenericImage};
     use tokio::fs;
     use tokio_postgres::NoTls;
+    use safety_postgres::executor::audit::AuditLog;
     use safety_postgres::legacy::conditions::{Conditions, IsInJoinedTable};
     use safety_postgres::legacy::conditions::IsInJoinedTable::No;
+    use safety_postgres::legacy::errors::PostgresBaseError;
     use safety_postgres::legacy::join_tables::JoinTables;
     use safety_postgres::legacy::postgres::PostgresBase;
     use safety_postgres::legacy::sql_base::{InsertRecords, QueryColumns, UpdateSets};
@@ -119,6 +123,35 @@ mod tests_curd {
         assert_eq!(query_result[0].len(), 16);
     }
 
+    #[tokio::test]
+    async fn test_select_schema_json() {
+        let docker = Cli::default();
+        let node = test_data_creation(&docker).await.unwrap();
+
+        let port  = node.get_host_port_ipv4(5432);
+
+        set_env(port);
+
+        let mut postgres = PostgresBase::new("users").unwrap();
+        postgres.set_schema("test_schema");
+        postgres.connect().await.unwrap();
+
+        let mut query_columns = QueryColumns::new(false);
+        query_columns.add_column("", "", "id").unwrap();
+        query_columns.add_column("", "", "username").unwrap();
+        let mut conditions = Conditions::new();
+        conditions.add_condition_from_str("id", "0", "lt", "", IsInJoinedTable::No).unwrap();
+
+        let json = postgres.query_condition_schema_json(&query_columns, &conditions).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["columns"], serde_json::json!([
+            {"name": "id", "type": "int4"},
+            {"name": "username", "type": "varchar"},
+        ]));
+        assert_eq!(value["data"], serde_json::json!([]));
+    }
+
     #[tokio::test]
     async fn test_insert() {
         let docker = Cli::default();
@@ -141,7 +174,8 @@ mod tests_curd {
         let record = vec!["2", "2023-09-28", "2", "3.8", "inserted_record"];
         insert_records.add_record(&record).unwrap();
 
-        let _ = postgres.insert(&insert_records).await.unwrap();
+        let inserted = postgres.insert(&insert_records).await.unwrap();
+        assert_eq!(inserted, 1);
         let after_query = postgres.query_raw(&query_columns);
 
         let mut conditions = Conditions::new();
@@ -175,6 +209,32 @@ mod tests_curd {
         assert_eq!(message, "inserted_record");
     }
 
+    #[tokio::test]
+    async fn test_insert_unique_violation() {
+        let docker = Cli::default();
+        let node = test_data_creation(&docker).await.unwrap();
+
+        let port  = node.get_host_port_ipv4(5432);
+
+        set_env(port);
+
+        let mut postgres = PostgresBase::new("records").unwrap();
+        postgres.set_schema("test_schema");
+        postgres.connect().await.unwrap();
+
+        let record_columns = vec!["id", "user_id", "record_date", "subcategory_id", "work_time", "message_comment"];
+        let mut insert_records = InsertRecords::new(&record_columns);
+        insert_records.add_record(&["1", "2", "2023-09-28", "2", "3.8", "duplicated_record"]).unwrap();
+
+        let e = postgres.insert(&insert_records).await.unwrap_err();
+        assert!(e.is_unique_violation());
+        assert!(!e.is_foreign_key_violation());
+        assert_eq!(e.constraint_name(), Some("records_pkey"));
+        let snapshot = e.get_database_error().unwrap().get_statement_snapshot().unwrap();
+        assert!(snapshot.get_statement().starts_with("INSERT INTO"));
+        assert_eq!(snapshot.get_parameters()[0], "int4(1)");
+    }
+
     #[tokio::test]
     async fn test_update() {
         let docker = Cli::default();
@@ -201,7 +261,8 @@ mod tests_curd {
 
         let mut update_set = UpdateSets::new();
         update_set.add_set("work_time", "2.9").unwrap();
-        postgres.update_condition(&update_set, &conditions).await.unwrap();
+        let updated = postgres.update_condition(&update_set, &conditions).await.unwrap();
+        assert_eq!(updated, 1);
 
         let updated_records = postgres.query_condition_raw(&query_column, &conditions).await.unwrap();
         assert_eq!(updated_records.len(), 1);
@@ -230,10 +291,77 @@ mod tests_curd {
 
         let mut condition = Conditions::new();
         condition.add_condition_from_str("user_id", "1", "eq", "", No).unwrap();
-        let _ = postgres.delete(&condition).await.unwrap();
+        let deleted = postgres.delete(&condition).await.unwrap();
+        assert_eq!(deleted, 5);
 
         let updated_records = postgres.query_raw(&query_column).await.unwrap();
 
         assert_eq!(updated_records.len(), 11);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_delete_max_affected() {
+        let docker = Cli::default();
+        let node = test_data_creation(&docker).await.unwrap();
+
+        let port  = node.get_host_port_ipv4(5432);
+
+        set_env(port);
+
+        let mut postgres = PostgresBase::new("records").unwrap();
+        postgres.set_schema("test_schema");
+        postgres.set_max_affected(Some(4));
+        postgres.connect().await.unwrap();
+
+        let mut condition = Conditions::new();
+        condition.add_condition_from_str("user_id", "1", "eq", "", No).unwrap();
+        match postgres.delete(&condition).await {
+            Err(PostgresBaseError::UnsafeExecutionError(_)) => {},
+            other => panic!("delete over the limit should be refused but got {:?}", other),
+        }
+
+        let query_column = QueryColumns::new(true);
+        let records = postgres.query_raw(&query_column).await.unwrap();
+        assert_eq!(records.len(), 16);
+
+        postgres.set_max_affected(Some(5));
+        postgres.delete(&condition).await.unwrap();
+
+        let records = postgres.query_raw(&query_column).await.unwrap();
+        assert_eq!(records.len(), 11);
+    }
+
+    #[tokio::test]
+    async fn test_returning_audited_once() {
+        let docker = Cli::default();
+        let node = test_data_creation(&docker).await.unwrap();
+
+        let port  = node.get_host_port_ipv4(5432);
+
+        set_env(port);
+
+        let audit_count = Arc::new(AtomicUsize::new(0));
+        let counter = audit_count.clone();
+        let mut postgres = PostgresBase::new("records").unwrap();
+        postgres.set_schema("test_schema");
+        postgres.set_audit_log(Some(AuditLog::to_callback(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })));
+        postgres.connect().await.unwrap();
+
+        let mut conditions = Conditions::new();
+        conditions.add_condition_from_str("id", "1", "eq", "", No).unwrap();
+        let mut update_set = UpdateSets::new();
+        update_set.add_set("work_time", "2.9").unwrap();
+        let updated = postgres.update_returning(&update_set, &conditions, &QueryColumns::new(true)).await.unwrap();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(audit_count.load(Ordering::SeqCst), 1);
+
+        let deleted = postgres.delete_returning(&conditions, &QueryColumns::new(true)).await.unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(audit_count.load(Ordering::SeqCst), 2);
+
+        postgres.query_raw(&QueryColumns::new(true)).await.unwrap();
+        assert_eq!(audit_count.load(Ordering::SeqCst), 3);
+    }
+}